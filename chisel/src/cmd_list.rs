@@ -0,0 +1,56 @@
+//! Module listing implementation.
+//! The main entry point is chisel_list, which prints every module known to the driver along with
+//! its kind and whether it requires a preset.
+
+use libchisel::ModuleKind;
+
+use chisel::driver::{ModuleInfo, MODULES};
+
+/// Print the available modules, one per line.
+pub fn chisel_list() -> i32 {
+    for line in MODULES.iter().map(format_module) {
+        println!("{}", line);
+    }
+    0
+}
+
+fn format_module(info: &ModuleInfo) -> String {
+    let kind = match info.kind {
+        ModuleKind::Creator => "creator",
+        ModuleKind::Translator => "translator",
+        ModuleKind::Validator => "validator",
+    };
+    let preset = if info.requires_preset {
+        "requires preset"
+    } else {
+        ""
+    };
+    format!("{:<24}{:<12}{}", info.name, kind, preset)
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifyimports_listed() {
+        let line = MODULES
+            .iter()
+            .find(|info| info.name == "verifyimports")
+            .map(format_module)
+            .expect("verifyimports must be listed");
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(
+            columns,
+            vec!["verifyimports", "validator", "requires", "preset"]
+        );
+    }
+
+    #[test]
+    fn translator_without_preset() {
+        let info = MODULES.iter().find(|info| info.name == "repack").unwrap();
+        assert_eq!(format_module(info), "repack                  translator");
+    }
+}
@@ -4,12 +4,15 @@
 //! Like config-driven mode, it then passes the config to the driver, executes, and writes
 //! output to the specified file (or stdout, if no file is specified).
 
-use crate::config::ChiselConfig;
-use crate::config::FromArgs;
-use crate::driver::{ChiselDriver, DriverState};
+use std::io;
+
+use chisel::config::ChiselConfig;
+use chisel::config::FromArgs;
+use chisel::driver::{ChiselDriver, DriverState};
+use chisel::logger;
+use chisel::options::ChiselFlags;
+
 use crate::fail;
-use crate::logger;
-use crate::options::ChiselFlags;
 
 pub fn chisel_oneliner(flags: ChiselFlags) -> i32 {
     let log_level = match flags.value_of("util.debugging") {
@@ -33,11 +36,17 @@ pub fn chisel_oneliner(flags: ChiselFlags) -> i32 {
                 ""
             };
 
-            let input_file = flags
-                .value_of("oneliner.file")
-                .unwrap_or_else(|| fail(1, "No file specified"));
+            // Without a file, the module is read from stdin if it is piped in.
+            let input_file = match flags.value_of("oneliner.file") {
+                Some(file) => file,
+                None if !atty::is(atty::Stream::Stdin) => "-",
+                None => fail(1, "No file specified"),
+            };
 
+            let json_mode = flags.value_eq("output.mode", "json");
+            let quiet = flags.value_eq("util.quiet", "true");
             let output_file = flags.value_of("oneliner.output");
+            let has_output_file = output_file.is_some();
             let output_file = match output_file {
                 Some(p) => p.to_string(),
                 None => "/dev/stdout".to_string(),
@@ -62,6 +71,10 @@ pub fn chisel_oneliner(flags: ChiselFlags) -> i32 {
             chisel_debug!(1, "{}", chisel_config);
 
             let mut driver = ChiselDriver::new(chisel_config);
+            driver.set_strict_inplace(flags.value_eq("util.strictinplace", "true"));
+            driver.set_dry_run(flags.value_eq("util.dryrun", "true"));
+            driver.set_stats(flags.value_eq("util.stats", "true"));
+            driver.set_diff(flags.value_eq("util.diff", "true"));
 
             loop {
                 match driver.fire() {
@@ -76,7 +89,41 @@ pub fn chisel_oneliner(flags: ChiselFlags) -> i32 {
             let mut results = driver.take_result();
             // wish list: write yaml-encoded results to stdout
             chisel_debug!(1, "Module execution completed successfully");
-            eprintln!("{}", &results);
+            if json_mode {
+                // Stdout carries the JSON results instead of the module in this mode.
+                println!("{}", results.to_json());
+            } else {
+                results
+                    .write_summary(&mut io::stderr(), quiet)
+                    .expect("Failed to write the summary to stderr");
+            }
+            if let Some(report_path) = flags.value_of("util.report") {
+                if let Err(e) = results.write_report(report_path) {
+                    fail(1, &format!("failed to write report to file: {}", e));
+                }
+            }
+
+            // In strict mode, failed modules are reported and fail the process once output is
+            // written.
+            let exit_code = if flags.value_eq("util.strict", "true") {
+                let (code, failures) = results.strict_exit_code();
+                if !failures.is_empty() {
+                    eprintln!(
+                        "strict mode: {} failure(s): {}",
+                        failures.len(),
+                        failures.join(", ")
+                    );
+                }
+                code
+            } else {
+                0
+            };
+            if flags.value_eq("util.dryrun", "true") {
+                if !quiet {
+                    eprintln!("Dry run; not writing output.");
+                }
+                return exit_code;
+            }
 
             // Get ruleset
             let results = results.rulesets_mut();
@@ -93,10 +140,21 @@ pub fn chisel_oneliner(flags: ChiselFlags) -> i32 {
                     let mut result = results.pop().expect("One ruleset was executed");
                     result.write("hex")
                 }
+                Some("json") if !has_output_file => {
+                    if !quiet {
+                        eprintln!("No output file specified; not writing output in json mode.");
+                    }
+                    return exit_code;
+                }
+                Some("json") => {
+                    let mut result = results.pop().expect("One ruleset was executed");
+                    result.write("bin")
+                }
                 _ => panic!("CLI parser ensures value can only be one of the above"),
             };
 
             match io_result {
+                Ok(_) if quiet => (),
                 Ok(true) => eprintln!("Successfully wrote output to file."),
                 Ok(false) => eprintln!("No changes to write."),
                 Err(e) => fail(
@@ -104,7 +162,7 @@ pub fn chisel_oneliner(flags: ChiselFlags) -> i32 {
                     &format!("failed to write output to file: {}", e.description()),
                 ),
             }
-            0
+            exit_code
         }
         None => fail(1, "no modules specified"),
     }
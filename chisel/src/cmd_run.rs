@@ -6,16 +6,20 @@
 
 use std::error::Error;
 use std::fs::read_to_string;
+use std::io;
 
 use serde_yaml::Value;
 
-use crate::config::ChiselConfig;
-use crate::config::FromYaml;
-use crate::driver::ChiselDriver;
-use crate::driver::DriverState;
+use chisel::config::ChiselConfig;
+use chisel::config::FromJson;
+use chisel::config::FromToml;
+use chisel::config::FromYaml;
+use chisel::driver::ChiselDriver;
+use chisel::driver::DriverState;
+use chisel::logger;
+use chisel::options::ChiselFlags;
+
 use crate::fail;
-use crate::logger;
-use crate::options::ChiselFlags;
 
 /// Execute chisel in config-driven mode.
 pub fn chisel_run(flags: ChiselFlags) -> i32 {
@@ -68,15 +72,31 @@ pub fn chisel_run(flags: ChiselFlags) -> i32 {
         ),
     };
 
-    let yaml_parsed = serde_yaml::from_str::<Value>(&config).unwrap_or_else(|e| {
-        fail(
-            1,
-            &format!("failed to parse configuration: {}", e.description()),
-        )
-    });
+    // JSON is used if requested, or if the configuration file has a .json extension. TOML is
+    // used for a .toml extension, and YAML otherwise.
+    let config_result =
+        if flags.value_eq("run.config.json", "true") || config_path.ends_with(".json") {
+            chisel_debug!(1, "Parsing configuration as JSON");
+            let json_parsed = serde_json::from_str::<serde_json::Value>(&config)
+                .unwrap_or_else(|e| fail(1, &format!("failed to parse configuration: {}", e)));
+            ChiselConfig::from_json(&json_parsed)
+        } else if config_path.ends_with(".toml") {
+            chisel_debug!(1, "Parsing configuration as TOML");
+            let toml_parsed = toml::from_str::<toml::Value>(&config)
+                .unwrap_or_else(|e| fail(1, &format!("failed to parse configuration: {}", e)));
+            ChiselConfig::from_toml(&toml_parsed)
+        } else {
+            let yaml_parsed = serde_yaml::from_str::<Value>(&config).unwrap_or_else(|e| {
+                fail(
+                    1,
+                    &format!("failed to parse configuration: {}", e.description()),
+                )
+            });
+            ChiselConfig::from_yaml(&yaml_parsed)
+        };
 
-    // Validate basic properties of the YAML configuration.
-    let chisel_config = match ChiselConfig::from_yaml(&yaml_parsed) {
+    // Validate basic properties of the configuration.
+    let chisel_config = match config_result {
         Ok(config) => {
             chisel_debug!(1, "Successfully processed configuration");
             config
@@ -85,6 +105,10 @@ pub fn chisel_run(flags: ChiselFlags) -> i32 {
     };
 
     let mut driver = ChiselDriver::new(chisel_config);
+    driver.set_strict_inplace(flags.value_eq("util.strictinplace", "true"));
+    driver.set_dry_run(flags.value_eq("util.dryrun", "true"));
+    driver.set_stats(flags.value_eq("util.stats", "true"));
+    driver.set_diff(flags.value_eq("util.diff", "true"));
 
     loop {
         match driver.fire() {
@@ -107,38 +131,57 @@ pub fn chisel_run(flags: ChiselFlags) -> i32 {
     // After execution, get results and write output.
     let mut results = driver.take_result();
     chisel_debug!(1, "Module execution completed successfully");
-    eprintln!("{}", &results);
-    results
-        .rulesets_mut()
-        .iter_mut()
-        .map(|ruleset| {
-            let ruleset_name = ruleset.name().to_string();
-            let write_result = match flags.value_of("output.mode") {
-                Some("bin") => ruleset.write("bin"),
-                Some("wat") => ruleset.write("wat"),
-                Some("hex") => ruleset.write("hex"),
-                _ => panic!("CLI parser ensures value can only be one of the above"),
-            };
-            (ruleset_name, write_result)
-        })
-        .filter_map(|(name, result)| {
-            if result.is_err() {
-                Some((name, result.unwrap_err()))
-            } else {
-                if result.expect("Errors sorted out already") {
-                    eprintln!("Ruleset {}: wrote output to file successfully.", name);
-                } else {
-                    eprintln!("Ruleset {}: no changes made to be written.", name);
-                }
-                None
-            }
-        })
-        .for_each(|(name, error)| {
+    let quiet = flags.value_eq("util.quiet", "true");
+    if flags.value_eq("output.mode", "json") {
+        println!("{}", results.to_json());
+    } else {
+        results
+            .write_summary(&mut io::stderr(), quiet)
+            .expect("Failed to write the summary to stderr");
+    }
+    if let Some(report_path) = flags.value_of("util.report") {
+        if let Err(e) = results.write_report(report_path) {
+            fail(1, &format!("failed to write report to file: {}", e));
+        }
+    }
+    // In strict mode, failed modules are reported and fail the process once output is written.
+    let exit_code = if flags.value_eq("util.strict", "true") {
+        let (code, failures) = results.strict_exit_code();
+        if !failures.is_empty() {
             eprintln!(
+                "strict mode: {} failure(s): {}",
+                failures.len(),
+                failures.join(", ")
+            );
+        }
+        code
+    } else {
+        0
+    };
+    if flags.value_eq("util.dryrun", "true") {
+        if !quiet {
+            eprintln!("Dry run; not writing output.");
+        }
+        return exit_code;
+    }
+    // Each ruleset writes to its own output, in its own output mode if it sets one.
+    let default_mode = match flags.value_of("output.mode") {
+        Some("bin") | Some("json") => "bin",
+        Some("wat") => "wat",
+        Some("hex") => "hex",
+        _ => panic!("CLI parser ensures value can only be one of the above"),
+    };
+    for (name, result) in results.write_all(default_mode) {
+        match result {
+            Ok(_) if quiet => (),
+            Ok(true) => eprintln!("Ruleset {}: wrote output to file successfully.", name),
+            Ok(false) => eprintln!("Ruleset {}: no changes made to be written.", name),
+            Err(error) => eprintln!(
                 "failed to write output from ruleset {} to file: {}",
                 name,
                 error.description()
-            )
-        });
-    0
+            ),
+        }
+    }
+    exit_code
 }
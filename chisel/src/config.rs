@@ -1,6 +1,7 @@
 //! Configuration utilities for chisel modules.
 //! The two main traits are FromYaml and FromArgs, for config-driven and oneliner mode
-//! respectively.
+//! respectively. FromJson and FromToml are provided for config-driven mode with a JSON or TOML
+//! configuration of the same shape.
 //! This module implements the ChiselConfig structure, which contains the information for a number
 //! of chisel executions.
 //! A ChiselConfig contains a list of ruleset configurations and their names.
@@ -9,14 +10,48 @@
 
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::error::Error;
 use std::fmt::{self, Display};
 
 use serde_yaml::Value;
 
+/// An error encountered while generating a configuration.
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    /// The top-level value is not a mapping of rulesets.
+    NotAMapping,
+    /// A ruleset is not a string-map pair.
+    MalformedRuleset,
+    /// The contained ruleset name appears more than once.
+    DuplicateRuleset(String),
+    /// The contained CLI option is not of the form `module.field=value`.
+    MalformedOption(String),
+    /// A CLI option was given for the contained module, which is not selected.
+    UnknownModule(String),
+    /// A JSON or TOML configuration could not be converted. Contains the conversion error.
+    Conversion(String),
+}
+
 /// Main trait for generating a configuration usable by the driver from deserialized YAML.
 pub trait FromYaml {
     // yaml: top-level yaml element generated by parser
-    fn from_yaml(yaml: &Value) -> Result<Self, String>
+    fn from_yaml(yaml: &Value) -> Result<Self, ConfigError>
+    where
+        Self: Sized;
+}
+
+/// Trait for generating a configuration usable by the driver from deserialized JSON.
+pub trait FromJson {
+    // json: top-level json element generated by parser
+    fn from_json(json: &serde_json::Value) -> Result<Self, ConfigError>
+    where
+        Self: Sized;
+}
+
+/// Trait for generating a configuration usable by the driver from deserialized TOML.
+pub trait FromToml {
+    // toml: top-level toml table generated by parser
+    fn from_toml(toml: &toml::Value) -> Result<Self, ConfigError>
     where
         Self: Sized;
 }
@@ -26,7 +61,7 @@ pub trait FromArgs {
     // Supported formatting example:
     // modules: "remapimports, trimexports, verifyimports"
     // options: "remapimports.preset=ewasm, trimexport.option=foo, verifyimports.a=b"
-    fn from_args(modules: &str, options: &str) -> Result<Self, String>
+    fn from_args(modules: &str, options: &str) -> Result<Self, ConfigError>
     where
         Self: Sized;
 }
@@ -84,10 +119,19 @@ impl ModuleConfig {
     pub fn options(&self) -> &HashMap<String, String> {
         &self.0
     }
+
+    /// Returns false if the module was disabled with `enabled: false`. Modules are enabled by
+    /// default.
+    pub fn enabled(&self) -> bool {
+        match self.0.get("enabled") {
+            Some(value) => value != "false",
+            None => true,
+        }
+    }
 }
 
 impl FromArgs for ChiselConfig {
-    fn from_args(modules: &str, options: &str) -> Result<Self, String>
+    fn from_args(modules: &str, options: &str) -> Result<Self, ConfigError>
     where
         Self: Sized,
     {
@@ -103,7 +147,7 @@ impl FromArgs for ChiselConfig {
 }
 
 impl FromArgs for Ruleset {
-    fn from_args(modules: &str, options: &str) -> Result<Self, String>
+    fn from_args(modules: &str, options: &str) -> Result<Self, ConfigError>
     where
         Self: Sized,
     {
@@ -131,6 +175,16 @@ impl FromArgs for Ruleset {
             })
             .collect();
 
+        // Reject options of the form "module.field=value" for a module which is not selected.
+        for option in module_options.iter() {
+            if let Some(dot) = option.find('.') {
+                let module = &option[..dot];
+                if !module.contains('=') && !module_names.iter().any(|name| name == module) {
+                    return Err(ConfigError::UnknownModule(module.to_string()));
+                }
+            }
+        }
+
         let mut module_configs = VecDeque::new();
         // Iterate over each module and generate a config from its provided options.
         for name in module_names.iter() {
@@ -162,8 +216,8 @@ impl FromArgs for Ruleset {
                 let keyval_pair: String =
                     option.chars().skip_while(|c| *c != '.').skip(1).collect();
 
-                // Split into its terms.
-                let keyval_pair: Vec<&str> = keyval_pair.split('=').collect();
+                // Split on the first equals sign only, so that values may contain '='.
+                let keyval_pair: Vec<&str> = keyval_pair.splitn(2, '=').collect();
 
                 if keyval_pair.len() == 2 {
                     chisel_debug!(
@@ -173,12 +227,17 @@ impl FromArgs for Ruleset {
                         &keyval_pair[0],
                         &keyval_pair[1]
                     );
-                    options_final.insert(keyval_pair[0].to_string(), keyval_pair[1].to_string());
+                    // Repeated keys are collected into a comma-separated list, as commas cannot
+                    // appear in a single value.
+                    options_final
+                        .entry(keyval_pair[0].to_string())
+                        .and_modify(|value: &mut String| {
+                            value.push(',');
+                            value.push_str(keyval_pair[1]);
+                        })
+                        .or_insert_with(|| keyval_pair[1].to_string());
                 } else {
-                    return Err(format!(
-                        "In module {}: option {} malformed: unexpected equals sign",
-                        name, keyval_pair[0]
-                    ));
+                    return Err(ConfigError::MalformedOption(option));
                 }
             }
 
@@ -197,13 +256,13 @@ impl FromArgs for Ruleset {
 }
 
 impl FromYaml for ChiselConfig {
-    fn from_yaml(yaml: &Value) -> Result<Self, String>
+    fn from_yaml(yaml: &Value) -> Result<Self, ConfigError>
     where
         Self: Sized,
     {
         // Ensure that the first value in the config is a mapping representing rulesets.
         if !yaml.is_mapping() {
-            return Err("Top-level value is not a mapping".to_string());
+            return Err(ConfigError::NotAMapping);
         }
 
         let rulesets = yaml.as_mapping().expect("Already validated");
@@ -219,27 +278,49 @@ impl FromYaml for ChiselConfig {
             .iter()
             .any(|(key, val)| !key.is_string() || !val.is_mapping())
         {
-            return Err(
-                "Malformed ruleset; expected all rulesets to be string-map pairs".to_string(),
-            );
+            return Err(ConfigError::MalformedRuleset);
         }
 
-        let rulesets: VecDeque<(String, Ruleset)> = rulesets
-            .iter()
-            .map(|(key, val)| {
-                (
-                    key.as_str().expect("Already validated").to_string(),
-                    Ruleset::from_yaml(val).expect("No failure cases yet"),
-                )
-            })
-            .collect();
+        let mut ret: VecDeque<(String, Ruleset)> = VecDeque::new();
+        for (key, val) in rulesets.iter() {
+            let name = key.as_str().expect("Already validated").to_string();
+            if ret.iter().any(|(existing, _)| *existing == name) {
+                return Err(ConfigError::DuplicateRuleset(name));
+            }
+            ret.push_back((name, Ruleset::from_yaml(val)?));
+        }
+
+        Ok(ChiselConfig(ret))
+    }
+}
 
-        Ok(ChiselConfig(rulesets))
+impl FromJson for ChiselConfig {
+    fn from_json(json: &serde_json::Value) -> Result<Self, ConfigError>
+    where
+        Self: Sized,
+    {
+        // The JSON configuration has the same shape as the YAML one, so convert and reuse the
+        // YAML handling.
+        let yaml =
+            serde_yaml::to_value(json).map_err(|e| ConfigError::Conversion(e.to_string()))?;
+        ChiselConfig::from_yaml(&yaml)
+    }
+}
+
+impl FromToml for ChiselConfig {
+    fn from_toml(toml: &toml::Value) -> Result<Self, ConfigError>
+    where
+        Self: Sized,
+    {
+        // Rulesets and modules are TOML tables, so the configuration maps onto the YAML shape.
+        let yaml =
+            serde_yaml::to_value(toml).map_err(|e| ConfigError::Conversion(e.to_string()))?;
+        ChiselConfig::from_yaml(&yaml)
     }
 }
 
 impl FromYaml for Ruleset {
-    fn from_yaml(yaml: &Value) -> Result<Self, String>
+    fn from_yaml(yaml: &Value) -> Result<Self, ConfigError>
     where
         Self: Sized,
     {
@@ -269,7 +350,7 @@ impl FromYaml for Ruleset {
 }
 
 impl FromYaml for ModuleConfig {
-    fn from_yaml(yaml: &Value) -> Result<Self, String>
+    fn from_yaml(yaml: &Value) -> Result<Self, ConfigError>
     where
         Self: Sized,
     {
@@ -389,6 +470,32 @@ impl Display for ModuleConfig {
     }
 }
 
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NotAMapping => write!(f, "top-level value is not a mapping"),
+            ConfigError::MalformedRuleset => write!(
+                f,
+                "malformed ruleset; expected all rulesets to be string-map pairs"
+            ),
+            ConfigError::DuplicateRuleset(name) => write!(f, "duplicate ruleset '{}'", name),
+            ConfigError::MalformedOption(option) => {
+                write!(f, "option '{}' malformed: missing equals sign", option)
+            }
+            ConfigError::UnknownModule(module) => {
+                write!(
+                    f,
+                    "option given for module '{}', which is not selected",
+                    module
+                )
+            }
+            ConfigError::Conversion(message) => write!(f, "conversion failed: {}", message),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
 #[cfg(test)]
 mod tests {
     use serde_yaml::Mapping;
@@ -479,10 +586,29 @@ mod tests {
     }
 
     #[test]
-    fn from_args_toomanyequals() {
-        let config = ChiselConfig::from_args("test", "test.preset=foo=bar");
+    fn from_args_equals_in_value() {
+        let config = ChiselConfig::from_args("test", "test.payload=aGVsbG8=,test.path=a=b=c")
+            .expect("Should be valid");
+        let options = config.rulesets()[0].1.modules()[0].1.options();
+        assert_eq!(options.get("payload").expect("Should be Some"), "aGVsbG8=");
+        assert_eq!(options.get("path").expect("Should be Some"), "a=b=c");
+    }
+
+    #[test]
+    fn from_args_multiple_keys() {
+        let config = ChiselConfig::from_args(
+            "test,test1",
+            "test.removed=1,test.preset=foo,test1.removed=3,test.removed=2",
+        )
+        .expect("Should be valid");
 
-        assert!(config.is_err());
+        let options = config.rulesets()[0].1.modules()[0].1.options();
+        assert_eq!(options.len(), 2);
+        assert_eq!(options.get("removed").expect("Should be Some"), "1,2");
+        assert_eq!(options.get("preset").expect("Should be Some"), "foo");
+
+        let options = config.rulesets()[0].1.modules()[1].1.options();
+        assert_eq!(options.get("removed").expect("Should be Some"), "3");
     }
 
     #[test]
@@ -531,12 +657,35 @@ mod tests {
         assert!(config.is_err());
     }
 
+    #[test]
+    fn from_args_missing_equals() {
+        let config = ChiselConfig::from_args("test", "test.preset");
+        let error = config.expect_err("Should be malformed");
+        assert_eq!(
+            error,
+            ConfigError::MalformedOption("test.preset".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            "option 'test.preset' malformed: missing equals sign"
+        );
+    }
+
+    #[test]
+    fn from_args_unselected_module() {
+        let config = ChiselConfig::from_args("remapimports", "remapimport.preset=ewasm");
+        assert_eq!(
+            config.expect_err("Should be rejected"),
+            ConfigError::UnknownModule("remapimport".to_string())
+        );
+    }
+
     #[test]
     fn yaml_not_mapping() {
         let yaml = Value::String("foobar".to_string());
 
         let config = ChiselConfig::from_yaml(&yaml);
-        assert!(config.is_err());
+        assert_eq!(config.expect_err("Must fail"), ConfigError::NotAMapping);
     }
 
     #[test]
@@ -567,4 +716,85 @@ mod tests {
         let config = ChiselConfig::from_yaml(&yaml);
         assert!(config.is_err());
     }
+
+    #[test]
+    fn from_json_same_as_yaml() {
+        let json =
+            r#"{"ruleset": {"file": "test.wasm", "test": {"preset": "ewasm"}, "test2": null}}"#;
+        let yaml = "ruleset:\n  file: \"test.wasm\"\n  test:\n    preset: \"ewasm\"\n  test2:\n";
+
+        let json_config =
+            ChiselConfig::from_json(&serde_json::from_str(json).unwrap()).expect("Should not fail");
+        let yaml_config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        assert_eq!(json_config.rulesets().len(), 1);
+        assert_eq!(yaml_config.rulesets().len(), 1);
+
+        let (json_name, json_ruleset) = &json_config.rulesets()[0];
+        let (yaml_name, yaml_ruleset) = &yaml_config.rulesets()[0];
+        assert_eq!(json_name, yaml_name);
+        assert_eq!(json_ruleset.options(), yaml_ruleset.options());
+
+        let json_modules: Vec<(&String, &HashMap<String, String>)> = json_ruleset
+            .modules()
+            .iter()
+            .map(|(name, module)| (name, module.options()))
+            .collect();
+        let yaml_modules: Vec<(&String, &HashMap<String, String>)> = yaml_ruleset
+            .modules()
+            .iter()
+            .map(|(name, module)| (name, module.options()))
+            .collect();
+        assert_eq!(json_modules, yaml_modules);
+        assert_eq!(json_modules[0].0, "test");
+        assert_eq!(json_modules[1].0, "test2");
+    }
+
+    #[test]
+    fn json_not_mapping() {
+        let json = serde_json::Value::String("foo".to_string());
+        let config = ChiselConfig::from_json(&json);
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn from_toml_same_as_yaml() {
+        let toml = "[ruleset]\nfile = \"test.wasm\"\noutput = \"out.wasm\"\n\n[ruleset.test]\npreset = \"ewasm\"\nenabled = true\n\n[ruleset.test2]\n";
+        let yaml = "ruleset:\n  file: \"test.wasm\"\n  output: \"out.wasm\"\n  test:\n    preset: \"ewasm\"\n    enabled: true\n  test2:\n";
+
+        let toml_config =
+            ChiselConfig::from_toml(&toml::from_str(toml).unwrap()).expect("Should not fail");
+        let yaml_config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        assert_eq!(toml_config.rulesets().len(), 1);
+        assert_eq!(yaml_config.rulesets().len(), 1);
+
+        let (toml_name, toml_ruleset) = &toml_config.rulesets()[0];
+        let (yaml_name, yaml_ruleset) = &yaml_config.rulesets()[0];
+        assert_eq!(toml_name, yaml_name);
+        assert_eq!(toml_ruleset.options(), yaml_ruleset.options());
+
+        let toml_modules: Vec<(&String, &HashMap<String, String>)> = toml_ruleset
+            .modules()
+            .iter()
+            .map(|(name, module)| (name, module.options()))
+            .collect();
+        let yaml_modules: Vec<(&String, &HashMap<String, String>)> = yaml_ruleset
+            .modules()
+            .iter()
+            .map(|(name, module)| (name, module.options()))
+            .collect();
+        assert_eq!(toml_modules, yaml_modules);
+        assert_eq!(toml_modules[0].0, "test");
+        assert_eq!(toml_modules[1].0, "test2");
+    }
+
+    #[test]
+    fn toml_ruleset_not_table() {
+        let toml = toml::from_str("ruleset = \"foo\"").unwrap();
+        let config = ChiselConfig::from_toml(&toml);
+        assert!(config.is_err());
+    }
 }
@@ -0,0 +1,131 @@
+//! Section-by-section comparison of the module a ruleset was given and the module it produced.
+//! Sections are matched by name, in module order, so that repeated custom sections of the same
+//! name are compared pairwise.
+
+use libchisel::{stats::module_stats, Module};
+
+/// A section which differs between two modules. Sizes are serialized sizes in bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SectionDiff {
+    /// A section only present in the second module.
+    Added(String, usize),
+    /// A section only present in the first module.
+    Removed(String, usize),
+    /// A section present in both modules with a different size. Left-hand is the size before.
+    Resized(String, usize, usize),
+}
+
+impl SectionDiff {
+    pub fn name(&self) -> &str {
+        match self {
+            SectionDiff::Added(name, _)
+            | SectionDiff::Removed(name, _)
+            | SectionDiff::Resized(name, _, _) => name,
+        }
+    }
+
+    /// Returns the size of the section before and after, if it was present.
+    pub fn sizes(&self) -> (Option<usize>, Option<usize>) {
+        match self {
+            SectionDiff::Added(_, after) => (None, Some(*after)),
+            SectionDiff::Removed(_, before) => (Some(*before), None),
+            SectionDiff::Resized(_, before, after) => (Some(*before), Some(*after)),
+        }
+    }
+}
+
+/// Compares the sections of two modules. Removed and resized sections are listed in the order of
+/// `before`, followed by the added sections in the order of `after`. Unchanged sections are
+/// omitted.
+pub fn diff_modules(before: &Module, after: &Module) -> Vec<SectionDiff> {
+    let before = module_stats(before).section_sizes;
+    let mut after: Vec<Option<(String, usize)>> = module_stats(after)
+        .section_sizes
+        .into_iter()
+        .map(Some)
+        .collect();
+
+    let mut ret = Vec::new();
+    for (name, size_before) in before {
+        let matched = after
+            .iter_mut()
+            .find(|section| matches!(section, Some((other, _)) if *other == name))
+            .and_then(Option::take);
+        match matched {
+            Some((_, size_after)) if size_after != size_before => {
+                ret.push(SectionDiff::Resized(name, size_before, size_after))
+            }
+            Some(_) => (),
+            None => ret.push(SectionDiff::Removed(name, size_before)),
+        }
+    }
+    ret.extend(
+        after
+            .into_iter()
+            .flatten()
+            .map(|(name, size)| SectionDiff::Added(name, size)),
+    );
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use libchisel::{dropsection::DropSection, ModuleTranslator};
+
+    use super::*;
+
+    #[test]
+    fn dropnames_removes_names_section() {
+        // wast:
+        // (module
+        //   (import "env" "ethereum_useGas" (func (param i64)))
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main2)
+        //   (func $main)
+        //   (start $main2)
+        // )
+        let wasm = hex::decode(
+            "0061736d0100000001080260017e0060000002170103656e760f657468657265756d5f7573654761730000\
+             0303020101050301000107110\
+             2046d61696e0001066d656d6f727902000801020a070202000b02000b0020046e616d65010e0201046d61\
+             696e02056d61696e320209030001000001000200",
+        )
+        .unwrap();
+        let before = Module::from_bytes(&wasm).unwrap().parse_names().unwrap();
+        assert!(before.names_section().is_some());
+
+        let mut after = before.clone();
+        let did_change = DropSection::NamesSection
+            .translate_inplace(&mut after)
+            .unwrap();
+        assert!(did_change);
+
+        let diff = diff_modules(&before, &after);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].name(), "name");
+        assert!(matches!(diff[0], SectionDiff::Removed(_, _)));
+    }
+
+    #[test]
+    fn added_and_resized_sections() {
+        // wast:
+        // (module
+        //   (func (export "main") nop)
+        // )
+        let before = wat::parse_str("(module (func (export \"main\") nop))").unwrap();
+        let before = Module::from_bytes(&before).unwrap();
+        let after = wat::parse_str(
+            "(module (memory 1) (func (export \"main\") nop) (func (export \"other\") nop))",
+        )
+        .unwrap();
+        let after = Module::from_bytes(&after).unwrap();
+
+        let diff = diff_modules(&before, &after);
+        let names: Vec<&str> = diff.iter().map(SectionDiff::name).collect();
+        assert_eq!(names, vec!["function", "export", "code", "memory"]);
+        assert!(matches!(diff[0], SectionDiff::Resized(_, _, _)));
+        assert!(matches!(diff[3], SectionDiff::Added(_, _)));
+    }
+}
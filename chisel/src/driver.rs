@@ -9,19 +9,80 @@
 use std::error::Error;
 use std::fmt::{self, Display};
 use std::fs::{canonicalize, read};
+use std::io::{self, stdin, Read};
 use std::path::PathBuf;
 
 #[cfg(feature = "binaryen")]
 use libchisel::binaryenopt::BinaryenOptimiser;
 use libchisel::{
-    checkfloat::CheckFloat, checkstartfunc::CheckStartFunc, deployer::Deployer,
-    dropsection::DropSection, remapimports::RemapImports, remapstart::RemapStart, repack::Repack,
-    snip::Snip, trimexports::TrimExports, trimstartfunc::TrimStartFunc,
-    verifyexports::VerifyExports, verifyimports::VerifyImports, ChiselModule, Module, ModulePreset,
-    ModuleTranslator, ModuleValidator,
+    addcustomsection::AddCustomSection,
+    available_presets,
+    canonicalize::Canonicalize,
+    checkcallbacks::CheckCallbacks,
+    checkconstoffsets::CheckConstOffsets,
+    checkdata::CheckDataBounds,
+    checkdeployregion::CheckDeployRegion,
+    checkduplicateexports::CheckDuplicateExports,
+    checkentrypoint::CheckEntryPoint,
+    checkfloat::CheckFloat,
+    checkfunccount::CheckFunctionCount,
+    checkfunctionbodies::CheckFunctionBodies,
+    checkhascode::CheckHasCode,
+    checkimportorder::CheckImportOrder,
+    checkimportsection::CheckImportSection,
+    checklocals::CheckLocals,
+    checkmemory::CheckMemory,
+    checkmemtablecount::CheckMemTableCount,
+    checkminmemory::CheckMinMemory,
+    checkmutableglobals::CheckMutableGlobals,
+    checkopcodes::CheckOpcodes,
+    checkstartfunc::CheckStartFunc,
+    checkstartnotimported::CheckStartNotImported,
+    checktable::CheckTable,
+    checktyperefs::CheckTypeRefs,
+    chiselversion::ChiselVersion,
+    convertdeployer::ConvertDeployer,
+    deadcode::DeadCode,
+    debugstubs::DebugStubs,
+    dedupetypes::DedupeTypes,
+    deployer::Deployer,
+    dropsection::DropSection,
+    ensureimports::EnsureImports,
+    fixedmemory::FixedMemory,
+    globalgetters::GlobalGetters,
+    inlineconstglobals::InlineConstGlobals,
+    padsize::PadSize,
+    profiledropnames::ProfileDropNames,
+    relocatedata::RelocateData,
+    remapimports::RemapImports,
+    remapstart::RemapStart,
+    removenops::RemoveNops,
+    renameexports::RenameExports,
+    renumberstart::RenumberStart,
+    repack::Repack,
+    roundtrip::RoundTrip,
+    setmemory::SetMemoryLimits,
+    snip::Snip,
+    sortexports::SortExports,
+    sortimports::SortImports,
+    stats::module_stats,
+    strictexports::StrictExports,
+    stub::Stub,
+    template::Template,
+    trimexports::TrimExports,
+    trimimports::TrimImports,
+    trimstartfunc::TrimStartFunc,
+    trimunreachable::TrimUnreachable,
+    unexportmemory::UnexportMemory,
+    verifyexports::VerifyExports,
+    verifyimports::{ImportStatus, VerifyImports},
+    wrapmain::WrapMain,
+    ChiselModule, Module, ModuleCreator, ModuleError, ModuleKind, ModulePreset, ModuleTranslator,
+    ModuleValidator,
 };
 
-use crate::config::{ChiselConfig, ModuleConfig};
+use crate::config::{ChiselConfig, ModuleConfig, Ruleset};
+use crate::diff::diff_modules;
 use crate::result::{ChiselResult, ModuleResult, RulesetResult};
 
 /// State machine implementing the main chisel execution loop. Consumes ChiselConfig and returns
@@ -29,6 +90,114 @@ use crate::result::{ChiselResult, ModuleResult, RulesetResult};
 pub struct ChiselDriver {
     config: ChiselConfig,
     state: DriverState,
+    /// Whether to report translators falling back from in-place translation.
+    strict_inplace: bool,
+    /// Diagnostic messages emitted during execution.
+    diagnostics: Vec<String>,
+    /// The binary the current ruleset was loaded from.
+    input: Vec<u8>,
+    /// Whether to record the serialized size of the module around each translator.
+    dry_run: bool,
+    /// Whether to collect statistics of the module produced by each ruleset.
+    stats: bool,
+    /// Whether to compare the sections of the module before and after each ruleset.
+    diff: bool,
+}
+
+/// Name, kind and preset requirement of a chisel module the driver can execute.
+pub struct ModuleInfo {
+    pub name: &'static str,
+    pub kind: ModuleKind,
+    /// Whether the module fails without a `preset` option.
+    pub requires_preset: bool,
+}
+
+impl ModuleInfo {
+    const fn new(name: &'static str, kind: ModuleKind, requires_preset: bool) -> Self {
+        ModuleInfo {
+            name,
+            kind,
+            requires_preset,
+        }
+    }
+}
+
+/// Every module known to the driver. `execute_module` rejects any module not listed here, so a
+/// module must be added both here and to its dispatch.
+pub const MODULES: &[ModuleInfo] = &[
+    ModuleInfo::new("addcustomsection", ModuleKind::Translator, false),
+    #[cfg(feature = "binaryen")]
+    ModuleInfo::new("binaryenopt", ModuleKind::Translator, false),
+    ModuleInfo::new("canonicalize", ModuleKind::Translator, false),
+    ModuleInfo::new("checkcallbacks", ModuleKind::Validator, false),
+    ModuleInfo::new("checkconstoffsets", ModuleKind::Validator, false),
+    ModuleInfo::new("checkdata", ModuleKind::Validator, false),
+    ModuleInfo::new("checkdeployregion", ModuleKind::Validator, true),
+    ModuleInfo::new("checkduplicateexports", ModuleKind::Validator, false),
+    ModuleInfo::new("checkentrypoint", ModuleKind::Validator, false),
+    ModuleInfo::new("checkfloat", ModuleKind::Validator, false),
+    ModuleInfo::new("checkfunccount", ModuleKind::Validator, false),
+    ModuleInfo::new("checkfunctionbodies", ModuleKind::Validator, false),
+    ModuleInfo::new("checkhascode", ModuleKind::Validator, false),
+    ModuleInfo::new("checkimportorder", ModuleKind::Validator, false),
+    ModuleInfo::new("checkimportsection", ModuleKind::Validator, false),
+    ModuleInfo::new("checklocals", ModuleKind::Validator, false),
+    ModuleInfo::new("checkmemory", ModuleKind::Validator, false),
+    ModuleInfo::new("checkmemtablecount", ModuleKind::Validator, false),
+    ModuleInfo::new("checkminmemory", ModuleKind::Validator, false),
+    ModuleInfo::new("checkmutableglobals", ModuleKind::Validator, false),
+    ModuleInfo::new("checkopcodes", ModuleKind::Validator, false),
+    ModuleInfo::new("checkstartfunc", ModuleKind::Validator, false),
+    ModuleInfo::new("checkstartnotimported", ModuleKind::Validator, false),
+    ModuleInfo::new("checktable", ModuleKind::Validator, false),
+    ModuleInfo::new("checktyperefs", ModuleKind::Validator, false),
+    ModuleInfo::new("chiselversion", ModuleKind::Translator, false),
+    ModuleInfo::new("convertdeployer", ModuleKind::Translator, true),
+    ModuleInfo::new("deadcode", ModuleKind::Translator, false),
+    ModuleInfo::new("debugstubs", ModuleKind::Translator, false),
+    ModuleInfo::new("dedupetypes", ModuleKind::Translator, false),
+    ModuleInfo::new("deployer", ModuleKind::Translator, true),
+    ModuleInfo::new("dropnames", ModuleKind::Translator, false),
+    ModuleInfo::new("ensureimports", ModuleKind::Translator, true),
+    ModuleInfo::new("fixedmemory", ModuleKind::Translator, false),
+    ModuleInfo::new("globalgetters", ModuleKind::Translator, false),
+    ModuleInfo::new("inlineconstglobals", ModuleKind::Translator, false),
+    ModuleInfo::new("padsize", ModuleKind::Translator, false),
+    ModuleInfo::new("profiledropnames", ModuleKind::Translator, false),
+    ModuleInfo::new("relocatedata", ModuleKind::Translator, false),
+    ModuleInfo::new("remapimports", ModuleKind::Translator, false),
+    ModuleInfo::new("remapstart", ModuleKind::Translator, false),
+    ModuleInfo::new("removenops", ModuleKind::Translator, false),
+    ModuleInfo::new("renameexports", ModuleKind::Translator, false),
+    ModuleInfo::new("renumberstart", ModuleKind::Translator, false),
+    ModuleInfo::new("repack", ModuleKind::Translator, false),
+    ModuleInfo::new("roundtrip", ModuleKind::Validator, false),
+    ModuleInfo::new("setmemory", ModuleKind::Translator, false),
+    ModuleInfo::new("snip", ModuleKind::Translator, false),
+    ModuleInfo::new("sortexports", ModuleKind::Translator, false),
+    ModuleInfo::new("sortimports", ModuleKind::Translator, false),
+    ModuleInfo::new("strictexports", ModuleKind::Validator, false),
+    ModuleInfo::new("stub", ModuleKind::Creator, true),
+    ModuleInfo::new("template", ModuleKind::Creator, true),
+    ModuleInfo::new("trimexports", ModuleKind::Translator, true),
+    ModuleInfo::new("trimimports", ModuleKind::Translator, false),
+    ModuleInfo::new("trimstartfunc", ModuleKind::Translator, false),
+    ModuleInfo::new("trimunreachable", ModuleKind::Translator, false),
+    ModuleInfo::new("unexportmemory", ModuleKind::Translator, false),
+    ModuleInfo::new("verifyexports", ModuleKind::Validator, true),
+    ModuleInfo::new("verifyimports", ModuleKind::Validator, true),
+    ModuleInfo::new("wrapmain", ModuleKind::Translator, false),
+];
+
+/// Where a ruleset reads its module from, resolved from its `file` field.
+#[derive(Debug, PartialEq)]
+enum InputSource {
+    /// `-`: the module is read from standard input.
+    Stdin,
+    /// A filesystem path, given either directly or as a `file://` URI.
+    File(PathBuf),
+    /// The module bytes were handed to the driver directly.
+    Memory(Vec<u8>),
 }
 
 /// The state of the chisel driver.
@@ -44,12 +213,19 @@ pub enum DriverError {
     MissingRequiredField(String, String),
     /// The contained module name was not successfully resolved to an existing chisel module.
     ModuleNotFound(String),
+    /// The contained ruleset name was not found in the configuration.
+    RulesetNotFound(String),
     /// A configuration value is of incorrect type or invalid value. Left-hand is the config object
     /// name, right-hand is the name of the invalid field.
     InvalidField(String, String),
+    /// The preset given to the contained module is not one of its available presets.
+    InvalidPreset(String),
     /// A canonicalized path was generated unsuccessfully. Left-hand is the config object name,
     /// right-hand is the invalid path.
     PathResolution(String, String),
+    /// A path was given as a URI with a scheme other than `file`. Left-hand is the config object
+    /// name, right-hand is the scheme.
+    UnsupportedScheme(String, String),
     /// An internal error occurred. Field 0 is the config object, during the execution of which the error occurred.
     /// Field 1 is an additional informational message. Field 2 is the error generated.
     Internal(String, String, Box<dyn Error>),
@@ -60,9 +236,41 @@ impl ChiselDriver {
         ChiselDriver {
             config,
             state: DriverState::Ready,
+            strict_inplace: false,
+            diagnostics: Vec::new(),
+            input: Vec::new(),
+            dry_run: false,
+            stats: false,
+            diff: false,
         }
     }
 
+    /// Enables diagnostics for translators which do not support in-place translation and fall
+    /// back to producing a copy of the module.
+    pub fn set_strict_inplace(&mut self, strict: bool) {
+        self.strict_inplace = strict;
+    }
+
+    /// Enables recording the serialized size of the module before and after each translator.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Enables collecting statistics of the module produced by each ruleset.
+    pub fn set_stats(&mut self, stats: bool) {
+        self.stats = stats;
+    }
+
+    /// Enables comparing the sections of the module before and after each ruleset.
+    pub fn set_diff(&mut self, diff: bool) {
+        self.diff = diff;
+    }
+
+    /// Returns the diagnostic messages emitted so far.
+    pub fn diagnostics(&self) -> &[String] {
+        &self.diagnostics
+    }
+
     pub fn take_result(self) -> ChiselResult {
         match self.state {
             DriverState::Ready => {
@@ -81,135 +289,198 @@ impl ChiselDriver {
         };
 
         // Consume the rulesets in the configuration and execute each one.
-        while let Some((name, mut ruleset)) = self.config.rulesets_mut().pop_front() {
-            let mut ruleset_result = RulesetResult::new(name.clone());
-
-            // Load binary.
-            chisel_debug!(1, "Running ruleset {}", name);
-            chisel_debug!(1, "Looking for binary path...");
-            let binary_path = if let Some(binary_path) = ruleset.options().get(&"file".to_string())
-            {
-                chisel_debug!(1, "Found binary path: {}", &binary_path);
-                chisel_debug!(1, "Attempting to resolve path...");
-
-                match canonicalize(binary_path) {
-                    Ok(path_resolved) => {
-                        chisel_debug!(1, "Successfully resolved binary path");
-                        path_resolved
-                    }
-                    Err(_) => {
-                        chisel_debug!(1, "Failed to resolve binary path");
-                        self.state = DriverState::Error(
-                            DriverError::PathResolution(name.clone(), binary_path.clone()),
-                            results,
-                        );
-                        return &self.state;
-                    }
+        while let Some((name, ruleset)) = self.config.rulesets_mut().pop_front() {
+            match self.execute_ruleset(name.clone(), ruleset, None) {
+                Ok(ruleset_result) => results.rulesets_mut().push(ruleset_result),
+                Err(error_state) => {
+                    // The ruleset is dropped, but recorded so that strict mode counts it.
+                    let mut skipped = RulesetResult::new(name);
+                    skipped.set_error(error_state.to_string());
+                    results.rulesets_mut().push(skipped);
+                    self.state = DriverState::Error(error_state, results);
+                    return &self.state;
                 }
-            } else {
-                self.state = DriverState::Error(
-                    DriverError::MissingRequiredField(name.clone(), "file".to_string()),
-                    results,
-                );
-                return &self.state;
-            };
+            }
+        }
+        self.state = DriverState::Done(results);
+        &self.state
+    }
 
-            // Look for output path and set.
-            let output_path =
-                if let Some(output_path) = ruleset.options().get(&"output".to_string()) {
-                    chisel_debug!(1, "Found output path: {}", &output_path);
-                    PathBuf::from(output_path)
-                } else {
-                    chisel_debug!(1, "No output path found.");
-                    binary_path.clone()
-                };
-            ruleset_result.set_output_path(output_path);
-
-            // Load the wasm binary into a buffer before deserialization.
-            chisel_debug!(1, "Deserializing module from file");
-            let wasm_raw = match read(binary_path) {
-                Ok(ret) => ret,
-                Err(e) => {
-                    chisel_debug!(1, "Failed to load Wasm binary");
-                    self.state = DriverState::Error(
-                        DriverError::Internal(
-                            name.clone(),
-                            "Failed to load file".to_string(),
-                            e.into(),
-                        ),
-                        results,
-                    );
-                    return &self.state;
+    /// Executes only the named ruleset and returns its result. The ruleset is consumed from the
+    /// configuration, so a later call to `fire()` will not execute it again.
+    pub fn run_ruleset(&mut self, name: &str) -> Result<RulesetResult, DriverError> {
+        let position = self
+            .config
+            .rulesets()
+            .iter()
+            .position(|(ruleset_name, _)| ruleset_name == name);
+
+        match position.and_then(|pos| self.config.rulesets_mut().remove(pos)) {
+            Some((name, ruleset)) => self.execute_ruleset(name, ruleset, None),
+            None => Err(DriverError::RulesetNotFound(name.to_string())),
+        }
+    }
+
+    /// Executes every ruleset in the configuration on the given module, which may be a Wasm
+    /// binary, hex-encoded binary or Wasm text. The `file` field of the rulesets is not required,
+    /// and nothing is read from or written to the filesystem: the output modules are kept in the
+    /// returned result.
+    pub fn run_bytes(config: ChiselConfig, wasm: &[u8]) -> Result<ChiselResult, DriverError> {
+        let mut driver = ChiselDriver::new(config);
+        let mut results = ChiselResult::new();
+        while let Some((name, ruleset)) = driver.config.rulesets_mut().pop_front() {
+            let ruleset_result = driver.execute_ruleset(name, ruleset, Some(wasm))?;
+            results.rulesets_mut().push(ruleset_result);
+        }
+        Ok(results)
+    }
+
+    /// Executes a single ruleset. If `input` is given, the module is loaded from it instead of
+    /// the ruleset's `file`.
+    fn execute_ruleset(
+        &mut self,
+        name: String,
+        mut ruleset: Ruleset,
+        input: Option<&[u8]>,
+    ) -> Result<RulesetResult, DriverError> {
+        let mut ruleset_result = RulesetResult::new(name.clone());
+
+        // Load binary.
+        chisel_debug!(1, "Running ruleset {}", name);
+        chisel_debug!(1, "Looking for binary path...");
+        let input_source = if let Some(input) = input {
+            chisel_debug!(1, "Using module passed in memory");
+            InputSource::Memory(input.to_vec())
+        } else if let Some(binary_path) = ruleset.options().get(&"file".to_string()) {
+            chisel_debug!(1, "Found binary path: {}", &binary_path);
+            chisel_debug!(1, "Attempting to resolve path...");
+            resolve_input(&name, binary_path)?
+        } else {
+            return Err(DriverError::MissingRequiredField(
+                name.clone(),
+                "file".to_string(),
+            ));
+        };
+
+        // Look for output path and set.
+        let output_path = if let Some(output_path) = ruleset.options().get(&"output".to_string()) {
+            chisel_debug!(1, "Found output path: {}", &output_path);
+            PathBuf::from(output_path)
+        } else {
+            chisel_debug!(1, "No output path found.");
+            match &input_source {
+                InputSource::Stdin => PathBuf::from("/dev/stdout"),
+                InputSource::File(binary_path) => binary_path.clone(),
+                InputSource::Memory(_) => PathBuf::new(),
+            }
+        };
+        ruleset_result.set_output_path(output_path);
+
+        // The output mode given on the CLI can be overridden per ruleset.
+        if let Some(output_mode) = ruleset.options().get("output_mode") {
+            match output_mode.as_str() {
+                "bin" | "wat" | "hex" => ruleset_result.set_output_mode(output_mode.to_string()),
+                _ => {
+                    return Err(DriverError::InvalidField(
+                        name.clone(),
+                        "output_mode".to_string(),
+                    ))
                 }
+            }
+        }
+
+        // Load the wasm binary into a buffer before deserialization.
+        chisel_debug!(1, "Deserializing module from file");
+        let wasm_raw = match read_input(&input_source, stdin()) {
+            Ok(ret) => ret,
+            Err(e) => {
+                chisel_debug!(1, "Failed to load Wasm binary");
+                return Err(DriverError::Internal(
+                    name.clone(),
+                    "Failed to load file".to_string(),
+                    e.into(),
+                ));
+            }
+        };
+
+        let (mut wasm, wasm_raw) = parse_input(&name, &wasm_raw)?;
+        self.input = wasm_raw;
+        let original = if self.diff { Some(wasm.clone()) } else { None };
+
+        // Consume modules in ruleset and execute.
+        while let Some((name, module)) = ruleset.modules_mut().pop_front() {
+            if !module.enabled() {
+                chisel_debug!(1, "Skipping disabled module {}", &name);
+                continue;
+            }
+
+            chisel_debug!(1, "Executing module {}", &name);
+
+            let size_before = if self.dry_run {
+                serialized_len(&wasm)
+            } else {
+                None
             };
 
-            // Try parsing as Wasm text (Wat) first. Note: this function passes through binaries.
-            let wasm_raw = match wat::parse_bytes(&wasm_raw) {
-                Ok(ret) => ret,
-                Err(e) => {
-                    chisel_debug!(1, "Failed to parse input as text");
-                    self.state = DriverState::Error(
-                        DriverError::Internal(
-                            name.clone(),
-                            "Failed to parse input as text".to_string(),
-                            e.into(),
-                        ),
-                        results,
-                    );
-                    return &self.state;
+            let module_result = self.execute_module(name, module, &mut wasm)?;
+
+            if let (ModuleResult::Translator(..), Some(before)) = (&module_result, size_before) {
+                if let Some(after) = serialized_len(&wasm) {
+                    let index = ruleset_result.results_mut().len();
+                    ruleset_result.set_size_change(index, before, after);
                 }
-            };
+            }
 
-            // Deserialize the Wasm binary and parse its names section.
-            let mut wasm = match Module::from_bytes(wasm_raw) {
-                Ok(wasm) => {
-                    chisel_debug!(1, "Successfully deserialized Wasm module");
-                    // TODO: Make this error recoverable
-                    wasm.parse_names().expect("names parsing failed")
-                }
-                Err(e) => {
-                    chisel_debug!(1, "Failed to deserialize Wasm module");
-                    self.state = DriverState::Error(
-                        DriverError::Internal(
-                            name.clone(),
-                            "Deserialization failure".to_string(),
-                            e.into(),
-                        ),
-                        results,
-                    );
-                    return &self.state;
+            // If the module was a translator or creator, we set the output in the result.
+            match module_result {
+                ModuleResult::Creator(_, ref result) | ModuleResult::Translator(_, ref result) => {
+                    if let Ok(true) = result {
+                        chisel_debug!(1, "Module mutated or created.");
+                        ruleset_result.set_output_module(wasm.clone()); //TODO: Refactor to only set this at the end and save some expensive copies
+                    }
                 }
-            };
+                ModuleResult::Validator(_, _) => (),
+            }
+            ruleset_result.results_mut().push(module_result);
+        }
 
-            // Consume modules in ruleset and execute.
-            while let Some((name, module)) = ruleset.modules_mut().pop_front() {
-                chisel_debug!(1, "Executing module {}", &name);
+        if self.stats {
+            ruleset_result.set_stats(module_stats(&wasm));
+        }
+        if let Some(original) = original {
+            ruleset_result.set_diff(diff_modules(&original, &wasm));
+        }
+        Ok(ruleset_result)
+    }
 
-                let module_result = match self.execute_module(name, module, &mut wasm) {
-                    Ok(result) => result,
-                    Err(error_state) => {
-                        self.state = DriverState::Error(error_state, results);
-                        return &self.state;
-                    }
-                };
+    /// Runs a translator in-place, falling back to `translate` if the translator does not support
+    /// in-place translation. Returns true if the module was modified.
+    fn translate_module(
+        &mut self,
+        name: &str,
+        translator: &dyn ModuleTranslator,
+        wasm: &mut Module,
+    ) -> Result<bool, ModuleError> {
+        match translator.translate_inplace(wasm) {
+            Err(ModuleError::NotSupported) => {
+                if self.strict_inplace {
+                    let message = format!(
+                        "module '{}' does not support in-place translation; falling back to a copy",
+                        name
+                    );
+                    chisel_debug!(0, "{}", &message);
+                    self.diagnostics.push(message);
+                }
 
-                // If the module was a translator or creator, we set the output in the result.
-                match module_result {
-                    ModuleResult::Creator(_, ref result)
-                    | ModuleResult::Translator(_, ref result) => {
-                        if let Ok(true) = result {
-                            chisel_debug!(1, "Module mutated or created.");
-                            ruleset_result.set_output_module(wasm.clone()); //TODO: Refactor to only set this at the end and save some expensive copies
-                        }
-                    }
-                    ModuleResult::Validator(_, _) => (),
+                if let Some(new_wasm) = translator.translate(wasm)? {
+                    *wasm = new_wasm;
+                    Ok(true)
+                } else {
+                    Ok(false)
                 }
-                ruleset_result.results_mut().push(module_result);
             }
-            results.rulesets_mut().push(ruleset_result);
+            result => result,
         }
-        self.state = DriverState::Done(results);
-        &self.state
     }
 
     pub fn execute_module(
@@ -218,12 +489,263 @@ impl ChiselDriver {
         module: ModuleConfig,
         wasm: &mut Module,
     ) -> Result<ModuleResult, DriverError> {
+        if !MODULES.iter().any(|info| info.name == name) {
+            return Err(DriverError::ModuleNotFound(name));
+        }
+
         let result = match name.as_str() {
+            "addcustomsection" => {
+                let options = module.options();
+                if let Ok(addcustomsection) = AddCustomSection::with_config(options) {
+                    let module_result = addcustomsection.translate_inplace(wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else if !options.contains_key("name") {
+                    chisel_debug!(1, "addcustomsection missing field 'name'");
+                    return Err(DriverError::MissingRequiredField(name, "name".to_string()));
+                } else if !options.contains_key("payload_hex") {
+                    chisel_debug!(1, "addcustomsection missing field 'payload_hex'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "payload_hex".to_string(),
+                    ));
+                } else {
+                    chisel_debug!(1, "addcustomsection given invalid hex payload");
+                    return Err(DriverError::InvalidField(name, "payload_hex".to_string()));
+                }
+            }
+            "checkentrypoint" => {
+                if let Ok(checkentrypoint) = CheckEntryPoint::with_config(module.options()) {
+                    let module_result = checkentrypoint.validate(wasm);
+                    ModuleResult::Validator(name, module_result)
+                } else {
+                    chisel_debug!(1, "checkentrypoint given invalid configuration");
+                    let field = match module.options().get("require").map(String::as_str) {
+                        None | Some("start") | Some("main") | Some("either") => "exclusive",
+                        _ => "require",
+                    };
+                    return Err(DriverError::InvalidField(name, field.to_string()));
+                }
+            }
             "checkfloat" => {
                 let checkfloat = CheckFloat::with_defaults().expect("Should not fail");
                 let module_result = checkfloat.validate(wasm);
                 ModuleResult::Validator(name, module_result)
             }
+            "canonicalize" => {
+                let canonicalize = Canonicalize::new(&self.input);
+                if let Ok(delta) = canonicalize.size_delta(wasm) {
+                    chisel_debug!(1, "canonicalize changes the size by {} bytes", delta);
+                }
+                let module_result = canonicalize.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "checkcallbacks" => {
+                if module.options().get("callbacks").is_some() {
+                    match CheckCallbacks::with_config(module.options()) {
+                        Ok(checkcallbacks) => {
+                            ModuleResult::Validator(name, checkcallbacks.validate(wasm))
+                        }
+                        Err(_) => {
+                            chisel_debug!(1, "checkcallbacks given invalid callbacks");
+                            return Err(DriverError::InvalidField(name, "callbacks".to_string()));
+                        }
+                    }
+                } else {
+                    chisel_debug!(1, "checkcallbacks missing field 'callbacks'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "callbacks".to_string(),
+                    ));
+                }
+            }
+            "checkconstoffsets" => {
+                let checkconstoffsets =
+                    CheckConstOffsets::with_defaults().expect("Should not fail");
+                let module_result = checkconstoffsets.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checkdata" => {
+                if let Ok(checkdata) = CheckDataBounds::with_config(module.options()) {
+                    let module_result = checkdata.validate(wasm);
+                    ModuleResult::Validator(name, module_result)
+                } else {
+                    chisel_debug!(1, "checkdata given invalid imported memory size");
+                    return Err(DriverError::InvalidField(
+                        name,
+                        "imported_pages".to_string(),
+                    ));
+                }
+            }
+            "checkdeployregion" => {
+                if let Some(preset) = module.options().get("preset") {
+                    let checkdeployregion = CheckDeployRegion::with_preset(preset.as_str());
+                    if let Ok(checkdeployregion) = checkdeployregion {
+                        let module_result = checkdeployregion.validate(wasm);
+                        ModuleResult::Validator(name, module_result)
+                    } else {
+                        chisel_debug!(1, "checkdeployregion given invalid preset");
+                        return Err(DriverError::InvalidPreset(name));
+                    }
+                } else {
+                    chisel_debug!(1, "checkdeployregion missing field 'preset'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "preset".to_string(),
+                    ));
+                }
+            }
+            "checkduplicateexports" => {
+                let checkduplicateexports =
+                    CheckDuplicateExports::with_defaults().expect("Should not fail");
+                let module_result = checkduplicateexports.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checkfunctionbodies" => {
+                let checkfunctionbodies =
+                    CheckFunctionBodies::with_defaults().expect("Should not fail");
+                let module_result = checkfunctionbodies.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checkhascode" => {
+                let checkhascode = CheckHasCode::with_defaults().expect("Should not fail");
+                if let Some(diagnostic) = checkhascode.diagnostic(wasm) {
+                    chisel_debug!(1, "checkhascode: {}", diagnostic);
+                }
+                let module_result = checkhascode.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checkimportorder" => {
+                if let Ok(checkimportorder) = CheckImportOrder::with_config(module.options()) {
+                    let module_result = checkimportorder.validate(wasm);
+                    ModuleResult::Validator(name, module_result)
+                } else if module.options().contains_key("preset") {
+                    chisel_debug!(1, "checkimportorder given invalid preset");
+                    return Err(DriverError::InvalidPreset(name));
+                } else {
+                    chisel_debug!(1, "checkimportorder given invalid group_kinds");
+                    return Err(DriverError::InvalidField(name, "group_kinds".to_string()));
+                }
+            }
+            "checkimportsection" => {
+                let checkimportsection =
+                    CheckImportSection::with_defaults().expect("Should not fail");
+                let module_result = checkimportsection.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checklocals" => {
+                let checklocals = CheckLocals::with_defaults().expect("Should not fail");
+                if let Ok(Some((body, offset, instruction))) = checklocals.find_violation(wasm) {
+                    chisel_debug!(
+                        1,
+                        "checklocals: function body {} accesses an undeclared local at offset {}: {:?}",
+                        body,
+                        offset,
+                        instruction
+                    );
+                }
+                let module_result = checklocals.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checkmemory" => {
+                let options = module.options();
+                if options.contains_key("preset") || options.contains_key("max_initial") {
+                    if let Ok(checkmemory) = CheckMemory::with_config(options) {
+                        let module_result = checkmemory.validate(wasm);
+                        ModuleResult::Validator(name, module_result)
+                    } else if options.contains_key("preset") {
+                        chisel_debug!(1, "checkmemory given invalid preset");
+                        return Err(DriverError::InvalidPreset(name));
+                    } else {
+                        chisel_debug!(1, "checkmemory given invalid limits");
+                        return Err(DriverError::InvalidField(name, "max_initial".to_string()));
+                    }
+                } else {
+                    chisel_debug!(1, "checkmemory missing field 'preset'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "preset".to_string(),
+                    ));
+                }
+            }
+            "checktable" => {
+                let options = module.options();
+                if options.contains_key("preset") || options.contains_key("max_initial") {
+                    if let Ok(checktable) = CheckTable::with_config(options) {
+                        let module_result = checktable.validate(wasm);
+                        ModuleResult::Validator(name, module_result)
+                    } else if options.contains_key("preset") {
+                        chisel_debug!(1, "checktable given invalid preset");
+                        return Err(DriverError::InvalidPreset(name));
+                    } else {
+                        chisel_debug!(1, "checktable given invalid limits");
+                        return Err(DriverError::InvalidField(name, "max_initial".to_string()));
+                    }
+                } else {
+                    chisel_debug!(1, "checktable missing field 'preset'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "preset".to_string(),
+                    ));
+                }
+            }
+            "checkmemtablecount" => {
+                let checkmemtablecount =
+                    CheckMemTableCount::with_defaults().expect("Should not fail");
+                let module_result = checkmemtablecount.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checkfunccount" => {
+                if module.options().get("max").is_some() {
+                    if let Ok(checkfunccount) = CheckFunctionCount::with_config(module.options()) {
+                        let module_result = checkfunccount.validate(wasm);
+                        ModuleResult::Validator(name, module_result)
+                    } else {
+                        chisel_debug!(1, "checkfunccount given invalid maximum");
+                        return Err(DriverError::InvalidField(name, "max".to_string()));
+                    }
+                } else {
+                    chisel_debug!(1, "checkfunccount missing field 'max'");
+                    return Err(DriverError::MissingRequiredField(name, "max".to_string()));
+                }
+            }
+            "checkminmemory" => {
+                if module.options().get("required_min_pages").is_some() {
+                    if let Ok(checkminmemory) = CheckMinMemory::with_config(module.options()) {
+                        let module_result = checkminmemory.validate(wasm);
+                        ModuleResult::Validator(name, module_result)
+                    } else {
+                        chisel_debug!(1, "checkminmemory given invalid minimum");
+                        return Err(DriverError::InvalidField(
+                            name,
+                            "required_min_pages".to_string(),
+                        ));
+                    }
+                } else {
+                    chisel_debug!(1, "checkminmemory missing field 'required_min_pages'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "required_min_pages".to_string(),
+                    ));
+                }
+            }
+            "checkmutableglobals" => {
+                let checkmutableglobals =
+                    CheckMutableGlobals::with_defaults().expect("Should not fail");
+                let module_result = checkmutableglobals.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checkopcodes" => {
+                if let Ok(checkopcodes) = CheckOpcodes::with_config(module.options()) {
+                    let module_result = checkopcodes.validate(wasm);
+                    ModuleResult::Validator(name, module_result)
+                } else if module.options().contains_key("preset") {
+                    chisel_debug!(1, "checkopcodes given invalid preset");
+                    return Err(DriverError::InvalidPreset(name));
+                } else {
+                    chisel_debug!(1, "checkopcodes given invalid flags");
+                    return Err(DriverError::InvalidField(name, "flags".to_string()));
+                }
+            }
             "checkstartfunc" => {
                 if let Some(require_start) = module.options().get("require_start") {
                     let require_start = match require_start.as_str() {
@@ -247,6 +769,57 @@ impl ChiselDriver {
                     ));
                 }
             }
+            "checkstartnotimported" => {
+                let checkstartnotimported =
+                    CheckStartNotImported::with_defaults().expect("Should not fail");
+                let module_result = checkstartnotimported.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "checktyperefs" => {
+                let checktyperefs = CheckTypeRefs::with_defaults().expect("Should not fail");
+                let module_result = checktyperefs.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "chiselversion" => {
+                let chiselversion = ChiselVersion::with_defaults().expect("Should not fail");
+                let module_result = chiselversion.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "convertdeployer" => {
+                if let Some(preset) = module.options().get("preset") {
+                    match ConvertDeployer::with_preset(preset.as_str()) {
+                        Ok(convertdeployer) => {
+                            let module_result = convertdeployer.translate_inplace(wasm);
+                            ModuleResult::Translator(name, module_result)
+                        }
+                        Err(_) => {
+                            chisel_debug!(1, "convertdeployer given invalid preset");
+                            return Err(DriverError::InvalidPreset(name));
+                        }
+                    }
+                } else {
+                    chisel_debug!(1, "convertdeployer missing field 'preset'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "preset".to_string(),
+                    ));
+                }
+            }
+            "deadcode" => {
+                let deadcode = DeadCode::with_defaults().expect("Should not fail");
+                let module_result = deadcode.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "debugstubs" => {
+                let debugstubs = DebugStubs::with_defaults().expect("Should not fail");
+                let module_result = debugstubs.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "dedupetypes" => {
+                let dedupetypes = DedupeTypes::with_defaults().expect("Should not fail");
+                let module_result = dedupetypes.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
             "deployer" => {
                 if let Some(preset) = module.options().get("preset") {
                     match Deployer::with_preset(preset.as_str()) {
@@ -265,7 +838,7 @@ impl ChiselDriver {
                         },
                         Err(_) => {
                             chisel_debug!(1, "deployer given invalid preset");
-                            return Err(DriverError::InvalidField(name, "preset".to_string()));
+                            return Err(DriverError::InvalidPreset(name));
                         }
                     }
                 } else {
@@ -280,67 +853,275 @@ impl ChiselDriver {
                 let dropsection = DropSection::NamesSection;
                 ModuleResult::Translator(name, dropsection.translate_inplace(wasm))
             }
-            "remapimports" => {
+            "ensureimports" => {
                 if let Some(preset) = module.options().get("preset") {
-                    let remapimports = RemapImports::with_preset(preset.as_str());
-                    if let Ok(remapimports) = remapimports {
-                        let module_result = remapimports.translate_inplace(wasm);
+                    let ensureimports = EnsureImports::with_preset(preset.as_str());
+                    if let Ok(ensureimports) = ensureimports {
+                        let module_result = ensureimports.translate_inplace(wasm);
                         ModuleResult::Translator(name, module_result)
                     } else {
-                        chisel_debug!(1, "remapimports given invalid preset");
-                        return Err(DriverError::InvalidField(name, "preset".to_string()));
+                        chisel_debug!(1, "ensureimports given invalid preset");
+                        return Err(DriverError::InvalidPreset(name));
                     }
                 } else {
-                    chisel_debug!(1, "remapimports missing field 'preset'");
+                    chisel_debug!(1, "ensureimports missing field 'preset'");
                     return Err(DriverError::MissingRequiredField(
                         name,
                         "preset".to_string(),
                     ));
                 }
             }
-            "remapstart" => {
-                // NOTE: preset "ewasm" maps to the default and only mode. Fixing
-                // later.
-                let remapstart = RemapStart::with_preset("ewasm").expect("Should not fail");
-                let module_result = remapstart.translate_inplace(wasm);
+            "fixedmemory" => {
+                let fixedmemory = FixedMemory::with_defaults().expect("Should not fail");
+                let module_result = fixedmemory.translate_inplace(wasm);
                 ModuleResult::Translator(name, module_result)
             }
-            "repack" => {
-                let repack = Repack::with_defaults().expect("Should not fail");
-                let module_result = repack.translate(wasm).expect("No failure cases");
-
-                let did_mutate = if let Some(new_wasm) = module_result {
-                    *wasm = new_wasm;
-                    true
-                } else {
-                    false
-                };
-
-                ModuleResult::Translator(name, Ok(did_mutate))
+            "globalgetters" => {
+                let globalgetters = GlobalGetters::with_defaults().expect("Should not fail");
+                let module_result = globalgetters.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
             }
-            "snip" => {
-                let snip = Snip::with_defaults().expect("Should not fail");
-                let module_result = match snip.translate(wasm) {
-                    Ok(result) => result,
-                    Err(e) => {
-                        return Err(DriverError::Internal(
-                            "snip".to_string(),
-                            "Chisel module failed".to_string(),
-                            e.into(),
-                        ))
+            "inlineconstglobals" => {
+                let inlineconstglobals =
+                    InlineConstGlobals::with_defaults().expect("Should not fail");
+                let module_result = inlineconstglobals.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "padsize" => {
+                if let Some(target_size) = module.options().get("target_size") {
+                    if let Ok(target_size) = target_size.parse::<usize>() {
+                        let padsize = PadSize::new(target_size);
+                        let module_result = padsize.translate_inplace(wasm);
+                        ModuleResult::Translator(name, module_result)
+                    } else {
+                        chisel_debug!(1, "padsize given invalid target size");
+                        return Err(DriverError::InvalidField(name, "target_size".to_string()));
                     }
-                };
-
-                let did_mutate = if let Some(new_wasm) = module_result {
-                    *wasm = new_wasm;
-                    true
                 } else {
-                    false
-                };
-
-                ModuleResult::Translator(name, Ok(did_mutate))
-            }
-            "trimexports" => {
+                    chisel_debug!(1, "padsize missing field 'target_size'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "target_size".to_string(),
+                    ));
+                }
+            }
+            "profiledropnames" => {
+                if module.options().get("profile").is_some() {
+                    let profiledropnames =
+                        ProfileDropNames::with_config(module.options()).expect("Should not fail");
+                    let module_result = profiledropnames.translate_inplace(wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else {
+                    chisel_debug!(1, "profiledropnames missing field 'profile'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "profile".to_string(),
+                    ));
+                }
+            }
+            "relocatedata" => {
+                if let Ok(relocatedata) = RelocateData::with_config(module.options()) {
+                    let module_result = relocatedata.translate_inplace(wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else if module.options().contains_key("relocations") {
+                    chisel_debug!(1, "relocatedata given invalid list of relocations");
+                    return Err(DriverError::InvalidField(name, "relocations".to_string()));
+                } else {
+                    chisel_debug!(1, "relocatedata missing list of relocations");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "relocations".to_string(),
+                    ));
+                }
+            }
+            "remapimports" => {
+                let options = module.options();
+                if options.contains_key("preset") || options.contains_key("namespace") {
+                    if let Ok(remapimports) = RemapImports::with_config(options) {
+                        let module_result = remapimports.translate_inplace(wasm);
+                        ModuleResult::Translator(name, module_result)
+                    } else if options
+                        .get("preset")
+                        .filter(|preset| RemapImports::with_preset(preset).is_err())
+                        .is_some()
+                    {
+                        chisel_debug!(1, "remapimports given invalid preset");
+                        return Err(DriverError::InvalidPreset(name));
+                    } else if options.contains_key("namespace") && !options.contains_key("fields") {
+                        chisel_debug!(1, "remapimports missing field 'fields'");
+                        return Err(DriverError::MissingRequiredField(
+                            name,
+                            "fields".to_string(),
+                        ));
+                    } else {
+                        chisel_debug!(1, "remapimports given invalid direction");
+                        return Err(DriverError::InvalidField(name, "direction".to_string()));
+                    }
+                } else {
+                    chisel_debug!(1, "remapimports missing field 'preset'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "preset".to_string(),
+                    ));
+                }
+            }
+            "remapstart" => {
+                // NOTE: preset "ewasm" maps to the default mode. Fixing later.
+                if let Ok(remapstart) = RemapStart::with_config(module.options()) {
+                    let module_result = remapstart.translate_inplace(wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else if module.options().contains_key("keep_start") {
+                    chisel_debug!(1, "remapstart given invalid value for 'keep_start'");
+                    return Err(DriverError::InvalidField(name, "keep_start".to_string()));
+                } else {
+                    chisel_debug!(1, "remapstart given invalid preset");
+                    return Err(DriverError::InvalidPreset(name));
+                }
+            }
+            "removenops" => {
+                let removenops = RemoveNops::with_defaults().expect("Should not fail");
+                let module_result = removenops.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "renameexports" => {
+                let options = module.options();
+                if let Ok(renameexports) = RenameExports::with_config(options) {
+                    let module_result = renameexports.translate_inplace(wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else if !options.contains_key("from") {
+                    chisel_debug!(1, "renameexports missing field 'from'");
+                    return Err(DriverError::MissingRequiredField(name, "from".to_string()));
+                } else if !options.contains_key("to") {
+                    chisel_debug!(1, "renameexports missing field 'to'");
+                    return Err(DriverError::MissingRequiredField(name, "to".to_string()));
+                } else {
+                    chisel_debug!(1, "renameexports given lists of different lengths");
+                    return Err(DriverError::InvalidField(name, "to".to_string()));
+                }
+            }
+            "renumberstart" => {
+                if let Ok(renumberstart) = RenumberStart::with_config(module.options()) {
+                    let module_result = renumberstart.translate_inplace(wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else {
+                    chisel_debug!(1, "renumberstart given invalid list of removed functions");
+                    return Err(DriverError::InvalidField(name, "removed".to_string()));
+                }
+            }
+            "repack" => {
+                if let Ok(repack) = Repack::with_config(module.options()) {
+                    let module_result = self.translate_module(&name, &repack, wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else {
+                    chisel_debug!(1, "repack given invalid value for 'keep_custom'");
+                    return Err(DriverError::InvalidField(name, "keep_custom".to_string()));
+                }
+            }
+            "roundtrip" => {
+                let roundtrip = RoundTrip::with_defaults().expect("Should not fail");
+                let module_result = roundtrip.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "setmemory" => {
+                let options = module.options();
+                if let Ok(setmemory) = SetMemoryLimits::with_config(options) {
+                    let module_result = setmemory.translate_inplace(wasm);
+                    ModuleResult::Translator(name, module_result)
+                } else if !options.contains_key("initial") {
+                    chisel_debug!(1, "setmemory missing field 'initial'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "initial".to_string(),
+                    ));
+                } else if options.contains_key("maximum") {
+                    chisel_debug!(1, "setmemory given invalid limits");
+                    return Err(DriverError::InvalidField(name, "maximum".to_string()));
+                } else {
+                    chisel_debug!(1, "setmemory given invalid initial size");
+                    return Err(DriverError::InvalidField(name, "initial".to_string()));
+                }
+            }
+            "snip" => {
+                let snip = Snip::with_config(module.options()).expect("Should not fail");
+                let did_mutate = match self.translate_module(&name, &snip, wasm) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        return Err(DriverError::Internal(
+                            "snip".to_string(),
+                            "Chisel module failed".to_string(),
+                            e.into(),
+                        ))
+                    }
+                };
+
+                ModuleResult::Translator(name, Ok(did_mutate))
+            }
+            "sortexports" => {
+                let sortexports = SortExports::with_defaults().expect("Should not fail");
+                let module_result = sortexports.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "sortimports" => {
+                let sortimports = SortImports::with_defaults().expect("Should not fail");
+                let module_result = sortimports.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "strictexports" => {
+                let strictexports = StrictExports::with_defaults().expect("Should not fail");
+                if let Some(extra) = strictexports.find_extra_export(wasm) {
+                    chisel_debug!(1, "strictexports: unexpected function export '{}'", extra);
+                }
+                let module_result = strictexports.validate(wasm);
+                ModuleResult::Validator(name, module_result)
+            }
+            "stub" => {
+                if module.options().contains_key("preset") {
+                    if let Ok(stub) = Stub::with_config(module.options()) {
+                        match stub.create() {
+                            Ok(new_wasm) => {
+                                *wasm = new_wasm;
+                                ModuleResult::Creator(name, Ok(true))
+                            }
+                            Err(e) => ModuleResult::Creator(name, Err(e)),
+                        }
+                    } else if Stub::with_preset(&module.options()["preset"]).is_err() {
+                        chisel_debug!(1, "stub given invalid preset");
+                        return Err(DriverError::InvalidPreset(name));
+                    } else {
+                        chisel_debug!(1, "stub given unknown host function");
+                        return Err(DriverError::InvalidField(name, "imports".to_string()));
+                    }
+                } else {
+                    chisel_debug!(1, "stub missing field 'preset'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "preset".to_string(),
+                    ));
+                }
+            }
+            "template" => {
+                if let Some(preset) = module.options().get("preset") {
+                    if let Ok(template) = Template::with_preset(preset.as_str()) {
+                        match template.create() {
+                            Ok(new_wasm) => {
+                                *wasm = new_wasm;
+                                ModuleResult::Creator(name, Ok(true))
+                            }
+                            Err(e) => ModuleResult::Creator(name, Err(e)),
+                        }
+                    } else {
+                        chisel_debug!(1, "template given invalid preset");
+                        return Err(DriverError::InvalidPreset(name));
+                    }
+                } else {
+                    chisel_debug!(1, "template missing field 'preset'");
+                    return Err(DriverError::MissingRequiredField(
+                        name,
+                        "preset".to_string(),
+                    ));
+                }
+            }
+            "trimexports" => {
                 if let Some(preset) = module.options().get("preset") {
                     let trimexports = TrimExports::with_preset(preset.as_str());
                     if let Ok(trimexports) = trimexports {
@@ -348,7 +1129,7 @@ impl ChiselDriver {
                         ModuleResult::Translator(name, module_result)
                     } else {
                         chisel_debug!(1, "trimexports given invalid preset");
-                        return Err(DriverError::InvalidField(name, "preset".to_string()));
+                        return Err(DriverError::InvalidPreset(name));
                     }
                 } else {
                     chisel_debug!(1, "remapimports missing field 'preset'");
@@ -358,6 +1139,11 @@ impl ChiselDriver {
                     ));
                 }
             }
+            "trimimports" => {
+                let trimimports = TrimImports::with_defaults().expect("Should not fail");
+                let module_result = trimimports.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
             "trimstartfunc" => {
                 // NOTE: preset "ewasm" maps to the default and only mode. Fixing
                 // later.
@@ -365,6 +1151,17 @@ impl ChiselDriver {
                 let module_result = trimstartfunc.translate_inplace(wasm);
                 ModuleResult::Translator(name, module_result)
             }
+            "trimunreachable" => {
+                let trimunreachable = TrimUnreachable::with_defaults().expect("Should not fail");
+                let module_result = trimunreachable.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
+            "unexportmemory" => {
+                let unexportmemory =
+                    UnexportMemory::with_config(module.options()).expect("Should not fail");
+                let module_result = unexportmemory.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
             "verifyexports" => {
                 if let Some(preset) = module.options().get("preset") {
                     let verifyexports = VerifyExports::with_preset(preset.as_str());
@@ -373,7 +1170,7 @@ impl ChiselDriver {
                         ModuleResult::Validator(name, module_result)
                     } else {
                         chisel_debug!(1, "verifyexports given invalid preset");
-                        return Err(DriverError::InvalidField(name, "preset".to_string()));
+                        return Err(DriverError::InvalidPreset(name));
                     }
                 } else {
                     chisel_debug!(1, "verifyexports missing field 'preset'");
@@ -387,11 +1184,24 @@ impl ChiselDriver {
                 if let Some(preset) = module.options().get("preset") {
                     let verifyimports = VerifyImports::with_preset(preset.as_str());
                     if let Ok(verifyimports) = verifyimports {
+                        if let Ok(report) = verifyimports.validate_verbose(wasm) {
+                            for (namespace, field, status) in report {
+                                if status != ImportStatus::Good {
+                                    chisel_debug!(
+                                        1,
+                                        "verifyimports: {}.{}: {:?}",
+                                        namespace,
+                                        field,
+                                        status
+                                    );
+                                }
+                            }
+                        }
                         let module_result = verifyimports.validate(&wasm);
                         ModuleResult::Validator(name, module_result)
                     } else {
                         chisel_debug!(1, "verifyimports given invalid preset");
-                        return Err(DriverError::InvalidField(name, "preset".to_string()));
+                        return Err(DriverError::InvalidPreset(name));
                     }
                 } else {
                     chisel_debug!(1, "verifyimports missing field 'preset'");
@@ -401,27 +1211,29 @@ impl ChiselDriver {
                     ));
                 }
             }
+            "wrapmain" => {
+                let wrapmain = WrapMain::with_defaults().expect("Should not fail");
+                let module_result = wrapmain.translate_inplace(wasm);
+                ModuleResult::Translator(name, module_result)
+            }
             #[cfg(feature = "binaryen")]
             "binaryenopt" => {
-                if let Some(preset) = module.options().get("preset") {
+                if module.options().contains_key("passes") {
+                    if let Ok(binaryenopt) = BinaryenOptimiser::with_config(module.options()) {
+                        let module_result = self.translate_module(&name, &binaryenopt, wasm);
+                        ModuleResult::Translator(name, module_result)
+                    } else {
+                        chisel_debug!(1, "binaryenopt given an empty pass list");
+                        return Err(DriverError::InvalidField(name, "passes".to_string()));
+                    }
+                } else if let Some(preset) = module.options().get("preset") {
                     let binaryenopt = BinaryenOptimiser::with_preset(preset.as_str());
                     if let Ok(binaryenopt) = binaryenopt {
-                        match binaryenopt.translate(wasm) {
-                            Ok(new_wasm) => {
-                                let did_mutate = if let Some(new_wasm) = new_wasm {
-                                    *wasm = new_wasm;
-                                    true
-                                } else {
-                                    false
-                                };
-
-                                ModuleResult::Translator(name, Ok(did_mutate))
-                            }
-                            Err(e) => ModuleResult::Translator(name, Err(e)),
-                        }
+                        let module_result = self.translate_module(&name, &binaryenopt, wasm);
+                        ModuleResult::Translator(name, module_result)
                     } else {
                         chisel_debug!(1, "binaryenopt given invalid preset");
-                        return Err(DriverError::InvalidField(name, "preset".to_string()));
+                        return Err(DriverError::InvalidPreset(name));
                     }
                 } else {
                     chisel_debug!(1, "binaryenopt missing field 'preset'");
@@ -439,7 +1251,123 @@ impl ChiselDriver {
     }
 }
 
+/// Returns the length of the serialized module, or None if it cannot be serialized.
+fn serialized_len(module: &Module) -> Option<usize> {
+    module.clone().to_bytes().ok().map(|bytes| bytes.len())
+}
+
 // Error.description() is deprecated for displaying errors now.
+
+/// Reads the module bytes from the given source. Standard input is read from `stdin_reader`.
+fn read_input<R: Read>(source: &InputSource, mut stdin_reader: R) -> io::Result<Vec<u8>> {
+    match source {
+        InputSource::Stdin => {
+            let mut buffer = Vec::new();
+            stdin_reader.read_to_end(&mut buffer)?;
+            Ok(buffer)
+        }
+        InputSource::File(binary_path) => read(binary_path),
+        InputSource::Memory(bytes) => Ok(bytes.clone()),
+    }
+}
+
+/// Decodes the buffer if it consists solely of ASCII hex digits, ignoring whitespace.
+fn decode_hex_input(raw: &[u8]) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = raw
+        .iter()
+        .copied()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    hex::decode(digits).ok()
+}
+
+/// Deserializes a module from an in-memory buffer holding either a Wasm binary, hex-encoded Wasm
+/// binary or Wasm text, and parses its names section. Returns the module along with its binary
+/// encoding.
+fn parse_input(name: &str, wasm_raw: &[u8]) -> Result<(Module, Vec<u8>), DriverError> {
+    // Decode hex-encoded input, mirroring the `hex` output mode.
+    let decoded = decode_hex_input(wasm_raw);
+    if decoded.is_some() {
+        chisel_debug!(1, "Decoded input as hex");
+    }
+    let wasm_raw = decoded.as_deref().unwrap_or(wasm_raw);
+
+    // Try parsing as Wasm text (Wat) first. Note: this function passes through binaries.
+    let wasm_raw = match wat::parse_bytes(wasm_raw) {
+        Ok(ret) => ret,
+        Err(e) => {
+            chisel_debug!(1, "Failed to parse input as text");
+            return Err(DriverError::Internal(
+                name.to_string(),
+                "Failed to parse input as text".to_string(),
+                e.into(),
+            ));
+        }
+    };
+
+    // Deserialize the Wasm binary and parse its names section.
+    match Module::from_bytes(&wasm_raw) {
+        Ok(wasm) => {
+            chisel_debug!(1, "Successfully deserialized Wasm module");
+            // A malformed names section is kept as an unparsed custom section.
+            let wasm = match wasm.parse_names() {
+                Ok(wasm) => wasm,
+                Err((_, wasm)) => {
+                    chisel_debug!(1, "Failed to parse names section; continuing without it");
+                    wasm
+                }
+            };
+            Ok((wasm, wasm_raw.into_owned()))
+        }
+        Err(e) => {
+            chisel_debug!(1, "Failed to deserialize Wasm module");
+            Err(DriverError::Internal(
+                name.to_string(),
+                "Deserialization failure".to_string(),
+                e.into(),
+            ))
+        }
+    }
+}
+
+/// Resolves the `file` field of a ruleset. Accepts `-` for standard input, a `file://` URI or a
+/// plain filesystem path.
+fn resolve_input(name: &str, path: &str) -> Result<InputSource, DriverError> {
+    if path == "-" {
+        chisel_debug!(1, "Reading binary from stdin");
+        return Ok(InputSource::Stdin);
+    }
+
+    let local_path = if let Some(local_path) = path.strip_prefix("file://") {
+        local_path
+    } else if let Some(position) = path.find("://") {
+        chisel_debug!(1, "Unsupported scheme in binary path");
+        return Err(DriverError::UnsupportedScheme(
+            name.to_string(),
+            path[..position].to_string(),
+        ));
+    } else {
+        path
+    };
+
+    match canonicalize(local_path) {
+        Ok(path_resolved) => {
+            chisel_debug!(1, "Successfully resolved binary path");
+            Ok(InputSource::File(path_resolved))
+        }
+        Err(_) => {
+            chisel_debug!(1, "Failed to resolve binary path");
+            Err(DriverError::PathResolution(
+                name.to_string(),
+                path.to_string(),
+            ))
+        }
+    }
+}
+
 impl Display for DriverError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -447,12 +1375,26 @@ impl Display for DriverError {
                 write!(f, "in '{}': missing required field '{}'", object, field)
             }
             DriverError::ModuleNotFound(module) => write!(f, "in '{}': module not found", module),
+            DriverError::RulesetNotFound(ruleset) => {
+                write!(f, "in '{}': ruleset not found", ruleset)
+            }
             DriverError::InvalidField(object, field) => {
                 write!(f, "in '{}': invalid field '{}'", object, field)
             }
+            DriverError::InvalidPreset(object) => write!(
+                f,
+                "in '{}': invalid field 'preset', expected one of: {}",
+                object,
+                available_presets(object).join(", ")
+            ),
             DriverError::PathResolution(object, path) => {
                 write!(f, "in '{}': failed to resolve path '{}'", object, path)
             }
+            DriverError::UnsupportedScheme(object, scheme) => write!(
+                f,
+                "in '{}': unsupported scheme '{}', expected a path, a 'file://' URI or '-'",
+                object, scheme
+            ),
             DriverError::Internal(object, info, err) => {
                 write!(f, "in '{}': {}; {}", object, info, err)
             }
@@ -465,7 +1407,7 @@ mod tests {
     use std::panic::catch_unwind;
 
     use super::*;
-    use crate::config::{ChiselConfig, FromArgs};
+    use crate::config::{ChiselConfig, FromArgs, FromJson, FromYaml};
 
     #[test]
     fn take_result_ready() {
@@ -493,6 +1435,151 @@ mod tests {
         }
     }
 
+    #[test]
+    fn resolve_input_stdin() {
+        assert!(resolve_input("test", "-").ok() == Some(InputSource::Stdin));
+    }
+
+    #[test]
+    fn read_stdin_wasm() {
+        // wast:
+        // (module
+        //   (func (export "main")
+        //     nop)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x05, 0x01, 0x03, 0x00, 0x01, 0x0b,
+        ];
+
+        let bytes = read_input(&InputSource::Stdin, &wasm[..]).unwrap();
+        let (module, binary) = parse_input("test", &bytes).ok().unwrap();
+        assert_eq!(binary, wasm);
+        assert_eq!(
+            module.export_section().unwrap().entries()[0].field(),
+            "main"
+        );
+    }
+
+    #[test]
+    fn read_stdin_wat() {
+        let wat = "(module (func (export \"main\") nop))";
+        let bytes = read_input(&InputSource::Stdin, wat.as_bytes()).unwrap();
+        let (module, _) = parse_input("test", &bytes).ok().unwrap();
+        assert_eq!(
+            module.export_section().unwrap().entries()[0].field(),
+            "main"
+        );
+    }
+
+    #[test]
+    fn read_stdin_garbage() {
+        let bytes = read_input(&InputSource::Stdin, &b"\x00asm\x2a"[..]).unwrap();
+        assert!(parse_input("test", &bytes).is_err());
+    }
+
+    #[test]
+    fn read_stdin_hex() {
+        let wat = "(module (func (export \"main\") nop))";
+        let wasm = wat::parse_str(wat).unwrap();
+        let encoded = format!("{}\n", hex::encode(&wasm));
+        let bytes = read_input(&InputSource::Stdin, encoded.as_bytes()).unwrap();
+        let (_, binary) = parse_input("test", &bytes).ok().unwrap();
+        assert_eq!(binary, wasm);
+    }
+
+    #[test]
+    fn hex_file_chisels_like_binary() {
+        let binary = std::fs::read("./res/test/custom_section.wasm").expect("Should read input");
+        let input = std::env::temp_dir().join("chisel_hex_file_chisels_like_binary.hex");
+        std::fs::write(&input, hex::encode(&binary)).expect("Should write input");
+
+        let output = std::env::temp_dir().join("chisel_hex_file_chisels_like_binary.wasm");
+        let chisel = |file: &str| {
+            let yaml = format!(
+                "ruleset:\n  file: \"{}\"\n  output: \"{}\"\n  repack:\n    keep_custom: \"false\"\n",
+                file,
+                output.display()
+            );
+            let config = ChiselConfig::from_yaml(&serde_yaml::from_str(&yaml).unwrap())
+                .expect("Should not fail");
+            let mut driver = ChiselDriver::new(config);
+            match driver.fire() {
+                DriverState::Done(_) => (),
+                _ => panic!("Must succeed"),
+            }
+            let written = driver.take_result().write_all("bin");
+            assert_eq!(written.len(), 1);
+            assert!(written[0].1.as_ref().ok() == Some(&true));
+            let chiseled = std::fs::read(&output).expect("Should read output");
+            std::fs::remove_file(&output).expect("Should remove output");
+            chiseled
+        };
+
+        let from_hex = chisel(&input.display().to_string());
+        std::fs::remove_file(&input).expect("Should remove input");
+        assert_eq!(from_hex, chisel("./res/test/custom_section.wasm"));
+    }
+
+    #[test]
+    fn corrupt_names_section() {
+        // An empty module with a names section whose function names subsection is truncated.
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x00, 0x08, 0x04, 0x6e, 0x61, 0x6d,
+            0x65, 0x01, 0x05, 0xff,
+        ];
+        let (module, _) = parse_input("test", &wasm).ok().expect("Should not fail");
+        assert!(module.names_section().is_none());
+        assert_eq!(module.custom_sections().count(), 1);
+
+        let path = std::env::temp_dir().join("chisel_corrupt_names_section.wasm");
+        std::fs::write(&path, &wasm).expect("Should write input");
+        let mut config = ChiselConfig::from_args("checkhascode", "").expect("Should not fail");
+        config.rulesets_mut()[0]
+            .1
+            .options_mut()
+            .insert("file".to_string(), path.display().to_string());
+
+        let mut driver = ChiselDriver::new(config);
+        let state = driver.fire();
+        std::fs::remove_file(&path).expect("Should remove input");
+        match state {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+    }
+
+    #[test]
+    fn file_uri_path() {
+        let path = canonicalize("./res/test/empty.wasm").unwrap();
+        assert!(
+            resolve_input("test", &format!("file://{}", path.display())).ok()
+                == Some(InputSource::File(path.clone()))
+        );
+
+        let mut config = ChiselConfig::from_args("verifyimports", "verifyimports.preset=ewasm")
+            .expect("Cannot fail");
+        config.rulesets_mut()[0]
+            .1
+            .options_mut()
+            .insert("file".to_string(), format!("file://{}", path.display()));
+
+        let mut driver = ChiselDriver::new(config);
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+    }
+
+    #[test]
+    fn unsupported_scheme() {
+        match resolve_input("test", "https://example.com/contract.wasm") {
+            Err(DriverError::UnsupportedScheme(_, scheme)) => assert_eq!(scheme, "https"),
+            _ => panic!("Must fail"),
+        }
+    }
+
     #[test]
     fn module_not_found() {
         let config = ChiselConfig::from_args("foo", "foo.bar=baz").expect("Cannot fail");
@@ -505,6 +1592,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn invalid_preset_lists_presets() {
+        let mut config = ChiselConfig::from_args("remapimports", "remapimports.preset=foo")
+            .expect("Cannot fail");
+
+        config.rulesets_mut()[0]
+            .1
+            .options_mut()
+            .insert("file".to_string(), "./res/test/empty.wasm".to_string());
+
+        let mut driver = ChiselDriver::new(config);
+
+        match driver.fire() {
+            DriverState::Error(err, _) => assert_eq!(
+                err.to_string(),
+                "in 'remapimports': invalid field 'preset', expected one of: ewasm, eth2, eth2_v2, debug, bignum, pwasm"
+            ),
+            _ => panic!("Must fail"),
+        }
+    }
+
     #[test]
     fn execute_module_smoke() {
         let mut config = ChiselConfig::from_args("verifyimports", "verifyimports.preset=ewasm")
@@ -536,4 +1644,320 @@ mod tests {
 
         assert!(is_correct, "Module result incorrect");
     }
+
+    #[test]
+    fn strict_inplace_reports_fallback() {
+        let mut config = ChiselConfig::from_args("snip", "").expect("Cannot fail");
+
+        config.rulesets_mut()[0]
+            .1
+            .options_mut()
+            .insert("file".to_string(), "./res/test/empty.wasm".to_string());
+
+        let mut driver = ChiselDriver::new(config);
+        driver.set_strict_inplace(true);
+
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        assert_eq!(
+            driver.diagnostics(),
+            &["module 'snip' does not support in-place translation; falling back to a copy"]
+        );
+    }
+
+    #[test]
+    fn json_and_yaml_execute_identically() {
+        let json = r#"{"ruleset": {"file": "./res/test/empty.wasm", "verifyimports": {"preset": "ewasm"}, "checkfloat": null}}"#;
+        let yaml = "ruleset:\n  file: \"./res/test/empty.wasm\"\n  verifyimports:\n    preset: \"ewasm\"\n  checkfloat:\n";
+
+        let json_config =
+            ChiselConfig::from_json(&serde_json::from_str(json).unwrap()).expect("Should not fail");
+        let yaml_config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut json_driver = ChiselDriver::new(json_config);
+        let mut yaml_driver = ChiselDriver::new(yaml_config);
+        match (json_driver.fire(), yaml_driver.fire()) {
+            (DriverState::Done(_), DriverState::Done(_)) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        let json_result = format!("{}", json_driver.take_result());
+        let yaml_result = format!("{}", yaml_driver.take_result());
+        assert_eq!(json_result, yaml_result);
+        assert!(json_result.contains("verifyimports"));
+        assert!(json_result.contains("checkfloat"));
+    }
+
+    fn ensureimports_result(file: &str) -> ModuleResult {
+        let mut config = ChiselConfig::from_args("ensureimports", "ensureimports.preset=ewasm")
+            .expect("Cannot fail");
+
+        config.rulesets_mut()[0]
+            .1
+            .options_mut()
+            .insert("file".to_string(), file.to_string());
+
+        let mut driver = ChiselDriver::new(config);
+
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        driver.take_result().rulesets_mut()[0].results_mut()[0].clone()
+    }
+
+    #[test]
+    fn ensureimports_remaps_and_verifies() {
+        match ensureimports_result("./res/test/remappable_imports.wasm") {
+            ModuleResult::Translator(name, Ok(true)) => assert_eq!(name, "ensureimports"),
+            _ => panic!("Module result incorrect"),
+        }
+    }
+
+    #[test]
+    fn ensureimports_unfixable_import() {
+        match ensureimports_result("./res/test/unknown_import.wasm") {
+            ModuleResult::Translator(name, Err(_)) => assert_eq!(name, "ensureimports"),
+            _ => panic!("Module result incorrect"),
+        }
+    }
+
+    #[test]
+    fn disabled_module_skipped() {
+        let yaml = "ruleset:\n  file: \"./res/test/empty.wasm\"\n  checkfloat:\n    enabled: false\n  verifyimports:\n    preset: \"ewasm\"\n    enabled: true\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        let mut result = driver.take_result();
+        let results = result.rulesets_mut()[0].results_mut();
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            ModuleResult::Validator(name, Ok(true)) => assert_eq!(name, "verifyimports"),
+            _ => panic!("Module result incorrect"),
+        }
+    }
+
+    #[test]
+    fn run_single_ruleset() {
+        let yaml = "first:\n  file: \"./res/test/empty.wasm\"\n  checkfloat:\n\
+                    second:\n  file: \"./res/test/empty.wasm\"\n  verifyimports:\n    preset: \"ewasm\"\n\
+                    third:\n  file: \"./res/test/empty.wasm\"\n  checkstartfunc:\n    require_start: false\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+
+        let mut result = match driver.run_ruleset("second") {
+            Ok(result) => result,
+            Err(_) => panic!("Must succeed"),
+        };
+        assert_eq!(result.name(), "second");
+        assert_eq!(result.results_mut().len(), 1);
+        match &result.results_mut()[0] {
+            ModuleResult::Validator(name, Ok(true)) => assert_eq!(name, "verifyimports"),
+            _ => panic!("Module result incorrect"),
+        }
+
+        // The executed ruleset is consumed, and the others are left for fire().
+        assert!(driver.run_ruleset("second").is_err());
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+        let result = driver.take_result();
+        let names: Vec<&str> = result.rulesets().iter().map(|r| r.name()).collect();
+        assert_eq!(names, vec!["first", "third"]);
+    }
+
+    #[test]
+    fn run_ruleset_not_found() {
+        let config = ChiselConfig::from_args("checkfloat", "").expect("Cannot fail");
+        let mut driver = ChiselDriver::new(config);
+
+        match driver.run_ruleset("foo") {
+            Err(DriverError::RulesetNotFound(name)) => assert_eq!(name, "foo"),
+            _ => panic!("Must fail with RulesetNotFound"),
+        }
+    }
+
+    #[test]
+    fn strict_invalid_import() {
+        let yaml = "ruleset:\n  file: \"./res/test/unknown_import.wasm\"\n  verifyimports:\n    preset: \"ewasm\"\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        let result = driver.take_result();
+        assert_eq!(result.failures(), vec!["ruleset.verifyimports".to_string()]);
+        assert_eq!(result.strict_exit_code().0, 1);
+    }
+
+    #[test]
+    fn strict_valid_module() {
+        let yaml = "ruleset:\n  file: \"./res/test/empty.wasm\"\n  verifyimports:\n    preset: \"ewasm\"\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        let result = driver.take_result();
+        assert!(result.failures().is_empty());
+        assert_eq!(result.strict_exit_code().0, 0);
+    }
+
+    #[test]
+    fn strict_skipped_ruleset() {
+        let yaml = "invalid:\n  file: \"./res/test/unknown_import.wasm\"\n  verifyimports:\n    preset: \"ewasm\"\nbroken:\n  file: \"./res/test/empty.wasm\"\n  foo:\n    bar: \"baz\"\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        match driver.fire() {
+            DriverState::Error(DriverError::ModuleNotFound(_), _) => (),
+            _ => panic!("Must fail on the unknown module"),
+        }
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed after skipping the ruleset"),
+        }
+
+        let result = driver.take_result();
+        assert_eq!(
+            result.failures(),
+            vec!["invalid.verifyimports".to_string(), "broken".to_string()]
+        );
+        assert_eq!(result.strict_exit_code().0, 1);
+        assert!(result.rulesets()[1].error().is_some());
+        assert!(result.to_plain_string().contains("SKIPPED; "));
+    }
+
+    #[test]
+    fn dry_run_records_size_change() {
+        let yaml = "ruleset:\n  file: \"./res/test/custom_section.wasm\"\n  repack:\n    enabled: true\n    keep_custom: \"false\"\n  checkfloat:\n    enabled: true\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        driver.set_dry_run(true);
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        let result = driver.take_result();
+        let ruleset = &result.rulesets()[0];
+        // Repack drops the custom section.
+        assert_eq!(ruleset.size_change(0), Some((17, 8)));
+        // Validators are not measured.
+        assert_eq!(ruleset.size_change(1), None);
+    }
+
+    #[test]
+    fn stats_reported() {
+        let yaml = "ruleset:\n  file: \"./res/test/remappable_imports.wasm\"\n  remapimports:\n    preset: \"ewasm\"\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        driver.set_stats(true);
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        let result = driver.take_result();
+        let summary = result.to_plain_string();
+        assert!(summary.contains("Statistics:"));
+        assert!(summary.contains("section sizes: type "));
+        assert!(summary.contains("imports: 1"));
+        assert!(result.to_json().contains("\"stats\""));
+    }
+
+    #[test]
+    fn rulesets_write_own_outputs() {
+        let dir = std::env::temp_dir();
+        let first = dir.join("chisel_rulesets_write_own_outputs_1.wasm");
+        let second = dir.join("chisel_rulesets_write_own_outputs_2.hex");
+        let yaml = format!(
+            "first:\n  file: \"./res/test/custom_section.wasm\"\n  output: \"{}\"\n  repack:\n    keep_custom: \"false\"\nsecond:\n  file: \"./res/test/custom_section.wasm\"\n  output: \"{}\"\n  output_mode: \"hex\"\n  repack:\n    keep_custom: \"false\"\n",
+            first.display(),
+            second.display()
+        );
+        let config = ChiselConfig::from_yaml(&serde_yaml::from_str(&yaml).unwrap())
+            .expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        match driver.fire() {
+            DriverState::Done(_) => (),
+            _ => panic!("Must succeed"),
+        }
+
+        let mut result = driver.take_result();
+        let written: Vec<bool> = result
+            .write_all("bin")
+            .into_iter()
+            .map(|(_, result)| result.ok() == Some(true))
+            .collect();
+        assert_eq!(written, vec![true, true]);
+
+        let first_output = std::fs::read(&first).expect("Should read first output");
+        let second_output = std::fs::read_to_string(&second).expect("Should read second output");
+        std::fs::remove_file(&first).expect("Should remove first output");
+        std::fs::remove_file(&second).expect("Should remove second output");
+
+        assert_eq!(&first_output[0..4], b"\0asm");
+        assert_eq!(second_output, hex::encode(&first_output));
+    }
+
+    #[test]
+    fn run_bytes_in_memory() {
+        let wasm = std::fs::read("./res/test/custom_section.wasm").expect("Should read input");
+        let yaml = "ruleset:\n  repack:\n    keep_custom: \"false\"\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let result = match ChiselDriver::run_bytes(config, &wasm) {
+            Ok(result) => result,
+            Err(_) => panic!("Must succeed"),
+        };
+        let ruleset = &result.rulesets()[0];
+        let output = ruleset.output_module().expect("Module was mutated");
+        assert_eq!(output.custom_sections().count(), 0);
+    }
+
+    #[test]
+    fn invalid_output_mode() {
+        let yaml =
+            "ruleset:\n  file: \"./res/test/empty.wasm\"\n  output_mode: \"elf\"\n  repack:\n";
+        let config =
+            ChiselConfig::from_yaml(&serde_yaml::from_str(yaml).unwrap()).expect("Should not fail");
+
+        let mut driver = ChiselDriver::new(config);
+        match driver.fire() {
+            DriverState::Error(err, _) => {
+                assert_eq!(err.to_string(), "in 'ruleset': invalid field 'output_mode'")
+            }
+            _ => panic!("Must fail"),
+        }
+    }
 }
@@ -0,0 +1,11 @@
+//! The chisel driver, which runs the rulesets of a configuration on a module. The `chisel`
+//! binary is built on top of it, and other tools can embed it, e.g. through
+//! `ChiselDriver::run_bytes` to chisel a module held in memory.
+
+#[macro_use]
+pub mod logger;
+pub mod config;
+pub mod diff;
+pub mod driver;
+pub mod options;
+pub mod result;
@@ -3,7 +3,7 @@ static mut LOG_LEVEL: i32 = 0;
 #[macro_export]
 macro_rules! chisel_debug {
     ($lvl:expr, $($arg:tt)*) => {
-        crate::logger::Logger::with_global_level().log($lvl, &format!($($arg)*));
+        $crate::logger::Logger::with_global_level().log($lvl, &format!($($arg)*));
     }
 }
 
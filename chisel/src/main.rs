@@ -1,19 +1,18 @@
 #[macro_use]
-mod logger;
+extern crate chisel;
+
+mod cmd_list;
 mod cmd_oneliner;
 mod cmd_run;
-mod config;
-mod driver;
-mod options;
-mod result;
 
 use std::process;
 
 use clap::{crate_description, crate_name, crate_version, App, Arg, SubCommand};
 
+use chisel::options::ChiselFlags;
+use cmd_list::chisel_list;
 use cmd_oneliner::chisel_oneliner;
 use cmd_run::chisel_run;
-use options::ChiselFlags;
 
 fn fail(code: i32, message: &str) -> ! {
     eprintln!("{}: {}", crate_name!(), message);
@@ -38,6 +37,51 @@ pub fn main() {
                 .help("Enables debug messages")
                 .global(true),
         )
+        .arg(
+            Arg::with_name("QUIET")
+                .short("q")
+                .long("quiet")
+                .help("Suppresses the result summary and success messages")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("STRICT_INPLACE")
+                .long("strict-inplace")
+                .help("Reports modules which fall back from in-place translation to copying the module")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("DRY_RUN")
+                .long("dry-run")
+                .help("Reports the module size before and after each translator without writing any output")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("STRICT")
+                .long("strict")
+                .help("Exits with a nonzero code if any validator reports INVALID or any module fails")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("STATS")
+                .long("stats")
+                .help("Reports section sizes, item counts and an instruction histogram of each resulting module")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("DIFF")
+                .long("diff")
+                .help("Reports which sections each ruleset added, removed or resized")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("REPORT_PATH")
+                .long("report")
+                .value_name("FILE")
+                .help("Also writes the execution summary to a file, without colours")
+                .takes_value(true)
+                .global(true),
+        )
         .arg(
             Arg::with_name("MODULES")
                 .short("m")
@@ -68,10 +112,10 @@ pub fn main() {
                 .long("output-mode")
                 .takes_value(true)
                 .help("Selects the type of output")
-                .possible_values(&["bin", "wat", "hex"])
+                .possible_values(&["bin", "wat", "hex", "json"])
                 .global(true)
         )
-        .arg(Arg::with_name("FILE").help("File to chisel"))
+        .arg(Arg::with_name("FILE").help("File to chisel, or '-' to read from stdin"))
         .subcommand(
             SubCommand::with_name("run")
                 .about("Runs chisel in config-driven mode.")
@@ -82,18 +126,27 @@ pub fn main() {
                         .help("Sets the configuration file in config-driven mode.")
                         .value_name("PATH")
                         .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("CONFIG_JSON")
+                        .long("config-json")
+                        .help("Parses the configuration file as JSON. Implied by a .json extension."),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("Lists the available modules, their kind and whether they require a preset."),
+        )
         .after_help("chisel runs in two primary modes: unix-style and config-driven.\n\nunix-style is invoked without a subcommand. \
                     It allows the user to run chisel in a single command and manipulate or redirect its output through standard streams. \
                     \nUsage example: chisel file.wasm --modules remapimports --config remapimports.preset=ewasm \
-                    \n\nConfig-driven mode relies entirely on a configuration file written in YAML. It is invoked with 'chisel run'. \
+                    \n\nConfig-driven mode relies entirely on a configuration file written in YAML, JSON or TOML. It is invoked with 'chisel run'. \
                     For more information on the configuration format, please refer to https://github.com/wasmx/wasm-chisel")
         .get_matches();
 
     let mut flags = ChiselFlags::default();
 
-    match cli_matches.subcommand() {
+    let code = match cli_matches.subcommand() {
         ("run", args) => {
             if let Some(opts) = args {
                 flags.apply(opts);
@@ -101,10 +154,12 @@ pub fn main() {
 
             chisel_run(flags)
         }
+        ("list", _) => chisel_list(),
         ("", None) => {
             flags.apply(&cli_matches);
             chisel_oneliner(flags)
         }
         (_, _) => fail(1, "invalid subcommand"),
     };
+    process::exit(code);
 }
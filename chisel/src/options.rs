@@ -9,15 +9,24 @@
 //! Options:
 //! NO_RECOVER: Forces panic on recoverable errors.
 //! VERBOSE: Enables verbose debug logging.
+//! QUIET: Suppresses the result summary and success messages. Errors are still reported.
+//! STRICT_INPLACE: Reports modules which do not support in-place translation.
 //! CONFIG: Overrides the configuration file path in config-driven mode.
+//! CONFIG_JSON: Parses the configuration file as JSON in config-driven mode.
+//! REPORT_PATH: Also writes the execution summary to a file.
+//! DRY_RUN: Reports the size of the module around each translator without writing any output.
+//! STRICT: Exits with a nonzero code if any validator reports INVALID or any module fails.
+//! STATS: Reports size and content statistics of the module produced by each ruleset.
+//! DIFF: Reports the sections added, removed or resized by each ruleset.
 //! MODULES: A list of modules to invoke in oneliner mode.
 //! MODULE_OPTIONS: A list of options set for the modules being invoked in oneliner mode.
-//! FILE: Sets the input file path in oneliner mode.
+//! FILE: Sets the input file path in oneliner mode. `-` reads the module from stdin.
 //! OUTPUT_PATH: Sets the path to write any mutated binaries in oneliner mode.
 //! OUTPUT_MODE: Sets the format in which to output mutated binaries.
 //!      - wasm: default binary mode. disallowed when writing to stdout.
 //!      - hex: write the output in hex. recommended if writing to stdout.
 //!      - wat: write the output in disassembled (.wat) format.
+//!      - json: print the module results as JSON to stdout, and write the output in binary.
 
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -58,9 +67,33 @@ impl ChiselFlags {
         if matches.is_present("VERBOSE") {
             self.set("util.debugging", "true");
         }
+        if matches.is_present("QUIET") {
+            self.set("util.quiet", "true");
+        }
+        if matches.is_present("STRICT_INPLACE") {
+            self.set("util.strictinplace", "true");
+        }
+        if matches.is_present("DRY_RUN") {
+            self.set("util.dryrun", "true");
+        }
+        if matches.is_present("STRICT") {
+            self.set("util.strict", "true");
+        }
+        if matches.is_present("STATS") {
+            self.set("util.stats", "true");
+        }
+        if matches.is_present("DIFF") {
+            self.set("util.diff", "true");
+        }
+        if let Some(value) = matches.value_of("REPORT_PATH") {
+            self.set("util.report", value);
+        }
         if let Some(value) = matches.value_of("CONFIG") {
             self.set("run.config.path", value);
         }
+        if matches.is_present("CONFIG_JSON") {
+            self.set("run.config.json", "true");
+        }
         if let Some(values) = matches.values_of("MODULES") {
             let values_collected = values.fold(String::new(), |mut acc, val| {
                 acc.push_str(&format!("{},", val));
@@ -83,10 +116,10 @@ impl ChiselFlags {
         }
         if let Some(value) = matches.value_of("OUTPUT_MODE") {
             match value {
-                val @ "bin" | val @ "wat" | val @ "hex" => {
+                val @ "bin" | val @ "wat" | val @ "hex" | val @ "json" => {
                     self.set("output.mode", val);
                 }
-                _ => panic!("CLI parser only accepts 'bin', 'wat', or 'hex'"),
+                _ => panic!("CLI parser only accepts 'bin', 'wat', 'hex', or 'json'"),
             }
         }
     }
@@ -106,8 +139,15 @@ impl Default for ChiselFlags {
 
         ret.set("util.norecover", "false");
         ret.set("util.debugging", "false");
+        ret.set("util.quiet", "false");
+        ret.set("util.strictinplace", "false");
+        ret.set("util.dryrun", "false");
+        ret.set("util.strict", "false");
+        ret.set("util.stats", "false");
+        ret.set("util.diff", "false");
         ret.set("output.mode", "bin");
         ret.set("run.config.path", "./chisel.yml");
+        ret.set("run.config.json", "false");
         ret
     }
 }
@@ -121,7 +161,13 @@ mod tests {
         let options = ChiselFlags::default();
         assert!(options.value_eq("util.norecover", "false"));
         assert!(options.value_eq("util.debugging", "false"));
+        assert!(options.value_eq("util.quiet", "false"));
+        assert!(options.value_eq("util.strictinplace", "false"));
+        assert!(options.value_eq("util.dryrun", "false"));
+        assert!(options.value_eq("util.stats", "false"));
+        assert!(options.value_eq("util.diff", "false"));
         assert!(options.value_eq("output.mode", "bin"));
         assert!(options.value_eq("run.config.path", "./chisel.yml"));
+        assert!(options.value_eq("run.config.json", "false"));
     }
 }
@@ -8,16 +8,25 @@
 //! RulesetResult also implements utilities for writing the resulting Wasm module to file, if the
 //! driver performed any transformations.
 
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{self, Display};
 use std::fs::write;
+use std::io::{self, Write};
 use std::path::PathBuf;
 
 use ansi_term::Colour::{Green, Red, Yellow};
+use ansi_term::Style;
+use serde_json::{json, Value};
 
-use libchisel::{Module, ModuleError};
+use libchisel::{stats::ModuleStats, Module, ModuleError};
 
-#[derive(Clone)]
+use crate::diff::SectionDiff;
+
+/// The result of writing a ruleset's output: whether anything was written.
+pub type WriteResult = Result<bool, Box<dyn Error>>;
+
+#[derive(Clone, Default)]
 /// Main result structure returned by ChiselDriver, containing a manifest of modules executed and
 /// exposing methods to write output from translators and creators.
 pub struct ChiselResult(Vec<RulesetResult>);
@@ -27,8 +36,19 @@ pub struct ChiselResult(Vec<RulesetResult>);
 pub struct RulesetResult {
     ruleset_name: String,
     results: Vec<ModuleResult>,
+    /// Serialized size of the module before and after a translator, keyed by the index of its
+    /// result. Only recorded in dry-run mode.
+    size_changes: HashMap<usize, (usize, usize)>,
     output_path: PathBuf,
+    /// The format to write the output in, if the ruleset overrides the one given on the CLI.
+    output_mode: Option<String>,
     output_module: Option<Module>,
+    /// Statistics of the module produced by the ruleset. Only collected if requested.
+    stats: Option<ModuleStats>,
+    /// Sections changed by the ruleset. Only collected if requested.
+    diff: Option<Vec<SectionDiff>>,
+    /// The runtime error the ruleset was skipped on, if any.
+    error: Option<String>,
 }
 
 #[derive(Clone)]
@@ -52,6 +72,77 @@ impl ChiselResult {
     pub fn rulesets(&self) -> &Vec<RulesetResult> {
         &self.0
     }
+
+    /// Writes the coloured summary to `out`, or nothing if `quiet` is set.
+    pub fn write_summary<W: Write>(&self, out: &mut W, quiet: bool) -> io::Result<()> {
+        if quiet {
+            Ok(())
+        } else {
+            writeln!(out, "{}", self)
+        }
+    }
+
+    /// Renders the summary without colours, as written to report files.
+    pub fn to_plain_string(&self) -> String {
+        format!("{}", PlainSummary(self))
+    }
+
+    /// Writes the plain summary to the file at `path`.
+    pub fn write_report(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write(path, self.to_plain_string())?;
+        Ok(())
+    }
+
+    /// Serializes the results of every ruleset and module as JSON, for consumption by CI systems.
+    pub fn to_json(&self) -> String {
+        let rulesets: Vec<Value> = self.0.iter().map(|ruleset| ruleset.to_json()).collect();
+        json!({ "rulesets": rulesets }).to_string()
+    }
+
+    /// Writes the output of every ruleset to its own output path, in the ruleset's output mode or
+    /// `default_mode` if it has none. Returns the name of each ruleset with its write result.
+    pub fn write_all(&mut self, default_mode: &str) -> Vec<(String, WriteResult)> {
+        self.0
+            .iter_mut()
+            .map(|ruleset| {
+                let mode = ruleset.output_mode().unwrap_or(default_mode).to_string();
+                (ruleset.name().to_string(), ruleset.write(&mode))
+            })
+            .collect()
+    }
+
+    /// Returns `ruleset.module` for every validator which found the module invalid and every
+    /// module which failed with an error, and `ruleset` for every ruleset skipped on a runtime
+    /// error.
+    pub fn failures(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|ruleset| {
+                let skipped = ruleset.error.as_ref().map(|_| ruleset.name().to_string());
+                ruleset
+                    .results
+                    .iter()
+                    .filter(|result| result.failed())
+                    .map(move |result| format!("{}.{}", ruleset.name(), result.name()))
+                    .chain(skipped)
+            })
+            .collect()
+    }
+
+    /// Returns the exit code in strict mode, nonzero if any module failed or any ruleset was
+    /// skipped, along with the failures as listed by `failures`.
+    pub fn strict_exit_code(&self) -> (i32, Vec<String>) {
+        let failures = self.failures();
+        let code = if failures.is_empty() { 0 } else { 1 };
+        (code, failures)
+    }
+
+    fn render(&self, f: &mut fmt::Formatter, colored: bool) -> fmt::Result {
+        self.0
+            .iter()
+            .map(|ruleset_result| ruleset_result.render(f, colored))
+            .fold(Ok(()), |acc, r| if r.is_err() { r } else { acc })
+    }
 }
 
 impl RulesetResult {
@@ -59,8 +150,13 @@ impl RulesetResult {
         RulesetResult {
             ruleset_name: name,
             results: Vec::new(),
+            size_changes: HashMap::new(),
             output_path: PathBuf::new(),
+            output_mode: None,
             output_module: None,
+            stats: None,
+            diff: None,
+            error: None,
         }
     }
 
@@ -72,18 +168,145 @@ impl RulesetResult {
         &mut self.results
     }
 
+    /// Records the serialized size of the module before and after the module whose result is at
+    /// `index`.
+    pub fn set_size_change(&mut self, index: usize, before: usize, after: usize) {
+        self.size_changes.insert(index, (before, after));
+    }
+
+    /// Returns the recorded serialized sizes before and after the module whose result is at
+    /// `index`.
+    pub fn size_change(&self, index: usize) -> Option<(usize, usize)> {
+        self.size_changes.get(&index).cloned()
+    }
+
     pub fn set_output_path(&mut self, path: PathBuf) {
         self.output_path = path;
     }
 
+    pub fn set_output_mode(&mut self, mode: String) {
+        self.output_mode = Some(mode);
+    }
+
+    /// Returns the output format set by the ruleset, if any.
+    pub fn output_mode(&self) -> Option<&str> {
+        self.output_mode.as_deref()
+    }
+
     pub fn set_output_module(&mut self, module: Module) {
         self.output_module = Some(module);
     }
 
+    /// Returns the output module if any module mutated or created it, and it was not written yet.
+    pub fn output_module(&self) -> Option<&Module> {
+        self.output_module.as_ref()
+    }
+
+    pub fn set_stats(&mut self, stats: ModuleStats) {
+        self.stats = Some(stats);
+    }
+
+    pub fn set_diff(&mut self, diff: Vec<SectionDiff>) {
+        self.diff = Some(diff);
+    }
+
+    /// Returns the sections changed by the ruleset, if they were compared.
+    pub fn diff(&self) -> Option<&[SectionDiff]> {
+        self.diff.as_deref()
+    }
+
+    /// Marks the ruleset as skipped on the given runtime error.
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Returns the runtime error the ruleset was skipped on, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    fn to_json(&self) -> Value {
+        let results: Vec<Value> = self
+            .results
+            .iter()
+            .enumerate()
+            .map(|(index, module_result)| {
+                let mut ret = module_result.to_json();
+                if let Some((before, after)) = self.size_change(index) {
+                    ret["size_before"] = json!(before);
+                    ret["size_after"] = json!(after);
+                }
+                ret
+            })
+            .collect();
+        let mut ret = json!({ "name": self.name(), "results": results });
+        if let Some(error) = &self.error {
+            ret["error"] = Value::String(error.clone());
+        }
+        if let Some(stats) = &self.stats {
+            let sections: Vec<Value> = stats
+                .section_sizes
+                .iter()
+                .map(|(name, size)| json!({ "name": name, "size": size }))
+                .collect();
+            ret["stats"] = json!({
+                "sections": sections,
+                "functions": stats.function_count,
+                "imports": stats.import_count,
+                "exports": stats.export_count,
+                "opcodes": stats.opcodes,
+            });
+        }
+        if let Some(diff) = &self.diff {
+            let sections: Vec<Value> = diff
+                .iter()
+                .map(|section| {
+                    let (before, after) = section.sizes();
+                    json!({ "name": section.name(), "before": before, "after": after })
+                })
+                .collect();
+            ret["diff"] = json!(sections);
+        }
+        ret
+    }
+
+    fn render(&self, f: &mut fmt::Formatter, colored: bool) -> fmt::Result {
+        let result = write!(f, "\nRuleset {}:", &self.name()).and_then(|_| match &self.error {
+            Some(error) => write!(f, "\n\tSKIPPED; {}", error),
+            None => Ok(()),
+        });
+        if let Err(e) = self
+            .results
+            .iter()
+            .enumerate()
+            .map(|(index, module_result)| {
+                write!(f, "\n\t")?;
+                module_result.render(f, colored)?;
+                if let Some((before, after)) = self.size_change(index) {
+                    write!(f, " ({} -> {} bytes)", before, after)?;
+                }
+                Ok(())
+            })
+            .fold(Ok(()), |acc, r| if r.is_err() { r } else { acc })
+        {
+            Err(e)
+        } else {
+            result
+                .and_then(|_| match &self.stats {
+                    Some(stats) => render_stats(f, stats),
+                    None => Ok(()),
+                })
+                .and_then(|_| match &self.diff {
+                    Some(diff) => render_diff(f, diff),
+                    None => Ok(()),
+                })
+        }
+    }
+
     /// Write output module to specified file if the module was mutated.
     /// Returns Ok(false) if there is no mutation.
     /// Returns error on writer error or invalid mode.
-    pub fn write(&mut self, mode: &str) -> Result<bool, Box<dyn Error>> {
+    pub fn write(&mut self, mode: &str) -> WriteResult {
         if let Some(module) = self.output_module.take() {
             let path = PathBuf::from(&self.output_path);
             let ret = match mode {
@@ -119,80 +342,162 @@ impl RulesetResult {
     }
 }
 
+impl ModuleResult {
+    fn name(&self) -> &str {
+        match self {
+            ModuleResult::Creator(name, _)
+            | ModuleResult::Translator(name, _)
+            | ModuleResult::Validator(name, _) => name,
+        }
+    }
+
+    /// Whether the module failed with an error or, for validators, found the module invalid.
+    fn failed(&self) -> bool {
+        match self {
+            ModuleResult::Validator(_, Ok(false)) => true,
+            ModuleResult::Creator(_, result)
+            | ModuleResult::Translator(_, result)
+            | ModuleResult::Validator(_, result) => result.is_err(),
+        }
+    }
+
+    /// Returns the module kind, the module name, and the outcome along with its colour.
+    fn outcome(&self) -> (&'static str, &str, String, Style) {
+        let (kind, name, result, labels) = match self {
+            ModuleResult::Creator(name, result) => {
+                ("Creator", name, result, [("OK", Green), ("FAILED", Red)])
+            }
+            ModuleResult::Translator(name, result) => (
+                "Translator",
+                name,
+                result,
+                [("MUTATED", Yellow), ("NO CHANGE", Green)],
+            ),
+            ModuleResult::Validator(name, result) => (
+                "Validator",
+                name,
+                result,
+                [("VALID", Green), ("INVALID", Red)],
+            ),
+        };
+        let (text, style) = match result {
+            Ok(r) => {
+                let (text, colour) = if *r { labels[0] } else { labels[1] };
+                (text.to_string(), colour.normal())
+            }
+            Err(e) => (format!("ERROR; {}", e.description()), Red.bold()),
+        };
+        (kind, name.as_str(), text, style)
+    }
+
+    /// Returns the machine-readable outcome, and the error message if the module failed.
+    fn status(&self) -> (&'static str, Option<String>) {
+        let (result, labels) = match self {
+            ModuleResult::Creator(_, result) => (result, ["OK", "FAILED"]),
+            ModuleResult::Translator(_, result) => (result, ["MUTATED", "NO_CHANGE"]),
+            ModuleResult::Validator(_, result) => (result, ["VALID", "INVALID"]),
+        };
+        match result {
+            Ok(true) => (labels[0], None),
+            Ok(false) => (labels[1], None),
+            Err(e) => ("ERROR", Some(e.description().to_string())),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let (kind, name, _, _) = self.outcome();
+        let (outcome, message) = self.status();
+        let mut ret = json!({ "name": name, "kind": kind, "outcome": outcome });
+        if let Some(message) = message {
+            ret["message"] = Value::String(message);
+        }
+        ret
+    }
+
+    fn render(&self, f: &mut fmt::Formatter, colored: bool) -> fmt::Result {
+        let (kind, name, text, style) = self.outcome();
+        if colored {
+            write!(f, "{} {}: {}", kind, name, style.paint(text))?;
+        } else {
+            write!(f, "{} {}: {}", kind, name, text)?;
+        }
+        // Creator results end with a newline.
+        match self {
+            ModuleResult::Creator(..) => writeln!(f),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Renders the statistics of a ruleset's module below its module results. Instructions are listed
+/// from the most to the least frequent.
+fn render_stats(f: &mut fmt::Formatter, stats: &ModuleStats) -> fmt::Result {
+    let sections: Vec<String> = stats
+        .section_sizes
+        .iter()
+        .map(|(name, size)| format!("{} {}", name, size))
+        .collect();
+    let mut opcodes: Vec<(&&str, &usize)> = stats.opcodes.iter().collect();
+    opcodes.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    let opcodes: Vec<String> = opcodes
+        .iter()
+        .map(|(name, count)| format!("{} {}", name, count))
+        .collect();
+
+    write!(f, "\n\tStatistics:")?;
+    write!(f, "\n\t\tsection sizes: {}", sections.join(", "))?;
+    write!(
+        f,
+        "\n\t\tfunctions: {}, imports: {}, exports: {}",
+        stats.function_count, stats.import_count, stats.export_count
+    )?;
+    write!(f, "\n\t\tinstructions: {}", opcodes.join(", "))
+}
+
+/// Renders the sections changed by a ruleset below its module results.
+fn render_diff(f: &mut fmt::Formatter, diff: &[SectionDiff]) -> fmt::Result {
+    write!(f, "\n\tDiff:")?;
+    if diff.is_empty() {
+        return write!(f, " no changes");
+    }
+    for section in diff {
+        match section {
+            SectionDiff::Added(name, size) => write!(f, "\n\t\tadded {} ({} bytes)", name, size)?,
+            SectionDiff::Removed(name, size) => {
+                write!(f, "\n\t\tremoved {} ({} bytes)", name, size)?
+            }
+            SectionDiff::Resized(name, before, after) => {
+                write!(f, "\n\t\tresized {} ({} -> {} bytes)", name, before, after)?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Display wrapper rendering a ChiselResult without colours.
+struct PlainSummary<'a>(&'a ChiselResult);
+
+impl<'a> Display for PlainSummary<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.render(f, false)
+    }
+}
+
 impl Display for ChiselResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0
-            .iter()
-            .map(|ruleset_result| write!(f, "{}", ruleset_result))
-            .fold(Ok(()), |acc, r| if r.is_err() { r } else { acc })
+        self.render(f, true)
     }
 }
 
 impl Display for RulesetResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let result = write!(f, "\nRuleset {}:", &self.name());
-        if let Err(e) = self
-            .results
-            .iter()
-            .map(|module_result| write!(f, "\n\t{}", module_result))
-            .fold(Ok(()), |acc, r| if r.is_err() { r } else { acc })
-        {
-            Err(e)
-        } else {
-            result
-        }
+        self.render(f, true)
     }
 }
 
 impl Display for ModuleResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ModuleResult::Creator(name, result) => writeln!(
-                f,
-                "Creator {}: {}",
-                name,
-                match result {
-                    Ok(r) => {
-                        if *r {
-                            Green.paint("OK")
-                        } else {
-                            Red.paint("FAILED")
-                        }
-                    }
-                    Err(e) => Red.bold().paint(format!("ERROR; {}", e.description())),
-                }
-            ),
-            ModuleResult::Translator(name, result) => write!(
-                f,
-                "Translator {}: {}",
-                name,
-                match result {
-                    Ok(r) => {
-                        if *r {
-                            Yellow.paint("MUTATED")
-                        } else {
-                            Green.paint("NO CHANGE")
-                        }
-                    }
-                    Err(e) => Red.bold().paint(format!("ERROR; {}", e.description())),
-                }
-            ),
-            ModuleResult::Validator(name, result) => write!(
-                f,
-                "Validator {}: {}",
-                name,
-                match result {
-                    Ok(r) => {
-                        if *r {
-                            Green.paint("VALID")
-                        } else {
-                            Red.paint("INVALID")
-                        }
-                    }
-                    Err(e) => Red.bold().paint(format!("ERROR; {}", e.description())),
-                }
-            ),
-        }
+        self.render(f, true)
     }
 }
 
@@ -249,6 +554,28 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn writer_wat_uses_names() {
+        let wasm = wat::parse_str("(module (func $main (export \"main\") (param $x i32)))")
+            .expect("Should parse");
+        let module = Module::from_bytes(&wasm)
+            .expect("Should deserialize")
+            .parse_names()
+            .ok()
+            .expect("Should parse names");
+
+        let path = std::env::temp_dir().join("chisel_writer_wat_uses_names.wat");
+        let mut ruleset_result = RulesetResult::new("Test".to_string());
+        ruleset_result.set_output_module(module);
+        ruleset_result.set_output_path(path.clone());
+        assert_eq!(ruleset_result.write("wat").ok(), Some(true));
+
+        let wat = std::fs::read_to_string(&path).expect("Should read output");
+        std::fs::remove_file(&path).expect("Should remove output");
+        assert!(wat.contains("(func $main"));
+        assert!(wat.contains("(param $x i32)"));
+    }
+
     #[test]
     fn writer_no_module() {
         let mut ruleset_result = RulesetResult::new("Test".to_string());
@@ -263,4 +590,108 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.expect("Should be Ok"), false);
     }
+
+    #[test]
+    fn report_matches_summary() {
+        let mut chisel_result = ChiselResult::new();
+        let mut ruleset_result = RulesetResult::new("Test".to_string());
+        ruleset_result.results_mut().push(ModuleResult::Validator(
+            "verifyexports".to_string(),
+            Ok(true),
+        ));
+        ruleset_result.results_mut().push(ModuleResult::Translator(
+            "remapimports".to_string(),
+            Err(ModuleError::NotSupported),
+        ));
+        chisel_result.rulesets_mut().push(ruleset_result);
+
+        let path = std::env::temp_dir().join("chisel_report_matches_summary.txt");
+        let path = path.to_str().expect("Path is valid unicode");
+        chisel_result
+            .write_report(path)
+            .expect("Should write report");
+        let report = std::fs::read_to_string(path).expect("Should read report");
+        std::fs::remove_file(path).expect("Should remove report");
+
+        assert_eq!(report, chisel_result.to_plain_string());
+        assert!(!report.contains('\x1b'));
+        assert!(report.contains("Validator verifyexports: VALID"));
+        assert!(report.contains("Translator remapimports: ERROR; "));
+    }
+
+    #[test]
+    fn quiet_summary() {
+        let mut chisel_result = ChiselResult::new();
+        let mut ruleset_result = RulesetResult::new("Test".to_string());
+        ruleset_result.results_mut().push(ModuleResult::Validator(
+            "verifyexports".to_string(),
+            Ok(false),
+        ));
+        chisel_result.rulesets_mut().push(ruleset_result);
+
+        let mut out = Vec::new();
+        chisel_result
+            .write_summary(&mut out, true)
+            .expect("Should write summary");
+        assert!(out.is_empty());
+
+        chisel_result
+            .write_summary(&mut out, false)
+            .expect("Should write summary");
+        let summary = String::from_utf8(out).expect("Summary is valid unicode");
+        assert!(summary.contains("Validator verifyexports: "));
+        // Quiet mode does not affect the strict exit code.
+        assert_eq!(chisel_result.strict_exit_code().0, 1);
+    }
+
+    #[test]
+    fn json_validator_failure() {
+        let mut chisel_result = ChiselResult::new();
+        let mut ruleset_result = RulesetResult::new("Test".to_string());
+        ruleset_result.results_mut().push(ModuleResult::Validator(
+            "verifyexports".to_string(),
+            Ok(false),
+        ));
+        ruleset_result.results_mut().push(ModuleResult::Translator(
+            "remapimports".to_string(),
+            Err(ModuleError::NotSupported),
+        ));
+        chisel_result.rulesets_mut().push(ruleset_result);
+
+        let json: Value = serde_json::from_str(&chisel_result.to_json()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "rulesets": [{
+                    "name": "Test",
+                    "results": [
+                        { "name": "verifyexports", "kind": "Validator", "outcome": "INVALID" },
+                        {
+                            "name": "remapimports",
+                            "kind": "Translator",
+                            "outcome": "ERROR",
+                            "message": ModuleError::NotSupported.description()
+                        }
+                    ]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn size_change_rendered() {
+        let mut chisel_result = ChiselResult::new();
+        let mut ruleset_result = RulesetResult::new("Test".to_string());
+        ruleset_result
+            .results_mut()
+            .push(ModuleResult::Translator("repack".to_string(), Ok(true)));
+        ruleset_result.set_size_change(0, 1024, 980);
+        chisel_result.rulesets_mut().push(ruleset_result);
+
+        assert!(chisel_result
+            .to_plain_string()
+            .contains("Translator repack: MUTATED (1024 -> 980 bytes)"));
+        let json: Value = serde_json::from_str(&chisel_result.to_json()).unwrap();
+        assert_eq!(json["rulesets"][0]["results"][0]["size_after"], 980);
+    }
 }
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{CustomSection, Module, Section};
+use rustc_hex::FromHex;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Appends a custom section with the given name and payload, such as a content hash or a
+/// compiler id. A custom section of the same name is replaced in place rather than duplicated.
+pub struct AddCustomSection {
+    name: String,
+    payload: Vec<u8>,
+}
+
+impl AddCustomSection {
+    pub fn new(name: String, payload: Vec<u8>) -> Self {
+        AddCustomSection { name, payload }
+    }
+}
+
+impl<'a> ChiselModule<'a> for AddCustomSection {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "addcustomsection".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        let (name, payload_hex) = match (config.get("name"), config.get("payload_hex")) {
+            (Some(name), Some(payload_hex)) => (name, payload_hex),
+            _ => return Err(ModuleError::NotSupported),
+        };
+        // The payload may be given with or without a `0x` prefix.
+        let payload_hex = payload_hex.trim_start_matches("0x");
+        let payload = payload_hex
+            .from_hex()
+            .map_err(|e| ModuleError::Custom(format!("invalid hex in 'payload_hex': {}", e)))?;
+        Ok(AddCustomSection::new(name.clone(), payload))
+    }
+}
+
+impl ModuleTranslator for AddCustomSection {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(add_custom_section(module, &self.name, &self.payload))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if add_custom_section(&mut ret, &self.name, &self.payload) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Replaces the payload of the first custom section called `name`, or appends a new one. Returns
+/// false if the section was already present with the same payload.
+fn add_custom_section(module: &mut Module, name: &str, payload: &[u8]) -> bool {
+    for section in module.sections_mut() {
+        if let Section::Custom(custom) = section {
+            if custom.name() == name {
+                if custom.payload() == payload {
+                    return false;
+                }
+                *custom.payload_mut() = payload.to_vec();
+                return true;
+            }
+        }
+    }
+
+    module
+        .sections_mut()
+        .push(Section::Custom(CustomSection::new(
+            name.to_string(),
+            payload.to_vec(),
+        )));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_sections(module: &Module) -> Vec<(String, Vec<u8>)> {
+        module
+            .custom_sections()
+            .map(|custom| (custom.name().to_string(), custom.payload().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn section_added() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let mut conf = HashMap::new();
+        conf.insert("name".to_string(), "hash".to_string());
+        conf.insert("payload_hex".to_string(), "0xc0ffee".to_string());
+        let translator = AddCustomSection::with_config(&conf).unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        assert_eq!(
+            custom_sections(&module),
+            vec![("hash".to_string(), vec![0xc0, 0xff, 0xee])]
+        );
+        // The section is appended after the known sections.
+        let reparsed = Module::from_bytes(&module.to_bytes().unwrap()).unwrap();
+        match reparsed.sections().last() {
+            Some(Section::Custom(custom)) => assert_eq!(custom.name(), "hash"),
+            _ => panic!("Custom section must be last"),
+        }
+    }
+
+    #[test]
+    fn section_replaced() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let first = AddCustomSection::new("hash".to_string(), vec![0x01]);
+        assert!(first.translate_inplace(&mut module).unwrap());
+
+        // Re-running with the same payload is a no-op.
+        assert!(first.translate(&module).unwrap().is_none());
+
+        let second = AddCustomSection::new("hash".to_string(), vec![0x02, 0x03]);
+        assert!(second.translate_inplace(&mut module).unwrap());
+        assert_eq!(
+            custom_sections(&module),
+            vec![("hash".to_string(), vec![0x02, 0x03])]
+        );
+    }
+
+    #[test]
+    fn invalid_payload_hex() {
+        let mut conf = HashMap::new();
+        conf.insert("name".to_string(), "hash".to_string());
+        assert!(AddCustomSection::with_config(&conf).is_err());
+
+        conf.insert("payload_hex".to_string(), "xyz".to_string());
+        assert!(AddCustomSection::with_config(&conf).is_err());
+    }
+}
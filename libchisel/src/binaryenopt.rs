@@ -13,6 +13,8 @@ pub enum BinaryenOptimiser {
     O4,
     Os,
     Oz,
+    /// Runs exactly the named passes, e.g. `dce` or `vacuum`, instead of a leveled preset.
+    Passes(Vec<String>),
 }
 
 impl<'a> ChiselModule<'a> for BinaryenOptimiser {
@@ -35,7 +37,19 @@ impl<'a> ChiselModule<'a> for BinaryenOptimiser {
     }
 
     fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
-        if let Some(preset) = config.get("preset") {
+        if let Some(passes) = config.get("passes") {
+            let passes: Vec<String> = passes
+                .split(',')
+                .map(str::trim)
+                .filter(|pass| !pass.is_empty())
+                .map(String::from)
+                .collect();
+            if passes.is_empty() {
+                Err(ModuleError::NotSupported)
+            } else {
+                Ok(BinaryenOptimiser::Passes(passes))
+            }
+        } else if let Some(preset) = config.get("preset") {
             BinaryenOptimiser::with_preset(preset)
         } else {
             Err(ModuleError::NotSupported)
@@ -78,7 +92,7 @@ impl ModuleTranslator for BinaryenOptimiser {
                 shrink_level: 0,
                 debug_info: has_names_section,
             },
-            BinaryenOptimiser::O2 => binaryen::CodegenConfig {
+            BinaryenOptimiser::O2 | BinaryenOptimiser::Passes(_) => binaryen::CodegenConfig {
                 optimization_level: 2,
                 shrink_level: 0,
                 debug_info: has_names_section,
@@ -106,7 +120,10 @@ impl ModuleTranslator for BinaryenOptimiser {
         };
 
         let serialized = module.clone().to_bytes()?;
-        let output = binaryen_optimiser(&serialized, &config)?;
+        let output = match &self {
+            BinaryenOptimiser::Passes(passes) => binaryen_passes(&serialized, passes, &config)?,
+            _ => binaryen_optimiser(&serialized, &config)?,
+        };
         let output = Module::from_bytes(&output)?;
         Ok(Some(output))
     }
@@ -127,6 +144,24 @@ fn binaryen_optimiser(
     }
 }
 
+fn binaryen_passes(
+    input: &[u8],
+    passes: &[String],
+    config: &binaryen::CodegenConfig,
+) -> Result<Vec<u8>, ModuleError> {
+    match binaryen::Module::read(&input) {
+        Ok(mut module) => match module.run_optimization_passes(passes, &config) {
+            Ok(()) => Ok(module.write()),
+            Err(_) => Err(ModuleError::Custom(
+                "Failed to run binaryen passes".to_string(),
+            )),
+        },
+        Err(_) => Err(ModuleError::Custom(
+            "Failed to deserialise binary with binaryen".to_string(),
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +186,33 @@ mod tests {
         let serialized = result.to_bytes().unwrap();
         assert_eq!(expected, serialized);
     }
+    #[test]
+    fn vacuum_pass() {
+        let input: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x08, 0x01, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut conf = HashMap::new();
+        conf.insert("passes".to_string(), "vacuum".to_string());
+        let translator = BinaryenOptimiser::with_config(&conf).unwrap();
+
+        let module = Module::from_bytes(&input).unwrap();
+        let result = translator.translate(&module).unwrap().unwrap();
+        let serialized = result.to_bytes().unwrap();
+        let roundtripped = Module::from_bytes(&serialized).unwrap();
+        assert_eq!(roundtripped.to_bytes().unwrap(), serialized);
+        assert_eq!(
+            roundtripped.export_section().unwrap().entries()[0].field(),
+            "main"
+        );
+    }
+
+    #[test]
+    fn empty_pass_list() {
+        let mut conf = HashMap::new();
+        conf.insert("passes".to_string(), " , ".to_string());
+        assert!(BinaryenOptimiser::with_config(&conf).is_err());
+    }
 }
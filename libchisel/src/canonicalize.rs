@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Canonicalizes the encoding of a module, such as non-minimal LEB128 encodings of indices and
+/// sizes, by serializing it again with parity-wasm. Holds the bytes the module was loaded from,
+/// and reports the module as changed if serializing it produces different bytes.
+pub struct Canonicalize {
+    original: Vec<u8>,
+}
+
+impl Canonicalize {
+    pub fn new(original: &[u8]) -> Self {
+        Canonicalize {
+            original: original.to_vec(),
+        }
+    }
+
+    /// Serializes the module and checks that the result can be deserialized again.
+    fn serialize(module: &Module) -> Result<Vec<u8>, ModuleError> {
+        let bytes = module.clone().to_bytes()?;
+        if let Err(e) = Module::from_bytes(&bytes) {
+            return Err(ModuleError::Custom(format!(
+                "canonicalized module failed to deserialize: {}",
+                e
+            )));
+        }
+        Ok(bytes)
+    }
+
+    /// Returns the difference in size between the canonical encoding of the module and the
+    /// original bytes. Negative when canonicalization shrinks the module.
+    pub fn size_delta(&self, module: &Module) -> Result<isize, ModuleError> {
+        let bytes = Canonicalize::serialize(module)?;
+        Ok(bytes.len() as isize - self.original.len() as isize)
+    }
+}
+
+impl<'a> ChiselModule<'a> for Canonicalize {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "canonicalize".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for Canonicalize {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        let bytes = Canonicalize::serialize(module)?;
+        Ok(bytes != self.original)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        if Canonicalize::serialize(module)? != self.original {
+            Ok(Some(module.clone()))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::{Func, FunctionSection, Section};
+
+    use super::*;
+
+    #[test]
+    fn non_minimal_leb_canonicalized() {
+        // wast:
+        // (module
+        //   (type (func))
+        // )
+        // with the size of the type section encoded in five bytes instead of one.
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x84, 0x80, 0x80, 0x80, 0x00,
+            0x01, 0x60, 0x00, 0x00,
+        ];
+        let canonical: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = Canonicalize::new(&wasm);
+        assert_eq!(translator.size_delta(&module).unwrap(), -4);
+
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(module.to_bytes().unwrap(), canonical);
+    }
+
+    #[test]
+    fn already_canonical() {
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = Canonicalize::new(&wasm);
+        assert_eq!(translator.size_delta(&module).unwrap(), 0);
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn inconsistent_module() {
+        let mut module = builder::module().build();
+        module
+            .insert_section(Section::Function(FunctionSection::with_entries(vec![
+                Func::new(0),
+            ])))
+            .unwrap();
+
+        let translator = Canonicalize::new(&[]);
+        assert!(translator.translate_inplace(&mut module).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{FunctionType, Module, ValueType};
+
+use super::verifyexports::has_func_export;
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the module exports a function with the expected signature for each host callback
+/// in the list. Other exports are not inspected.
+///
+/// In configuration, callbacks are listed under `callbacks` as comma-separated signatures of the
+/// form `name(params)` or `name(params)->result`, e.g. `main(), on_deposit(i32, i64)->i32`.
+pub struct CheckCallbacks {
+    callbacks: Vec<(String, FunctionType)>,
+}
+
+impl CheckCallbacks {
+    pub fn new(callbacks: Vec<(String, FunctionType)>) -> Self {
+        CheckCallbacks { callbacks }
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckCallbacks {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkcallbacks".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(callbacks) = config.get("callbacks") {
+            Ok(CheckCallbacks::new(parse_callbacks(callbacks)?))
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModuleValidator for CheckCallbacks {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        Ok(self
+            .callbacks
+            .iter()
+            .all(|(name, sig)| has_func_export(module, name, sig)))
+    }
+}
+
+/// Splits a list of callback signatures on the commas which are not inside a parameter list, and
+/// parses each of them.
+fn parse_callbacks(list: &str) -> Result<Vec<(String, FunctionType)>, ModuleError> {
+    let mut callbacks = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                callbacks.push(parse_callback(&list[start..index])?);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    if !list[start..].trim().is_empty() {
+        callbacks.push(parse_callback(&list[start..])?);
+    }
+    Ok(callbacks)
+}
+
+fn parse_callback(callback: &str) -> Result<(String, FunctionType), ModuleError> {
+    let invalid =
+        || ModuleError::Custom(format!("invalid callback signature '{}'", callback.trim()));
+
+    let open = callback.find('(').ok_or_else(invalid)?;
+    let close = callback.rfind(')').ok_or_else(invalid)?;
+    let name = callback[..open].trim();
+    if name.is_empty() || close < open {
+        return Err(invalid());
+    }
+
+    let params = callback[open + 1..close]
+        .split(',')
+        .map(|param| param.trim())
+        .filter(|param| !param.is_empty())
+        .map(|param| parse_value_type(param).ok_or_else(invalid))
+        .collect::<Result<Vec<ValueType>, ModuleError>>()?;
+
+    let rest = callback[close + 1..].trim();
+    let result = if rest.is_empty() {
+        None
+    } else if let Some(result) = rest.strip_prefix("->") {
+        Some(parse_value_type(result.trim()).ok_or_else(invalid)?)
+    } else {
+        return Err(invalid());
+    };
+
+    Ok((name.to_string(), FunctionType::new(params, result)))
+}
+
+fn parse_value_type(value_type: &str) -> Option<ValueType> {
+    match value_type {
+        "i32" => Some(ValueType::I32),
+        "i64" => Some(ValueType::I64),
+        "f32" => Some(ValueType::F32),
+        "f64" => Some(ValueType::F64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // wast:
+    // (module
+    //   (func (export "main"))
+    //   (func (export "on_deposit") (param i32 i64) (result i32)
+    //     i32.const 0)
+    // )
+    const WASM: [u8; 59] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0a, 0x02, 0x60, 0x00, 0x00, 0x60,
+        0x02, 0x7f, 0x7e, 0x01, 0x7f, 0x03, 0x03, 0x02, 0x00, 0x01, 0x07, 0x15, 0x02, 0x04, 0x6d,
+        0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x6f, 0x6e, 0x5f, 0x64, 0x65, 0x70, 0x6f, 0x73, 0x69,
+        0x74, 0x00, 0x01, 0x0a, 0x09, 0x02, 0x02, 0x00, 0x0b, 0x04, 0x00, 0x41, 0x00, 0x0b,
+    ];
+
+    fn check(callbacks: &str) -> bool {
+        let mut conf = HashMap::new();
+        conf.insert("callbacks".to_string(), callbacks.to_string());
+        let module = Module::from_bytes(WASM).unwrap();
+        let checker = CheckCallbacks::with_config(&conf).unwrap();
+        checker.validate(&module).unwrap()
+    }
+
+    #[test]
+    fn custom_callback_good() {
+        assert!(check("main(), on_deposit(i32, i64)->i32"));
+    }
+
+    #[test]
+    fn custom_callback_wrong_signature() {
+        assert!(!check("main(), on_deposit(i32, i32)->i32"));
+        assert!(!check("on_deposit(i32, i64)"));
+    }
+
+    #[test]
+    fn custom_callback_missing() {
+        assert!(!check("main(), on_withdraw(i32)"));
+    }
+
+    #[test]
+    fn invalid_signature() {
+        assert!(parse_callbacks("main").is_err());
+        assert!(parse_callbacks("main(u32)").is_err());
+        assert!(parse_callbacks("main() i32").is_err());
+    }
+}
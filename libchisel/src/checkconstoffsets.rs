@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{InitExpr, Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that every data and element segment is active, with an offset given by a single
+/// `i32.const`. Passive segments and offsets read from globals are rejected, as not every host
+/// supports them.
+pub struct CheckConstOffsets {}
+
+impl<'a> ChiselModule<'a> for CheckConstOffsets {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkconstoffsets".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckConstOffsets {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+/// Checks that a segment offset is present (i.e. the segment is active) and is a constant.
+fn is_const_offset(offset: &Option<InitExpr>) -> bool {
+    if let Some(expr) = offset {
+        if let [Instruction::I32Const(_), Instruction::End] = expr.code() {
+            return true;
+        }
+    }
+    false
+}
+
+impl ModuleValidator for CheckConstOffsets {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if let Some(section) = module.data_section() {
+            if !section
+                .entries()
+                .iter()
+                .all(|segment| is_const_offset(segment.offset()))
+            {
+                return Ok(false);
+            }
+        }
+        if let Some(section) = module.elements_section() {
+            if !section
+                .entries()
+                .iter()
+                .all(|segment| is_const_offset(segment.offset()))
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{DataSection, DataSegment, Section};
+
+    use super::*;
+
+    #[test]
+    fn const_offsets_good() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (table 1 funcref)
+        //   (func)
+        //   (elem (i32.const 0) 0)
+        //   (data (i32.const 8) "hi")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x04, 0x04, 0x01, 0x70, 0x00, 0x01, 0x05, 0x03, 0x01, 0x00,
+            0x01, 0x09, 0x07, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x00, 0x0a, 0x04, 0x01, 0x02,
+            0x00, 0x0b, 0x0b, 0x08, 0x01, 0x00, 0x41, 0x08, 0x0b, 0x02, 0x68, 0x69,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckConstOffsets::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn data_offset_from_global() {
+        // wast:
+        // (module
+        //   (import "env" "base" (global i32))
+        //   (memory 1)
+        //   (data (global.get 0) "hi")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0d, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x04, 0x62, 0x61, 0x73, 0x65, 0x03, 0x7f, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01,
+            0x0b, 0x08, 0x01, 0x00, 0x23, 0x00, 0x0b, 0x02, 0x68, 0x69,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckConstOffsets::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn element_offset_from_global() {
+        // wast:
+        // (module
+        //   (import "env" "base" (global i32))
+        //   (table 1 funcref)
+        //   (func)
+        //   (elem (global.get 0) 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0d, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x04, 0x62, 0x61, 0x73, 0x65, 0x03, 0x7f,
+            0x00, 0x03, 0x02, 0x01, 0x00, 0x04, 0x04, 0x01, 0x70, 0x00, 0x01, 0x09, 0x07, 0x01,
+            0x00, 0x23, 0x00, 0x0b, 0x01, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckConstOffsets::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn passive_data_segment() {
+        // Passive segments have no offset expression. They are built directly, as parsing them
+        // requires the bulk memory feature of parity-wasm.
+        let mut module = Module::default();
+        module
+            .insert_section(Section::Data(DataSection::with_entries(vec![
+                DataSegment::new(0, None, b"hi".to_vec()),
+            ])))
+            .unwrap();
+
+        let checker = CheckConstOffsets::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+}
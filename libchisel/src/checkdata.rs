@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// The size of a Wasm memory page in bytes.
+const PAGE_SIZE: u64 = 65536;
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that every active data segment with a constant offset fits into the initial memory.
+/// Segments are not checked against an imported memory unless its size is given as
+/// `imported_pages`.
+pub struct CheckDataBounds {
+    imported_pages: Option<u32>,
+}
+
+impl CheckDataBounds {
+    pub fn new(imported_pages: Option<u32>) -> Self {
+        CheckDataBounds { imported_pages }
+    }
+
+    /// Returns the initial memory size in pages, or None if the bounds cannot be checked.
+    fn initial_pages(&self, module: &Module) -> Option<u32> {
+        if let Some(section) = module.import_section() {
+            if section
+                .entries()
+                .iter()
+                .any(|entry| matches!(entry.external(), External::Memory(_)))
+            {
+                return self.imported_pages;
+            }
+        }
+        Some(
+            module
+                .memory_section()
+                .and_then(|section| section.entries().first())
+                .map_or(0, |memory| memory.limits().initial()),
+        )
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckDataBounds {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkdata".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckDataBounds::new(None))
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        let imported_pages = match config.get("imported_pages") {
+            Some(pages) => Some(pages.trim().parse::<u32>()?),
+            None => None,
+        };
+        Ok(CheckDataBounds::new(imported_pages))
+    }
+}
+
+impl ModuleValidator for CheckDataBounds {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let section = match module.data_section() {
+            Some(section) => section,
+            None => return Ok(true),
+        };
+        let memory_bytes = match self.initial_pages(module) {
+            Some(pages) => u64::from(pages) * PAGE_SIZE,
+            None => return Ok(true),
+        };
+
+        for segment in section.entries() {
+            let offset = segment.offset().as_ref().map(|offset| offset.code());
+            // Passive segments and offsets read from globals are not checked.
+            if let Some([Instruction::I32Const(offset), Instruction::End]) = offset {
+                let end = u64::from(*offset as u32) + segment.value().len() as u64;
+                if end > memory_bytes {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_in_bounds() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (data (i32.const 65532) "abcd")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x0b,
+            0x0c, 0x01, 0x00, 0x41, 0xfc, 0xff, 0x03, 0x0b, 0x04, 0x61, 0x62, 0x63, 0x64,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckDataBounds::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn segment_out_of_bounds() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (data (i32.const 65533) "abcd")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x0b,
+            0x0c, 0x01, 0x00, 0x41, 0xfd, 0xff, 0x03, 0x0b, 0x04, 0x61, 0x62, 0x63, 0x64,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckDataBounds::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn imported_memory() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 1))
+        //   (data (i32.const 65533) "abcd")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x01, 0x0b, 0x0c, 0x01,
+            0x00, 0x41, 0xfd, 0xff, 0x03, 0x0b, 0x04, 0x61, 0x62, 0x63, 0x64,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        // The size of an imported memory is unknown without configuration.
+        let checker = CheckDataBounds::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+
+        let mut conf = HashMap::new();
+        conf.insert("imported_pages".to_string(), "1".to_string());
+        let checker = CheckDataBounds::with_config(&conf).unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+
+        conf.insert("imported_pages".to_string(), "2".to_string());
+        let checker = CheckDataBounds::with_config(&conf).unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+}
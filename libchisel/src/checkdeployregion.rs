@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Instruction, Module};
+
+use super::deployer::create_custom_deployer;
+use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleValidator};
+
+/// Enum on which ModuleValidator is implemented.
+/// Checks that no active data segment of the module overlaps the region of memory starting at
+/// offset 0 which the deployer of the given format copies code into. For `Memory`, this is the
+/// serialized module itself; for `CustomSection`, the whole deployer including the payload.
+///
+/// Segments whose offset is not a constant cannot be shown to stay clear of the region, and are
+/// reported as colliding.
+pub enum CheckDeployRegion {
+    Memory,
+    CustomSection,
+}
+
+impl<'a> ChiselModule<'a> for CheckDeployRegion {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkdeployregion".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            CheckDeployRegion::with_preset(preset)
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModulePreset for CheckDeployRegion {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            "memory" => Ok(CheckDeployRegion::Memory),
+            "customsection" => Ok(CheckDeployRegion::CustomSection),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+impl CheckDeployRegion {
+    /// Returns the length of the reserved region for the given module.
+    fn reserved_len(&self, module: &Module) -> Result<usize, ModuleError> {
+        let payload = module.clone().to_bytes()?;
+        match self {
+            CheckDeployRegion::Memory => Ok(payload.len()),
+            CheckDeployRegion::CustomSection => {
+                Ok(create_custom_deployer(&payload)?.to_bytes()?.len())
+            }
+        }
+    }
+}
+
+impl ModuleValidator for CheckDeployRegion {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let segments = if let Some(section) = module.data_section() {
+            section.entries()
+        } else {
+            return Ok(true);
+        };
+
+        let reserved_len = self.reserved_len(module)?;
+        for segment in segments {
+            // Passive segments are not copied to memory on instantiation.
+            let offset = if let Some(offset) = segment.offset() {
+                offset
+            } else {
+                continue;
+            };
+            if segment.value().is_empty() {
+                continue;
+            }
+
+            if let [Instruction::I32Const(start), Instruction::End] = offset.code() {
+                if (*start as u32 as usize) < reserved_len {
+                    return Ok(false);
+                }
+            } else {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colliding_segment() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (data (i32.const 0) "abc")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x0b,
+            0x09, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x03, 0x61, 0x62, 0x63,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        for preset in &["memory", "customsection"] {
+            let checker = CheckDeployRegion::with_preset(preset).unwrap();
+            let result = checker.validate(&module).unwrap();
+            assert_eq!(false, result);
+        }
+    }
+
+    #[test]
+    fn segment_after_region() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (data (i32.const 1024) "abc")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x0b,
+            0x0a, 0x01, 0x00, 0x41, 0x80, 0x08, 0x0b, 0x03, 0x61, 0x62, 0x63,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        for preset in &["memory", "customsection"] {
+            let checker = CheckDeployRegion::with_preset(preset).unwrap();
+            let result = checker.validate(&module).unwrap();
+            assert_eq!(true, result);
+        }
+    }
+
+    #[test]
+    fn segment_at_global_offset() {
+        // wast:
+        // (module
+        //   (import "env" "offset" (global i32))
+        //   (memory 1)
+        //   (data (global.get 0) "abc")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x03, 0x7f, 0x00, 0x05, 0x03, 0x01,
+            0x00, 0x01, 0x0b, 0x09, 0x01, 0x00, 0x23, 0x00, 0x0b, 0x03, 0x61, 0x62, 0x63,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckDeployRegion::with_preset("memory").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet};
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that no two exports share a name, which some toolchains emit and engines reject.
+pub struct CheckDuplicateExports {}
+
+impl<'a> ChiselModule<'a> for CheckDuplicateExports {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkduplicateexports".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckDuplicateExports {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for CheckDuplicateExports {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if let Some(section) = module.export_section() {
+            let mut seen = HashSet::new();
+            Ok(section
+                .entries()
+                .iter()
+                .all(|entry| seen.insert(entry.field())))
+        } else {
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_exported_twice() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        //   (export "main" (func 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x0f, 0x02, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckDuplicateExports::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn unique_exports_ewasm() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckDuplicateExports::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+}
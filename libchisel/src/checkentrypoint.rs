@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Internal, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Enum representing which entry point a module is required to have.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EntryPoint {
+    /// A start section.
+    Start,
+    /// A function exported as `main`.
+    Main,
+    /// Either a start section or a `main` export.
+    Either,
+}
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the module has the required entry point. When `exclusive` is set, a module with
+/// both a start section and a `main` export is rejected as ambiguous.
+pub struct CheckEntryPoint {
+    required: EntryPoint,
+    exclusive: bool,
+}
+
+impl CheckEntryPoint {
+    pub fn new(required: EntryPoint, exclusive: bool) -> Self {
+        CheckEntryPoint {
+            required,
+            exclusive,
+        }
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckEntryPoint {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkentrypoint".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckEntryPoint::new(EntryPoint::Either, true))
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        let required = match config.get("require").map(|r| r.as_str()) {
+            None | Some("either") => EntryPoint::Either,
+            Some("start") => EntryPoint::Start,
+            Some("main") => EntryPoint::Main,
+            Some(_) => return Err(ModuleError::NotSupported),
+        };
+        let exclusive = match config.get("exclusive").map(|e| e.as_str()) {
+            None | Some("true") => true,
+            Some("false") => false,
+            Some(_) => return Err(ModuleError::NotSupported),
+        };
+        Ok(CheckEntryPoint::new(required, exclusive))
+    }
+}
+
+fn has_main_export(module: &Module) -> bool {
+    if let Some(section) = module.export_section() {
+        for entry in section.entries() {
+            if let Internal::Function(_) = entry.internal() {
+                if entry.field() == "main" {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+impl ModuleValidator for CheckEntryPoint {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let has_start = module.start_section().is_some();
+        let has_main = has_main_export(module);
+
+        if self.exclusive && has_start && has_main {
+            return Ok(false);
+        }
+
+        Ok(match self.required {
+            EntryPoint::Start => has_start,
+            EntryPoint::Main => has_main,
+            EntryPoint::Either => has_start || has_main,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_entry_point() {
+        // wast:
+        // (module
+        //   (func (export "other"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x09, 0x01, 0x05, 0x6f, 0x74, 0x68, 0x65, 0x72, 0x00,
+            0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        for required in &[EntryPoint::Start, EntryPoint::Main, EntryPoint::Either] {
+            let checker = CheckEntryPoint::new(*required, true);
+            assert_eq!(false, checker.validate(&module).unwrap());
+        }
+    }
+
+    #[test]
+    fn both_entry_points() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        //   (start 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x08, 0x01, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckEntryPoint::with_defaults().unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+
+        let mut conf = HashMap::new();
+        conf.insert("exclusive".to_string(), "false".to_string());
+        let checker = CheckEntryPoint::with_config(&conf).unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn main_export_only() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckEntryPoint::with_defaults().unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        let mut conf = HashMap::new();
+        conf.insert("require".to_string(), "main".to_string());
+        let checker = CheckEntryPoint::with_config(&conf).unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        conf.insert("require".to_string(), "start".to_string());
+        let checker = CheckEntryPoint::with_config(&conf).unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn start_section_only() {
+        // wast:
+        // (module
+        //   (func)
+        //   (start 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x08, 0x01, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckEntryPoint::new(EntryPoint::Start, true);
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        let checker = CheckEntryPoint::new(EntryPoint::Main, true);
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn invalid_config() {
+        let mut conf = HashMap::new();
+        conf.insert("require".to_string(), "both".to_string());
+        assert!(CheckEntryPoint::with_config(&conf).is_err());
+    }
+}
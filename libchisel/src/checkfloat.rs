@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use parity_wasm::elements::{Instruction, Module};
+use parity_wasm::elements::{Instruction, Module, SimdInstruction};
 
 use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
 
@@ -31,16 +31,35 @@ impl<'a> ChiselModule<'a> for CheckFloat {
     }
 }
 
-impl ModuleValidator for CheckFloat {
-    // NOTE: this will not check for SIMD instructions.
-    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
-        let code_section = module.code_section();
-        if code_section.is_none() {
-            return Err(ModuleError::NotFound);
-        }
-        for function in code_section.unwrap().bodies() {
-            for instruction in function.code().elements() {
-                match instruction {
+impl CheckFloat {
+    /// Finds the first floating point instruction in the module. Returns the index of the
+    /// function body, the offset of the instruction within the body, and the instruction itself.
+    pub fn find_float(&self, module: &Module) -> Option<(usize, usize, Instruction)> {
+        self.float_locations(module, true).pop()
+    }
+
+    /// Lists every floating point instruction in the module, in the same form as `find_float`,
+    /// so that all of them can be fixed in one pass.
+    pub fn report(&self, module: &Module) -> Vec<(usize, usize, Instruction)> {
+        self.float_locations(module, false)
+    }
+
+    /// Collects floating point instructions, stopping after the first one if `first_only` is set.
+    /// SIMD instructions operating on `f32x4` or `f64x2` lanes count as floating point.
+    fn float_locations(
+        &self,
+        module: &Module,
+        first_only: bool,
+    ) -> Vec<(usize, usize, Instruction)> {
+        let mut locations = Vec::new();
+        let code_section = if let Some(code_section) = module.code_section() {
+            code_section
+        } else {
+            return locations;
+        };
+        for (body_index, function) in code_section.bodies().iter().enumerate() {
+            for (offset, instruction) in function.code().elements().iter().enumerate() {
+                let is_float = match instruction {
                     Instruction::F32Eq
                     | Instruction::F32Ne
                     | Instruction::F32Lt
@@ -108,15 +127,79 @@ impl ModuleValidator for CheckFloat {
                     | Instruction::F32Store(_, _)
                     | Instruction::F64Const(_)
                     | Instruction::F64Load(_, _)
-                    | Instruction::F64Store(_, _) => {
-                        return Ok(false);
+                    | Instruction::F64Store(_, _) => true,
+                    Instruction::Simd(simd) => is_float_simd(simd),
+                    _ => false,
+                };
+                if is_float {
+                    locations.push((body_index, offset, instruction.clone()));
+                    if first_only {
+                        return locations;
                     }
-                    _ => {}
                 }
             }
         }
 
-        Ok(true)
+        locations
+    }
+}
+
+fn is_float_simd(instruction: &SimdInstruction) -> bool {
+    match instruction {
+        SimdInstruction::F32x4Splat
+        | SimdInstruction::F64x2Splat
+        | SimdInstruction::F32x4ExtractLane(_)
+        | SimdInstruction::F64x2ExtractLane(_)
+        | SimdInstruction::F32x4ReplaceLane(_)
+        | SimdInstruction::F64x2ReplaceLane(_)
+        | SimdInstruction::F32x4Eq
+        | SimdInstruction::F64x2Eq
+        | SimdInstruction::F32x4Ne
+        | SimdInstruction::F64x2Ne
+        | SimdInstruction::F32x4Lt
+        | SimdInstruction::F64x2Lt
+        | SimdInstruction::F32x4Le
+        | SimdInstruction::F64x2Le
+        | SimdInstruction::F32x4Gt
+        | SimdInstruction::F64x2Gt
+        | SimdInstruction::F32x4Ge
+        | SimdInstruction::F64x2Ge
+        | SimdInstruction::F32x4Neg
+        | SimdInstruction::F64x2Neg
+        | SimdInstruction::F32x4Abs
+        | SimdInstruction::F64x2Abs
+        | SimdInstruction::F32x4Min
+        | SimdInstruction::F64x2Min
+        | SimdInstruction::F32x4Max
+        | SimdInstruction::F64x2Max
+        | SimdInstruction::F32x4Add
+        | SimdInstruction::F64x2Add
+        | SimdInstruction::F32x4Sub
+        | SimdInstruction::F64x2Sub
+        | SimdInstruction::F32x4Div
+        | SimdInstruction::F64x2Div
+        | SimdInstruction::F32x4Mul
+        | SimdInstruction::F64x2Mul
+        | SimdInstruction::F32x4Sqrt
+        | SimdInstruction::F64x2Sqrt
+        | SimdInstruction::F32x4ConvertSI32x4
+        | SimdInstruction::F32x4ConvertUI32x4
+        | SimdInstruction::F64x2ConvertSI64x2
+        | SimdInstruction::F64x2ConvertUI64x2
+        | SimdInstruction::I32x4TruncSF32x4Sat
+        | SimdInstruction::I32x4TruncUF32x4Sat
+        | SimdInstruction::I64x2TruncSF64x2Sat
+        | SimdInstruction::I64x2TruncUF64x2Sat => true,
+        _ => false,
+    }
+}
+
+impl ModuleValidator for CheckFloat {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if module.code_section().is_none() {
+            return Err(ModuleError::NotFound);
+        }
+        Ok(self.find_float(module).is_none())
     }
 }
 
@@ -186,6 +269,136 @@ mod tests {
         assert_eq!(false, result);
     }
 
+    #[test]
+    fn add_f32x4_fp() {
+        //  (module
+        //    (func $add (param $lhs v128) (param $rhs v128) (result v128)
+        //      get_local $lhs
+        //      get_local $rhs
+        //      f32x4.add)
+        //    (export "add" (func $add))
+        //  )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x07, 0x01, 0x60, 0x02, 0x7b,
+            0x7b, 0x01, 0x7b, 0x03, 0x02, 0x01, 0x00, 0x07, 0x07, 0x01, 0x03, 0x61, 0x64, 0x64,
+            0x00, 0x00, 0x0a, 0x0b, 0x01, 0x09, 0x00, 0x20, 0x00, 0x20, 0x01, 0xfd, 0x9a, 0x01,
+            0x0b,
+        ];
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckFloat::with_defaults().unwrap();
+        assert_eq!(
+            checker.find_float(&module),
+            Some((0, 2, Instruction::Simd(SimdInstruction::F32x4Add)))
+        );
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn find_float_second_function() {
+        //  (module
+        //    (func $add_i32 (param $lhs i32) (param $rhs i32) (result i32)
+        //      get_local $lhs
+        //      get_local $rhs
+        //      i32.add)
+        //    (func $add (param $lhs f32) (param $rhs f32) (result f32)
+        //      get_local $lhs
+        //      get_local $rhs
+        //      f32.add)
+        //    (export "add" (func $add))
+        //  )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0d, 0x02, 0x60, 0x02, 0x7f,
+            0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7d, 0x7d, 0x01, 0x7d, 0x03, 0x03, 0x02, 0x00, 0x01,
+            0x07, 0x07, 0x01, 0x03, 0x61, 0x64, 0x64, 0x00, 0x01, 0x0a, 0x11, 0x02, 0x07, 0x00,
+            0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x92, 0x0b,
+        ];
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckFloat::with_defaults().unwrap();
+        assert_eq!(
+            checker.find_float(&module),
+            Some((1, 2, Instruction::F32Add))
+        );
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn find_float_none() {
+        //  (module
+        //    (func $add (param $lhs i32) (param $rhs i32) (result i32)
+        //      get_local $lhs
+        //      get_local $rhs
+        //      i32.add)
+        //    (export "add" (func $add))
+        //  )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x07, 0x01, 0x60, 0x02, 0x7f,
+            0x7f, 0x01, 0x7f, 0x03, 0x02, 0x01, 0x00, 0x07, 0x07, 0x01, 0x03, 0x61, 0x64, 0x64,
+            0x00, 0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b,
+        ];
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckFloat::with_defaults().unwrap();
+        assert_eq!(checker.find_float(&module), None);
+    }
+
+    #[test]
+    fn report_all_floats() {
+        //  (module
+        //    (func $add (param $lhs f32) (param $rhs f32) (result f32)
+        //      get_local $lhs
+        //      get_local $rhs
+        //      f32.add)
+        //    (func $id (param $x i32) (result i32)
+        //      get_local $x)
+        //    (func $mix (param $lhs f64) (param $rhs f64) (result i32)
+        //      get_local $lhs
+        //      get_local $rhs
+        //      f64.mul
+        //      i32.trunc_f64_s)
+        //  )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x12, 0x03, 0x60, 0x02, 0x7d,
+            0x7d, 0x01, 0x7d, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7c, 0x7c, 0x01, 0x7f,
+            0x03, 0x04, 0x03, 0x00, 0x01, 0x02, 0x0a, 0x17, 0x03, 0x07, 0x00, 0x20, 0x00, 0x20,
+            0x01, 0x92, 0x0b, 0x04, 0x00, 0x20, 0x00, 0x0b, 0x08, 0x00, 0x20, 0x00, 0x20, 0x01,
+            0xa2, 0xaa, 0x0b,
+        ];
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckFloat::with_defaults().unwrap();
+        assert_eq!(
+            checker.report(&module),
+            vec![
+                (0, 2, Instruction::F32Add),
+                (2, 2, Instruction::F64Mul),
+                (2, 3, Instruction::I32TruncSF64),
+            ]
+        );
+        assert_eq!(
+            checker.find_float(&module),
+            Some((0, 2, Instruction::F32Add))
+        );
+    }
+
+    #[test]
+    fn report_no_floats() {
+        //  (module
+        //    (func $add (param $lhs i32) (param $rhs i32) (result i32)
+        //      get_local $lhs
+        //      get_local $rhs
+        //      i32.add)
+        //    (export "add" (func $add))
+        //  )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x07, 0x01, 0x60, 0x02, 0x7f,
+            0x7f, 0x01, 0x7f, 0x03, 0x02, 0x01, 0x00, 0x07, 0x07, 0x01, 0x03, 0x61, 0x64, 0x64,
+            0x00, 0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b,
+        ];
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckFloat::with_defaults().unwrap();
+        assert!(checker.report(&module).is_empty());
+    }
+
     #[test]
     fn no_code_section() {
         let module = builder::module().build();
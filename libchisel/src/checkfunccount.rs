@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{ImportCountType, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the number of functions, imported and defined, does not exceed `max`.
+pub struct CheckFunctionCount {
+    max: u32,
+}
+
+impl CheckFunctionCount {
+    pub fn new(max: u32) -> Self {
+        CheckFunctionCount { max }
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckFunctionCount {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkfunccount".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(max) = config.get("max") {
+            Ok(CheckFunctionCount::new(max.trim().parse::<u32>()?))
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModuleValidator for CheckFunctionCount {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let imported = module.import_count(ImportCountType::Function);
+        let defined = module
+            .function_section()
+            .map_or(0, |section| section.entries().len());
+        Ok(imported + defined <= self.max as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_are_counted() {
+        // wast:
+        // (module
+        //   (import "env" "a" (func))
+        //   (import "env" "b" (func))
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x11, 0x02, 0x03, 0x65, 0x6e, 0x76, 0x01, 0x61, 0x00, 0x00, 0x03, 0x65, 0x6e,
+            0x76, 0x01, 0x62, 0x00, 0x00, 0x03, 0x02, 0x01, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00,
+            0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckFunctionCount::new(2);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+
+        let checker = CheckFunctionCount::new(3);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn max_from_config() {
+        let mut conf = HashMap::new();
+        conf.insert("max".to_string(), "0".to_string());
+        let checker = CheckFunctionCount::with_config(&conf).unwrap();
+        let result = checker.validate(&Module::default()).unwrap();
+        assert_eq!(true, result);
+
+        conf.insert("max".to_string(), "many".to_string());
+        assert!(CheckFunctionCount::with_config(&conf).is_err());
+    }
+}
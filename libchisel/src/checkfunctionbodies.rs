@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that every function declared in the function section has a body in the code section.
+pub struct CheckFunctionBodies {}
+
+impl<'a> ChiselModule<'a> for CheckFunctionBodies {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkfunctionbodies".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckFunctionBodies {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for CheckFunctionBodies {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let declared = module
+            .function_section()
+            .map_or(0, |section| section.entries().len());
+        let bodies = module
+            .code_section()
+            .map_or(0, |section| section.bodies().len());
+        Ok(declared == bodies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::{Func, FunctionSection, Section};
+
+    use super::*;
+
+    #[test]
+    fn function_without_body() {
+        let mut module = builder::module().build();
+        module
+            .insert_section(Section::Function(FunctionSection::with_entries(vec![
+                Func::new(0),
+            ])))
+            .unwrap();
+
+        let checker = CheckFunctionBodies::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn bodies_match_ewasm() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckFunctionBodies::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+
+        // A module without functions passes too.
+        let result = checker.validate(&Module::default()).unwrap();
+        assert_eq!(true, result);
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the module has a code section with at least one function body. A module without
+/// code, even if it has imports and exports, is almost never a deployable contract.
+pub struct CheckHasCode {}
+
+impl CheckHasCode {
+    /// Describes why the module fails validation, or returns None if it passes.
+    pub fn diagnostic(&self, module: &Module) -> Option<&'static str> {
+        match module.code_section() {
+            None => Some("module has no code section"),
+            Some(code) if code.bodies().is_empty() => Some("code section has no function bodies"),
+            Some(_) => None,
+        }
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckHasCode {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkhascode".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckHasCode {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for CheckHasCode {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        Ok(self.diagnostic(module).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::{CodeSection, Section};
+
+    use super::*;
+
+    #[test]
+    fn has_code() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckHasCode::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn no_code_section() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (export "foo" (func 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x07,
+            0x07, 0x01, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckHasCode::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+        assert_eq!(
+            checker.diagnostic(&module),
+            Some("module has no code section")
+        );
+    }
+
+    #[test]
+    fn empty_code_section() {
+        let module = builder::module()
+            .with_section(Section::Code(CodeSection::default()))
+            .build();
+
+        let checker = CheckHasCode::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+        assert_eq!(
+            checker.diagnostic(&module),
+            Some("code section has no function bodies")
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, ImportEntry, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the imports of each module namespace are contiguous, and optionally that the
+/// imports of each kind (functions, tables, memories, globals) are contiguous as well.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CheckImportOrder {
+    group_kinds: bool,
+}
+
+impl CheckImportOrder {
+    /// Also requires the imports of each kind to be grouped.
+    pub fn with_grouped_kinds(self) -> Self {
+        CheckImportOrder { group_kinds: true }
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckImportOrder {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkimportorder".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckImportOrder { group_kinds: false })
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            return CheckImportOrder::with_preset(preset);
+        }
+
+        let group_kinds = match config.get("group_kinds").map(|value| value.trim()) {
+            None | Some("false") => false,
+            Some("true") => true,
+            Some(_) => return Err(ModuleError::NotSupported),
+        };
+        Ok(CheckImportOrder { group_kinds })
+    }
+}
+
+impl ModulePreset for CheckImportOrder {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            "ewasm" => CheckImportOrder::with_defaults(),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+fn external_kind(entry: &ImportEntry) -> u8 {
+    match entry.external() {
+        External::Function(_) => 0,
+        External::Table(_) => 1,
+        External::Memory(_) => 2,
+        External::Global(_) => 3,
+    }
+}
+
+/// Returns true if equal keys only appear in a single run.
+fn is_grouped<T: PartialEq>(keys: impl Iterator<Item = T>) -> bool {
+    let mut seen: Vec<T> = Vec::new();
+    for key in keys {
+        if seen.last() == Some(&key) {
+            continue;
+        }
+        if seen.contains(&key) {
+            return false;
+        }
+        seen.push(key);
+    }
+    true
+}
+
+impl ModuleValidator for CheckImportOrder {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let entries = match module.import_section() {
+            Some(section) => section.entries(),
+            None => return Ok(true),
+        };
+
+        if !is_grouped(entries.iter().map(|entry| entry.module())) {
+            return Ok(false);
+        }
+
+        Ok(!self.group_kinds || is_grouped(entries.iter().map(external_kind)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::{GlobalType, MemoryType, ValueType};
+
+    use super::*;
+
+    fn import(namespace: &str, field: &str, external: External) -> ImportEntry {
+        ImportEntry::new(namespace.to_string(), field.to_string(), external)
+    }
+
+    #[test]
+    fn grouped_namespaces() {
+        let module = builder::module()
+            .with_import(import("ethereum", "useGas", External::Function(0)))
+            .with_import(import("ethereum", "finish", External::Function(0)))
+            .with_import(import("env", "print", External::Function(0)))
+            .build();
+
+        let checker = CheckImportOrder::with_preset("ewasm").unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn interleaved_namespaces() {
+        let module = builder::module()
+            .with_import(import("ethereum", "useGas", External::Function(0)))
+            .with_import(import("env", "print", External::Function(0)))
+            .with_import(import("ethereum", "finish", External::Function(0)))
+            .build();
+
+        let checker = CheckImportOrder::with_preset("ewasm").unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn interleaved_kinds() {
+        let module = builder::module()
+            .with_import(import("env", "print", External::Function(0)))
+            .with_import(import(
+                "env",
+                "counter",
+                External::Global(GlobalType::new(ValueType::I32, false)),
+            ))
+            .with_import(import("env", "abort", External::Function(0)))
+            .with_import(import(
+                "env",
+                "memory",
+                External::Memory(MemoryType::new(1, None)),
+            ))
+            .build();
+
+        let checker = CheckImportOrder::with_defaults().unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        let checker = checker.with_grouped_kinds();
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn with_config_group_kinds() {
+        let mut conf = HashMap::new();
+        conf.insert("group_kinds".to_string(), "true".to_string());
+        let checker = CheckImportOrder::with_config(&conf).unwrap();
+        assert_eq!(checker, CheckImportOrder::default().with_grouped_kinds());
+
+        conf.insert("group_kinds".to_string(), "yes".to_string());
+        assert!(CheckImportOrder::with_config(&conf).is_err());
+    }
+}
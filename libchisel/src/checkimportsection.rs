@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Module, Section};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the import section, if present, precedes the function and code sections, and that
+/// the function and code sections agree in length. Either mistake shifts the defined functions
+/// in the function index space, so references meant for a defined function resolve to an import
+/// or to the wrong body.
+pub struct CheckImportSection {}
+
+impl<'a> ChiselModule<'a> for CheckImportSection {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkimportsection".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckImportSection {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for CheckImportSection {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let mut seen_functions = false;
+        for section in module.sections() {
+            match section {
+                Section::Function(_) | Section::Code(_) => seen_functions = true,
+                Section::Import(_) if seen_functions => return Ok(false),
+                _ => (),
+            }
+        }
+
+        let function_count = module
+            .function_section()
+            .map_or(0, |section| section.entries().len());
+        let body_count = module
+            .code_section()
+            .map_or(0, |section| section.bodies().len());
+
+        Ok(function_count == body_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_before_functions_good() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (func (export "main")
+        //     call 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x03,
+            0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a,
+            0x06, 0x01, 0x04, 0x00, 0x10, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckImportSection::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn import_after_code_malformed() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (func (export "main")
+        //     call 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x03,
+            0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a,
+            0x06, 0x01, 0x04, 0x00, 0x10, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        // Move the import section to the end of the module.
+        let sections = module.sections_mut();
+        let import = sections.remove(1);
+        sections.push(import);
+
+        let checker = CheckImportSection::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn missing_code_section() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (func (export "main")
+        //     call 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x03,
+            0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a,
+            0x06, 0x01, 0x04, 0x00, 0x10, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        module.sections_mut().pop();
+
+        let checker = CheckImportSection::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+}
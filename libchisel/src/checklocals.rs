@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Instruction, Module, Type};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that every `local.get`, `local.set` and `local.tee` refers to a parameter or declared
+/// local of the enclosing function.
+pub struct CheckLocals {}
+
+impl<'a> ChiselModule<'a> for CheckLocals {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checklocals".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckLocals {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl CheckLocals {
+    /// Finds the first access to an undeclared local. Returns the index of the function body, the
+    /// offset of the instruction within the body, and the instruction itself.
+    pub fn find_violation(
+        &self,
+        module: &Module,
+    ) -> Result<Option<(usize, usize, Instruction)>, ModuleError> {
+        let code = match module.code_section() {
+            Some(code) => code,
+            None => return Ok(None),
+        };
+        let functions = module
+            .function_section()
+            .ok_or(ModuleError::NotFound)?
+            .entries();
+        let types = module.type_section().ok_or(ModuleError::NotFound)?.types();
+
+        for (body_index, body) in code.bodies().iter().enumerate() {
+            let type_ref = functions
+                .get(body_index)
+                .ok_or(ModuleError::NotFound)?
+                .type_ref();
+            let params = match types.get(type_ref as usize) {
+                Some(Type::Function(sig)) => sig.params().len() as u64,
+                None => return Err(ModuleError::NotFound),
+            };
+            // Counted in u64, as a malformed body may declare more than u32::MAX locals.
+            let locals_len = body
+                .locals()
+                .iter()
+                .fold(params, |acc, local| acc + u64::from(local.count()));
+
+            for (offset, instruction) in body.code().elements().iter().enumerate() {
+                let index = match instruction {
+                    Instruction::GetLocal(index)
+                    | Instruction::SetLocal(index)
+                    | Instruction::TeeLocal(index) => *index,
+                    _ => continue,
+                };
+                if u64::from(index) >= locals_len {
+                    return Ok(Some((body_index, offset, instruction.clone())));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl ModuleValidator for CheckLocals {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        Ok(self.find_violation(module)?.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locals_in_range() {
+        // wast:
+        // (module
+        //   (func (export "main") (param i32) (result i32)
+        //     (local i64 i32)
+        //     local.get 0
+        //     local.set 2
+        //     local.get 2)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x06, 0x01, 0x60, 0x01, 0x7f,
+            0x01, 0x7f, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+            0x00, 0x00, 0x0a, 0x0e, 0x01, 0x0c, 0x02, 0x01, 0x7e, 0x01, 0x7f, 0x20, 0x00, 0x21,
+            0x02, 0x20, 0x02, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckLocals::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn local_out_of_range() {
+        // wast:
+        // (module
+        //   (func (export "main") (param i32) (result i32)
+        //     (local i64 i32)
+        //     local.get 0
+        //     local.set 2
+        //     local.get 2)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x06, 0x01, 0x60, 0x01, 0x7f,
+            0x01, 0x7f, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+            0x00, 0x00, 0x0a, 0x0e, 0x01, 0x0c, 0x02, 0x01, 0x7e, 0x01, 0x7f, 0x20, 0x00, 0x21,
+            0x02, 0x20, 0x02, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        // Point the last access past the declared locals.
+        module.code_section_mut().unwrap().bodies_mut()[0]
+            .code_mut()
+            .elements_mut()[2] = Instruction::GetLocal(3);
+
+        let checker = CheckLocals::with_defaults().unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+        assert_eq!(
+            checker.find_violation(&module).unwrap(),
+            Some((0, 2, Instruction::GetLocal(3)))
+        );
+    }
+
+    #[test]
+    fn no_code_section() {
+        // wast:
+        // (module
+        //   (memory 1)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckLocals::with_defaults().unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+}
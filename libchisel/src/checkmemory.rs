@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, Module, ResizableLimits};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks the limits of every imported and defined memory: the initial number of pages must not
+/// exceed `max_initial`, and if `max_maximum` is set, a declared maximum must not exceed it.
+pub struct CheckMemory {
+    max_initial: u32,
+    max_maximum: Option<u32>,
+}
+
+impl CheckMemory {
+    pub fn new(max_initial: u32, max_maximum: Option<u32>) -> Self {
+        CheckMemory {
+            max_initial,
+            max_maximum,
+        }
+    }
+
+    fn check_limits(&self, limits: &ResizableLimits) -> bool {
+        if limits.initial() > self.max_initial {
+            return false;
+        }
+        if let (Some(cap), Some(maximum)) = (self.max_maximum, limits.maximum()) {
+            return maximum <= cap;
+        }
+        true
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckMemory {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkmemory".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            CheckMemory::with_preset(preset)
+        } else if let Some(max_initial) = config.get("max_initial") {
+            let max_maximum = match config.get("max_maximum") {
+                Some(max_maximum) => Some(max_maximum.trim().parse::<u32>()?),
+                None => None,
+            };
+            Ok(CheckMemory::new(
+                max_initial.trim().parse::<u32>()?,
+                max_maximum,
+            ))
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModulePreset for CheckMemory {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            // Contracts start with a single page and grow memory as needed.
+            "ewasm" => Ok(CheckMemory::new(1, None)),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+impl ModuleValidator for CheckMemory {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if let Some(section) = module.import_section() {
+            for entry in section.entries() {
+                if let External::Memory(memory) = entry.external() {
+                    if !self.check_limits(memory.limits()) {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+
+        if let Some(section) = module.memory_section() {
+            for memory in section.entries() {
+                if !self.check_limits(memory.limits()) {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_exceeds_limit() {
+        // wast:
+        // (module
+        //   (memory 2)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x02,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMemory::new(1, None);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+
+        let checker = CheckMemory::new(2, None);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn imported_memory_maximum() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 1 16))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x10, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x01, 0x01, 0x10,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMemory::new(1, Some(16));
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        let checker = CheckMemory::new(1, Some(8));
+        assert_eq!(false, checker.validate(&module).unwrap());
+
+        let checker = CheckMemory::new(0, None);
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn ewasm_preset() {
+        // wast:
+        // (module
+        //   (memory (export "memory") 1)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07,
+            0x0a, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMemory::with_preset("ewasm").unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn with_config_limits() {
+        let mut conf = HashMap::new();
+        conf.insert("max_initial".to_string(), "4".to_string());
+        conf.insert("max_maximum".to_string(), "32".to_string());
+        let checker = CheckMemory::with_config(&conf).unwrap();
+        assert_eq!(checker.max_initial, 4);
+        assert_eq!(checker.max_maximum, Some(32));
+
+        conf.insert("max_maximum".to_string(), "many".to_string());
+        assert!(CheckMemory::with_config(&conf).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the module defines or imports at most one memory and at most one table, as required
+/// by the MVP.
+pub struct CheckMemTableCount {}
+
+impl<'a> ChiselModule<'a> for CheckMemTableCount {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkmemtablecount".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckMemTableCount {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for CheckMemTableCount {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        // Both spaces include imported and defined entries.
+        Ok(module.memory_space() <= 1 && module.table_space() <= 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::{External, ImportEntry, MemoryType};
+
+    use super::*;
+
+    #[test]
+    fn one_memory_ok() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMemTableCount::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn two_memories_defined() {
+        let module = builder::module()
+            .memory()
+            .with_min(1)
+            .build()
+            .memory()
+            .with_min(1)
+            .build()
+            .build();
+        assert_eq!(module.memory_section().unwrap().entries().len(), 2);
+
+        let checker = CheckMemTableCount::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn imported_and_defined_memory() {
+        let module = builder::module()
+            .with_import(ImportEntry::new(
+                "env".to_string(),
+                "memory".to_string(),
+                External::Memory(MemoryType::new(1, None)),
+            ))
+            .memory()
+            .with_min(1)
+            .build()
+            .build();
+
+        let checker = CheckMemTableCount::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn two_tables_defined() {
+        let module = builder::module()
+            .table()
+            .with_min(1)
+            .build()
+            .table()
+            .with_min(1)
+            .build()
+            .build();
+
+        let checker = CheckMemTableCount::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+}
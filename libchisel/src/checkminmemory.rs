@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::fixedmemory::initial_memory_size;
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the initial size of the memory, whether imported or defined, is at least
+/// `required_min_pages`. A module without memory is treated as having zero pages.
+pub struct CheckMinMemory {
+    required_min_pages: u32,
+}
+
+impl CheckMinMemory {
+    pub fn new(required_min_pages: u32) -> Self {
+        CheckMinMemory { required_min_pages }
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckMinMemory {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkminmemory".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(required_min_pages) = config.get("required_min_pages") {
+            Ok(CheckMinMemory::new(
+                required_min_pages.trim().parse::<u32>()?,
+            ))
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModuleValidator for CheckMinMemory {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        Ok(initial_memory_size(module).unwrap_or(0) >= self.required_min_pages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_required_minimum() {
+        // wast:
+        // (module
+        //   (memory (export "memory") 2)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x02, 0x07,
+            0x0a, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMinMemory::new(2);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn below_required_minimum() {
+        // wast:
+        // (module
+        //   (memory (export "memory") 2)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x02, 0x07,
+            0x0a, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMinMemory::new(3);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn imported_memory() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 1))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut conf = HashMap::new();
+        conf.insert("required_min_pages".to_string(), "1".to_string());
+        let checker = CheckMinMemory::with_config(&conf).unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        conf.insert("required_min_pages".to_string(), "2".to_string());
+        let checker = CheckMinMemory::with_config(&conf).unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn no_memory() {
+        // wast:
+        // (module)
+        let wasm: Vec<u8> = vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        assert_eq!(false, CheckMinMemory::new(1).validate(&module).unwrap());
+        assert_eq!(true, CheckMinMemory::new(0).validate(&module).unwrap());
+    }
+}
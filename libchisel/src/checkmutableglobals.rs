@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, GlobalType, Internal, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that no exported global is mutable, as exporting mutable globals is not allowed in the
+/// MVP.
+pub struct CheckMutableGlobals {}
+
+impl<'a> ChiselModule<'a> for CheckMutableGlobals {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkmutableglobals".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckMutableGlobals {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+/// Resolves the type of a global in the global index space, where imported globals come first.
+pub(crate) fn global_type_by_index(module: &Module, index: u32) -> Option<&GlobalType> {
+    let imported: Vec<&GlobalType> = if let Some(section) = module.import_section() {
+        section
+            .entries()
+            .iter()
+            .filter_map(|entry| match entry.external() {
+                External::Global(global_type) => Some(global_type),
+                _ => None,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let index = index as usize;
+    if index < imported.len() {
+        Some(imported[index])
+    } else {
+        module.global_section().and_then(|section| {
+            section
+                .entries()
+                .get(index - imported.len())
+                .map(|entry| entry.global_type())
+        })
+    }
+}
+
+impl ModuleValidator for CheckMutableGlobals {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if let Some(section) = module.export_section() {
+            for entry in section.entries() {
+                if let Internal::Global(index) = entry.internal() {
+                    match global_type_by_index(module, *index) {
+                        Some(global_type) if global_type.is_mutable() => return Ok(false),
+                        Some(_) => (),
+                        None => return Err(ModuleError::NotFound),
+                    }
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutable_global_exported() {
+        // wast:
+        // (module
+        //   (global (export "foo") (mut i32) (i32.const 1))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x06, 0x06, 0x01, 0x7f, 0x01, 0x41,
+            0x01, 0x0b, 0x07, 0x07, 0x01, 0x03, 0x66, 0x6f, 0x6f, 0x03, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMutableGlobals::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn immutable_global_exported() {
+        // wast:
+        // (module
+        //   (import "env" "bar" (global (mut i64)))
+        //   (global (export "foo") i32 (i32.const 1))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0c, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x03, 0x62, 0x61, 0x72, 0x03, 0x7e, 0x01, 0x06, 0x06, 0x01, 0x7f, 0x00, 0x41,
+            0x01, 0x0b, 0x07, 0x07, 0x01, 0x03, 0x66, 0x6f, 0x6f, 0x03, 0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMutableGlobals::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn mutable_imported_global_reexported() {
+        // wast:
+        // (module
+        //   (import "env" "bar" (global (mut i64)))
+        //   (global i32 (i32.const 1))
+        //   (export "bar" (global 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0c, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x03, 0x62, 0x61, 0x72, 0x03, 0x7e, 0x01, 0x06, 0x06, 0x01, 0x7f, 0x00, 0x41,
+            0x01, 0x0b, 0x07, 0x07, 0x01, 0x03, 0x62, 0x61, 0x72, 0x03, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckMutableGlobals::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+}
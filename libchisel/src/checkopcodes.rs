@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Instruction, Module};
+
+use super::checkfloat::CheckFloat;
+use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Rejects function bodies using instructions from the enabled post-MVP proposals, and
+/// optionally floating point instructions.
+///
+/// The saturating float-to-int instructions cannot be decoded by parity-wasm, so a module using
+/// them already fails to load. The `saturating_float_to_int` flag is accepted so that
+/// configurations can state the restriction explicitly.
+///
+/// The bulk memory decoder of parity-wasm 0.41 predates the final proposal: `memory.copy`
+/// takes a single reserved byte instead of two. A module encoded to the final specification
+/// misdecodes, its second reserved byte being read as `unreachable`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CheckOpcodes {
+    simd: bool,
+    bulk_memory: bool,
+    atomics: bool,
+    sign_extension: bool,
+    saturating_float_to_int: bool,
+    float: bool,
+}
+
+impl CheckOpcodes {
+    /// Returns a validator forbidding every post-MVP proposal. Floats are allowed.
+    pub fn mvp() -> Self {
+        CheckOpcodes {
+            simd: true,
+            bulk_memory: true,
+            atomics: true,
+            sign_extension: true,
+            saturating_float_to_int: true,
+            float: false,
+        }
+    }
+
+    /// Forbids floating point instructions as well.
+    pub fn without_float(self) -> Self {
+        CheckOpcodes {
+            float: true,
+            ..self
+        }
+    }
+
+    fn is_forbidden(&self, instruction: &Instruction) -> bool {
+        match instruction {
+            Instruction::Simd(_) => self.simd,
+            Instruction::Bulk(_) => self.bulk_memory,
+            Instruction::Atomics(_) => self.atomics,
+            Instruction::SignExt(_) => self.sign_extension,
+            _ => false,
+        }
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckOpcodes {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checkopcodes".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckOpcodes::mvp())
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            return CheckOpcodes::with_preset(preset);
+        }
+
+        // Each listed category is forbidden when set to true.
+        let flag = |key: &str| -> Result<bool, ModuleError> {
+            match config.get(key).map(|value| value.trim()) {
+                None | Some("false") => Ok(false),
+                Some("true") => Ok(true),
+                Some(_) => Err(ModuleError::NotSupported),
+            }
+        };
+        Ok(CheckOpcodes {
+            simd: flag("simd")?,
+            bulk_memory: flag("bulk_memory")?,
+            atomics: flag("atomics")?,
+            sign_extension: flag("sign_extension")?,
+            saturating_float_to_int: flag("saturating_float_to_int")?,
+            float: flag("float")?,
+        })
+    }
+}
+
+impl ModulePreset for CheckOpcodes {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            "ewasm" => Ok(CheckOpcodes::mvp().without_float()),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+impl ModuleValidator for CheckOpcodes {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if self.float && (CheckFloat {}).find_float(module).is_some() {
+            return Ok(false);
+        }
+
+        if let Some(code) = module.code_section() {
+            for body in code.bodies() {
+                for instruction in body.code().elements() {
+                    if self.is_forbidden(instruction) {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::BulkInstruction;
+
+    use super::*;
+
+    #[test]
+    fn memory_copy_forbidden() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func (export "main")
+        //     i32.const 0
+        //     i32.const 16
+        //     i32.const 4
+        //     memory.copy)
+        // )
+        // Encoded as parity-wasm expects, with a single reserved byte after `memory.copy`.
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x0d, 0x01, 0x0b, 0x00, 0x41, 0x00, 0x41, 0x10,
+            0x41, 0x04, 0xfc, 0x0a, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        assert_eq!(
+            module.code_section().unwrap().bodies()[0].code().elements(),
+            &[
+                Instruction::I32Const(0),
+                Instruction::I32Const(16),
+                Instruction::I32Const(4),
+                Instruction::Bulk(BulkInstruction::MemoryCopy),
+                Instruction::End,
+            ]
+        );
+        let checker = CheckOpcodes::with_defaults().unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+
+        let mut conf = HashMap::new();
+        conf.insert("sign_extension".to_string(), "true".to_string());
+        let checker = CheckOpcodes::with_config(&conf).unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn sign_extension_forbidden() {
+        // wast:
+        // (module
+        //   (func (export "main") (param i32) (result i32)
+        //     local.get 0
+        //     i32.extend8_s)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x06, 0x01, 0x60, 0x01, 0x7f,
+            0x01, 0x7f, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+            0x00, 0x00, 0x0a, 0x07, 0x01, 0x05, 0x00, 0x20, 0x00, 0xc0, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckOpcodes::with_preset("ewasm").unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+
+        let mut conf = HashMap::new();
+        conf.insert("bulk_memory".to_string(), "true".to_string());
+        let checker = CheckOpcodes::with_config(&conf).unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn ewasm_forbids_float() {
+        // wast:
+        // (module
+        //   (func (export "main") (result f32)
+        //     f32.const 1)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7d, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00,
+            0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x43, 0x00, 0x00, 0x80, 0x3f, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckOpcodes::with_defaults().unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        let checker = CheckOpcodes::with_preset("ewasm").unwrap();
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn invalid_flag() {
+        let mut conf = HashMap::new();
+        conf.insert("simd".to_string(), "yes".to_string());
+        assert!(CheckOpcodes::with_config(&conf).is_err());
+    }
+}
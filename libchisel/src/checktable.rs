@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, Module, ResizableLimits};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks the limits of every imported and defined table: the initial number of elements must
+/// not exceed `max_initial`, and if `max_maximum` is set, a declared maximum must not exceed it.
+/// The `ewasm` preset rejects any table.
+pub struct CheckTable {
+    max_initial: u32,
+    max_maximum: Option<u32>,
+    allow_tables: bool,
+}
+
+impl CheckTable {
+    pub fn new(max_initial: u32, max_maximum: Option<u32>) -> Self {
+        CheckTable {
+            max_initial,
+            max_maximum,
+            allow_tables: true,
+        }
+    }
+
+    /// Returns a validator rejecting every module which has a table, whatever its limits.
+    pub fn forbid_tables() -> Self {
+        CheckTable {
+            max_initial: 0,
+            max_maximum: Some(0),
+            allow_tables: false,
+        }
+    }
+
+    fn check_limits(&self, limits: &ResizableLimits) -> bool {
+        if !self.allow_tables || limits.initial() > self.max_initial {
+            return false;
+        }
+        if let (Some(cap), Some(maximum)) = (self.max_maximum, limits.maximum()) {
+            return maximum <= cap;
+        }
+        true
+    }
+}
+
+impl<'a> ChiselModule<'a> for CheckTable {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checktable".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            CheckTable::with_preset(preset)
+        } else if let Some(max_initial) = config.get("max_initial") {
+            let max_maximum = match config.get("max_maximum") {
+                Some(max_maximum) => Some(max_maximum.trim().parse::<u32>()?),
+                None => None,
+            };
+            Ok(CheckTable::new(
+                max_initial.trim().parse::<u32>()?,
+                max_maximum,
+            ))
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModulePreset for CheckTable {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            // Contracts are not expected to make indirect calls.
+            "ewasm" => Ok(CheckTable::forbid_tables()),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+impl ModuleValidator for CheckTable {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if let Some(section) = module.import_section() {
+            for entry in section.entries() {
+                if let External::Table(table) = entry.external() {
+                    if !self.check_limits(table.limits()) {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+
+        if let Some(section) = module.table_section() {
+            for table in section.entries() {
+                if !self.check_limits(table.limits()) {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_exceeds_limit() {
+        // wast:
+        // (module
+        //   (table 1 funcref)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x04, 0x04, 0x01, 0x70, 0x00, 0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckTable::new(0, None);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+
+        let checker = CheckTable::new(1, None);
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn imported_table_maximum() {
+        // wast:
+        // (module
+        //   (import "env" "table" (table 2 10 funcref))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x10, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x05, 0x74, 0x61, 0x62, 0x6c, 0x65, 0x01, 0x70, 0x01, 0x02, 0x0a,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut conf = HashMap::new();
+        conf.insert("max_initial".to_string(), "2".to_string());
+        conf.insert("max_maximum".to_string(), "8".to_string());
+        let checker = CheckTable::with_config(&conf).unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+
+        conf.insert("max_maximum".to_string(), "10".to_string());
+        let checker = CheckTable::with_config(&conf).unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn table_fails_ewasm() {
+        // wast:
+        // (module
+        //   (table 0 funcref)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x04, 0x04, 0x01, 0x70, 0x00, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckTable::with_preset("ewasm").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+
+        // A module without a table passes.
+        let result = checker.validate(&Module::default()).unwrap();
+        assert_eq!(true, result);
+    }
+}
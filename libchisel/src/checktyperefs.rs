@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that every type reference, in the function and import sections and in
+/// `call_indirect` instructions, points inside the type section.
+pub struct CheckTypeRefs {}
+
+impl<'a> ChiselModule<'a> for CheckTypeRefs {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "checktyperefs".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(CheckTypeRefs {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for CheckTypeRefs {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let type_count = module
+            .type_section()
+            .map_or(0, |section| section.types().len()) as u32;
+
+        if let Some(section) = module.function_section() {
+            if section
+                .entries()
+                .iter()
+                .any(|func| func.type_ref() >= type_count)
+            {
+                return Ok(false);
+            }
+        }
+
+        if let Some(section) = module.import_section() {
+            for entry in section.entries() {
+                if let External::Function(type_ref) = entry.external() {
+                    if *type_ref >= type_count {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+
+        if let Some(section) = module.code_section() {
+            for body in section.bodies() {
+                for instruction in body.code().elements() {
+                    if let Instruction::CallIndirect(type_ref, _) = instruction {
+                        if *type_ref >= type_count {
+                            return Ok(false);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // wast:
+    // (module
+    //   (type (func))
+    //   (import "env" "f" (func (type 0)))
+    //   (table 1 funcref)
+    //   (func (type 0)
+    //     (call_indirect (type 0) (i32.const 0)))
+    // )
+    const WASM: [u8; 46] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00, 0x02,
+        0x09, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x01, 0x66, 0x00, 0x00, 0x03, 0x02, 0x01, 0x00, 0x04,
+        0x04, 0x01, 0x70, 0x00, 0x01, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x41, 0x00, 0x11, 0x00, 0x00,
+        0x0b,
+    ];
+
+    /// Offsets of the type references in WASM.
+    const IMPORT_TYPE_REF: usize = 24;
+    const FUNCTION_TYPE_REF: usize = 28;
+    const CALL_INDIRECT_TYPE_REF: usize = 43;
+
+    fn validate_with_type_ref(offset: Option<usize>) -> bool {
+        let mut wasm = WASM.to_vec();
+        if let Some(offset) = offset {
+            wasm[offset] = 0x05;
+        }
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = CheckTypeRefs::with_defaults().unwrap();
+        checker.validate(&module).unwrap()
+    }
+
+    #[test]
+    fn type_refs_in_range() {
+        assert_eq!(true, validate_with_type_ref(None));
+    }
+
+    #[test]
+    fn function_type_ref_out_of_range() {
+        assert_eq!(false, validate_with_type_ref(Some(FUNCTION_TYPE_REF)));
+    }
+
+    #[test]
+    fn import_type_ref_out_of_range() {
+        assert_eq!(false, validate_with_type_ref(Some(IMPORT_TYPE_REF)));
+    }
+
+    #[test]
+    fn call_indirect_type_ref_out_of_range() {
+        assert_eq!(false, validate_with_type_ref(Some(CALL_INDIRECT_TYPE_REF)));
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{
+    ExportEntry, ExportSection, GlobalEntry, GlobalSection, GlobalType, ImportCountType, InitExpr,
+    Instruction, Internal, Module, Section, ValueType,
+};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Name of the exported global carrying the version.
+const VERSION_EXPORT: &str = "__chisel_version";
+
+/// Struct on which ModuleTranslator is implemented.
+/// Records which version of chisel processed a module by adding an exported immutable i32 global
+/// named `__chisel_version`. The version is encoded as `major << 16 | minor << 8 | patch`.
+pub struct ChiselVersion {}
+
+impl<'a> ChiselModule<'a> for ChiselVersion {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "chiselversion".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(ChiselVersion {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+/// Encodes the version of this crate as an i32.
+pub fn encoded_version() -> i32 {
+    let part = |part: &str| part.parse::<i32>().unwrap_or(0) & 0xff;
+    (part(env!("CARGO_PKG_VERSION_MAJOR")) << 16)
+        | (part(env!("CARGO_PKG_VERSION_MINOR")) << 8)
+        | part(env!("CARGO_PKG_VERSION_PATCH"))
+}
+
+fn add_version(module: &mut Module) -> bool {
+    if let Some(section) = module.export_section() {
+        if section
+            .entries()
+            .iter()
+            .any(|entry| entry.field() == VERSION_EXPORT)
+        {
+            return false;
+        }
+    }
+
+    if module.global_section().is_none() {
+        module
+            .insert_section(Section::Global(GlobalSection::default()))
+            .expect("insert_section should not fail");
+    }
+    if module.export_section().is_none() {
+        module
+            .insert_section(Section::Export(ExportSection::default()))
+            .expect("insert_section should not fail");
+    }
+
+    let index = module.import_count(ImportCountType::Global) as u32
+        + module
+            .global_section()
+            .map_or(0, |section| section.entries().len() as u32);
+
+    module
+        .global_section_mut()
+        .expect("Global section inserted above")
+        .entries_mut()
+        .push(GlobalEntry::new(
+            GlobalType::new(ValueType::I32, false),
+            InitExpr::new(vec![
+                Instruction::I32Const(encoded_version()),
+                Instruction::End,
+            ]),
+        ));
+    module
+        .export_section_mut()
+        .expect("Export section inserted above")
+        .entries_mut()
+        .push(ExportEntry::new(
+            VERSION_EXPORT.to_string(),
+            Internal::Global(index),
+        ));
+    true
+}
+
+impl ModuleTranslator for ChiselVersion {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(add_version(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if add_version(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version_global(module: &Module) -> Option<&GlobalEntry> {
+        let export = module
+            .export_section()?
+            .entries()
+            .iter()
+            .find(|entry| entry.field() == VERSION_EXPORT)?;
+        if let Internal::Global(index) = export.internal() {
+            let index = *index as usize - module.import_count(ImportCountType::Global);
+            module.global_section()?.entries().get(index)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn version_encoding() {
+        let version = encoded_version();
+        assert_eq!(
+            format!(
+                "{}.{}.{}",
+                version >> 16,
+                (version >> 8) & 0xff,
+                version & 0xff
+            ),
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    #[test]
+    fn version_global_added() {
+        // wast:
+        // (module
+        //   (import "env" "g" (global i32))
+        //   (global i64 (i64.const 1))
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0a, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x01, 0x67, 0x03, 0x7f, 0x00, 0x03, 0x02,
+            0x01, 0x00, 0x06, 0x06, 0x01, 0x7e, 0x00, 0x42, 0x01, 0x0b, 0x07, 0x08, 0x01, 0x04,
+            0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = ChiselVersion::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let global = version_global(&module).unwrap();
+        assert!(!global.global_type().is_mutable());
+        assert_eq!(global.global_type().content_type(), ValueType::I32);
+        assert_eq!(
+            global.init_expr().code(),
+            &[Instruction::I32Const(encoded_version()), Instruction::End]
+        );
+        assert!(walrus::Module::from_buffer(&module.to_bytes().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn empty_module_and_idempotent() {
+        let wasm: Vec<u8> = vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = ChiselVersion::with_defaults().unwrap();
+        let module = translator.translate(&module).unwrap().unwrap();
+        assert!(version_global(&module).is_some());
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::deployer::{
+    create_custom_deployer, create_memory_deployer, custom_deployer_payload,
+    memory_deployer_payload,
+};
+use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleTranslator};
+
+/// Enum on which ModuleTranslator is implemented.
+/// Re-wraps a module produced by `Deployer` in the other deployer format. The variant is the
+/// format to convert to.
+pub enum ConvertDeployer {
+    Memory,
+    CustomSection,
+}
+
+impl<'a> ChiselModule<'a> for ConvertDeployer {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "convertdeployer".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            ConvertDeployer::with_preset(preset)
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModulePreset for ConvertDeployer {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            "memory" => Ok(ConvertDeployer::Memory),
+            "customsection" => Ok(ConvertDeployer::CustomSection),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+impl ModuleTranslator for ConvertDeployer {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        if let Some(new_module) = self.translate(module)? {
+            *module = new_module;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        // Modules which are not a deployer of the opposite format are left untouched.
+        match self {
+            ConvertDeployer::Memory => {
+                Ok(custom_deployer_payload(module).map(|payload| create_memory_deployer(&payload)))
+            }
+            ConvertDeployer::CustomSection => match memory_deployer_payload(module) {
+                Some(payload) => Ok(Some(create_custom_deployer(&payload)?)),
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_hex::FromHex;
+
+    use super::*;
+
+    #[test]
+    fn custom_to_memory_to_custom() {
+        let payload: Vec<u8> = FromHex::from_hex("80ff007faa550011").unwrap();
+        let custom = create_custom_deployer(&payload).unwrap();
+
+        let to_memory = ConvertDeployer::with_preset("memory").unwrap();
+        let memory = to_memory.translate(&custom).unwrap().unwrap();
+        assert_eq!(memory_deployer_payload(&memory), Some(payload.clone()));
+        assert_eq!(
+            memory.clone().to_bytes().unwrap(),
+            create_memory_deployer(&payload).to_bytes().unwrap()
+        );
+
+        let to_custom = ConvertDeployer::with_preset("customsection").unwrap();
+        let mut roundtrip = memory;
+        let did_change = to_custom.translate_inplace(&mut roundtrip).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(roundtrip.to_bytes().unwrap(), custom.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn already_target_format() {
+        let payload: Vec<u8> = FromHex::from_hex("80ff007faa550011").unwrap();
+        let mut memory = create_memory_deployer(&payload);
+
+        let to_memory = ConvertDeployer::with_preset("memory").unwrap();
+        let did_change = to_memory.translate_inplace(&mut memory).unwrap();
+        assert_eq!(false, did_change);
+    }
+
+    #[test]
+    fn not_a_deployer() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        for preset in &["memory", "customsection"] {
+            let translator = ConvertDeployer::with_preset(preset).unwrap();
+            assert!(translator.translate(&module).unwrap().is_none());
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, ImportCountType, Instruction, Module};
+
+use super::depgraph::reachable_functions;
+use super::typeindex::renumber_type_refs;
+use super::{funcindex::remove_functions, ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Removes the defined functions which cannot be reached from an exported function, the start
+/// function or the table, along with the types no longer referenced, without a round trip through
+/// wasm-gc. Calls and type references are rewritten to the new indices. Imports are kept.
+pub struct DeadCode {}
+
+impl<'a> ChiselModule<'a> for DeadCode {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "deadcode".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(DeadCode {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for DeadCode {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(remove_dead_code(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if remove_dead_code(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn remove_dead_code(module: &mut Module) -> bool {
+    let imports_len = module.import_count(ImportCountType::Function) as u32;
+    let bodies_len = module
+        .code_section()
+        .map_or(0, |code| code.bodies().len() as u32);
+    let reachable = reachable_functions(module);
+
+    let removed: Vec<u32> = (imports_len..imports_len + bodies_len)
+        .filter(|idx| !reachable.contains(idx))
+        .collect();
+    if removed.is_empty() {
+        return false;
+    }
+
+    remove_functions(module, &removed);
+    remove_unused_types(module);
+    true
+}
+
+/// Removes the types which are not referenced by a function, a function import or a
+/// `call_indirect`, and renumbers the remaining type references.
+fn remove_unused_types(module: &mut Module) {
+    let types_len = if let Some(section) = module.type_section() {
+        section.types().len()
+    } else {
+        return;
+    };
+
+    // Dangling references do not mark any type as used.
+    let mut used = vec![false; types_len];
+    let mut mark = |type_ref: u32| {
+        if let Some(used) = used.get_mut(type_ref as usize) {
+            *used = true;
+        }
+    };
+    if let Some(section) = module.import_section() {
+        for entry in section.entries() {
+            if let External::Function(type_ref) = entry.external() {
+                mark(*type_ref);
+            }
+        }
+    }
+    if let Some(section) = module.function_section() {
+        for func in section.entries() {
+            mark(func.type_ref());
+        }
+    }
+    if let Some(section) = module.code_section() {
+        for body in section.bodies() {
+            for instruction in body.code().elements() {
+                if let Instruction::CallIndirect(type_ref, _) = instruction {
+                    mark(*type_ref);
+                }
+            }
+        }
+    }
+
+    if used.iter().all(|used| *used) {
+        return;
+    }
+
+    let mut remap = Vec::with_capacity(types_len);
+    let mut next = 0;
+    for used in used.iter() {
+        remap.push(next);
+        if *used {
+            next += 1;
+        }
+    }
+
+    renumber_type_refs(module, &remap);
+
+    let types = module
+        .type_section_mut()
+        .expect("Type section checked above")
+        .types_mut();
+    let mut index = 0;
+    types.retain(|_| {
+        index += 1;
+        used[index - 1]
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uncalled_function_removed() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (func (param i32))
+        //   (func (export "main")
+        //     call 3)
+        //   (func
+        //     call 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x00, 0x00,
+            0x60, 0x01, 0x7f, 0x00, 0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x66, 0x6f,
+            0x6f, 0x00, 0x00, 0x03, 0x04, 0x03, 0x01, 0x00, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x02, 0x0a, 0x0e, 0x03, 0x02, 0x00, 0x0b, 0x04, 0x00, 0x10,
+            0x03, 0x0b, 0x04, 0x00, 0x10, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = DeadCode::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let bodies = module.code_section().unwrap().bodies();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(
+            bodies[0].code().elements(),
+            &[Instruction::Call(2), Instruction::End]
+        );
+        assert_eq!(
+            bodies[1].code().elements(),
+            &[Instruction::Call(0), Instruction::End]
+        );
+        // The type of the removed function is no longer used.
+        assert_eq!(module.type_section().unwrap().types().len(), 1);
+        assert!(walrus::Module::from_buffer(&module.to_bytes().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn all_reachable() {
+        // wast:
+        // (module
+        //   (type (func (param i32)))
+        //   (table 1 anyfunc)
+        //   (elem (i32.const 0) 2)
+        //   (func (export "main")
+        //     i32.const 0
+        //     i32.const 0
+        //     call_indirect (type 0))
+        //   (func $start)
+        //   (func (param i32))
+        //   (start $start)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x00, 0x00, 0x03, 0x04, 0x03, 0x01, 0x01, 0x00, 0x04, 0x04, 0x01, 0x70,
+            0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x08, 0x01,
+            0x01, 0x09, 0x07, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x02, 0x0a, 0x11, 0x03, 0x09,
+            0x00, 0x41, 0x00, 0x41, 0x00, 0x11, 0x00, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x02, 0x00,
+            0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = DeadCode::with_defaults().unwrap();
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+}
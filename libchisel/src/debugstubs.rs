@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{
+    CodeSection, External, Func, FuncBody, FunctionSection, Instruction, Instructions, Module,
+    Section, SimdInstruction, Type, ValueType,
+};
+
+use super::{
+    funcindex::remap_func_indices, ChiselModule, ModuleError, ModuleKind, ModuleTranslator,
+};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Replaces every function imported from the `debug` namespace with a local no-op function of
+/// the same signature, and rewrites all references to the import to point at the stub.
+pub struct DebugStubs {}
+
+impl<'a> ChiselModule<'a> for DebugStubs {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "debugstubs".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(DebugStubs {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for DebugStubs {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        stub_debug_imports(module)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if stub_debug_imports(&mut ret)? {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the type references of all imported functions, paired with whether they are imported
+/// from the `debug` namespace.
+fn func_imports(module: &Module) -> Vec<(u32, bool)> {
+    if let Some(section) = module.import_section() {
+        section
+            .entries()
+            .iter()
+            .filter_map(|e| match e.external() {
+                External::Function(type_ref) => Some((*type_ref, e.module() == "debug")),
+                _ => None,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Builds a body which ignores its parameters and returns the zero value of the result type.
+fn stub_body(module: &Module, type_ref: u32) -> Result<FuncBody, ModuleError> {
+    let types = module.type_section().ok_or(ModuleError::NotFound)?.types();
+    let result = match types.get(type_ref as usize) {
+        Some(Type::Function(sig)) => sig.return_type(),
+        None => return Err(ModuleError::NotFound),
+    };
+
+    let mut instructions = match result {
+        Some(ValueType::I32) => vec![Instruction::I32Const(0)],
+        Some(ValueType::I64) => vec![Instruction::I64Const(0)],
+        Some(ValueType::F32) => vec![Instruction::F32Const(0)],
+        Some(ValueType::F64) => vec![Instruction::F64Const(0)],
+        Some(ValueType::V128) => vec![Instruction::Simd(SimdInstruction::V128Const(Box::new(
+            [0; 16],
+        )))],
+        None => vec![],
+    };
+    instructions.push(Instruction::End);
+
+    Ok(FuncBody::new(vec![], Instructions::new(instructions)))
+}
+
+fn stub_debug_imports(module: &mut Module) -> Result<bool, ModuleError> {
+    let imports = func_imports(module);
+    let stubbed: Vec<u32> = imports
+        .iter()
+        .filter(|(_, is_debug)| *is_debug)
+        .map(|(type_ref, _)| *type_ref)
+        .collect();
+
+    if stubbed.is_empty() {
+        return Ok(false);
+    }
+
+    let kept_imports = (imports.len() - stubbed.len()) as u32;
+    let defined_funcs = module
+        .function_section()
+        .map_or(0, |section| section.entries().len()) as u32;
+
+    // Kept imports stay in order, defined functions shift down by the number of removed imports,
+    // and the stubs are appended after the defined functions.
+    let mut remap = Vec::with_capacity(imports.len() + defined_funcs as usize);
+    let (mut next_import, mut next_stub) = (0, kept_imports + defined_funcs);
+    for (_, is_debug) in imports.iter() {
+        if *is_debug {
+            remap.push(next_stub);
+            next_stub += 1;
+        } else {
+            remap.push(next_import);
+            next_import += 1;
+        }
+    }
+    remap.extend(kept_imports..kept_imports + defined_funcs);
+
+    let bodies = stubbed
+        .iter()
+        .map(|type_ref| stub_body(module, *type_ref))
+        .collect::<Result<Vec<FuncBody>, ModuleError>>()?;
+
+    remap_func_indices(module, &remap);
+
+    module
+        .import_section_mut()
+        .expect("Function imports were found")
+        .entries_mut()
+        .retain(|e| match e.external() {
+            External::Function(_) => e.module() != "debug",
+            _ => true,
+        });
+
+    if module.function_section().is_none() {
+        module
+            .insert_section(Section::Function(FunctionSection::default()))
+            .expect("insert_section should not fail");
+    }
+    if module.code_section().is_none() {
+        module
+            .insert_section(Section::Code(CodeSection::default()))
+            .expect("insert_section should not fail");
+    }
+
+    module
+        .function_section_mut()
+        .expect("Function section exists")
+        .entries_mut()
+        .extend(stubbed.iter().map(|type_ref| Func::new(*type_ref)));
+    module
+        .code_section_mut()
+        .expect("Code section exists")
+        .bodies_mut()
+        .extend(bodies);
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{deserialize_buffer, serialize, Internal};
+
+    use super::*;
+
+    #[test]
+    fn no_debug_imports() {
+        // wast:
+        // (module
+        //   (import "ethereum" "useGas" (func (param i64)))
+        //   (func (export "main")
+        //     i64.const 1
+        //     call 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7e,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x13, 0x01, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65,
+            0x75, 0x6d, 0x06, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x00, 0x03, 0x02, 0x01,
+            0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a, 0x08, 0x01,
+            0x06, 0x00, 0x42, 0x01, 0x10, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = DebugStubs::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(false, did_change);
+    }
+
+    #[test]
+    fn debug_imports_stubbed() {
+        // wast:
+        // (module
+        //   (import "debug" "print32" (func (param i32)))
+        //   (import "ethereum" "useGas" (func (param i64)))
+        //   (import "debug" "getValue" (func (result i64)))
+        //   (table 1 funcref)
+        //   (elem (i32.const 0) 0)
+        //   (func (export "main")
+        //     i32.const 1
+        //     call 0
+        //     call 2
+        //     call 1)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x10, 0x04, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x01, 0x7e, 0x00, 0x60, 0x00, 0x01, 0x7e, 0x60, 0x00, 0x00, 0x02, 0x34,
+            0x03, 0x05, 0x64, 0x65, 0x62, 0x75, 0x67, 0x07, 0x70, 0x72, 0x69, 0x6e, 0x74, 0x33,
+            0x32, 0x00, 0x00, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65, 0x75, 0x6d, 0x06, 0x75,
+            0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x01, 0x05, 0x64, 0x65, 0x62, 0x75, 0x67, 0x08,
+            0x67, 0x65, 0x74, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x00, 0x02, 0x03, 0x02, 0x01, 0x03,
+            0x04, 0x04, 0x01, 0x70, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+            0x00, 0x03, 0x09, 0x07, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x00, 0x0a, 0x0c, 0x01,
+            0x0a, 0x00, 0x41, 0x01, 0x10, 0x00, 0x10, 0x02, 0x10, 0x01, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = DebugStubs::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let imports = module.import_section().unwrap().entries();
+        assert_eq!(imports.len(), 1);
+        assert!(imports.iter().all(|e| e.module() != "debug"));
+
+        // useGas is now 0, main is 1, and the stubs for print32 and getValue are 2 and 3.
+        let main = &module.code_section().unwrap().bodies()[0];
+        assert_eq!(
+            main.code().elements(),
+            &[
+                Instruction::I32Const(1),
+                Instruction::Call(2),
+                Instruction::Call(3),
+                Instruction::Call(0),
+                Instruction::End,
+            ]
+        );
+        match module.export_section().unwrap().entries()[0].internal() {
+            Internal::Function(idx) => assert_eq!(*idx, 1),
+            _ => panic!("Export must be a function"),
+        }
+        assert_eq!(
+            module.elements_section().unwrap().entries()[0].members(),
+            &[2]
+        );
+
+        let output = serialize(module).unwrap();
+        assert!(deserialize_buffer::<Module>(&output).is_ok());
+        assert!(walrus::Module::from_buffer(&output).is_ok());
+    }
+}
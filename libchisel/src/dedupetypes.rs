@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Module, Type};
+
+use super::typeindex::renumber_type_refs;
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Merges identical entries of the type section into the first of them, and rewrites the type
+/// references of functions, function imports and `call_indirect` instructions to match.
+pub struct DedupeTypes {}
+
+impl<'a> ChiselModule<'a> for DedupeTypes {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "dedupetypes".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(DedupeTypes {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for DedupeTypes {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(dedupe_types(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if dedupe_types(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn dedupe_types(module: &mut Module) -> bool {
+    let types = if let Some(section) = module.type_section() {
+        section.types()
+    } else {
+        return false;
+    };
+
+    let mut canonical: Vec<&Type> = Vec::with_capacity(types.len());
+    let mut remap = Vec::with_capacity(types.len());
+    for ty in types.iter() {
+        if let Some(index) = canonical.iter().position(|existing| *existing == ty) {
+            remap.push(index as u32);
+        } else {
+            remap.push(canonical.len() as u32);
+            canonical.push(ty);
+        }
+    }
+
+    if canonical.len() == types.len() {
+        return false;
+    }
+
+    let deduped: Vec<Type> = canonical.into_iter().cloned().collect();
+    renumber_type_refs(module, &remap);
+    *module
+        .type_section_mut()
+        .expect("Type section checked above")
+        .types_mut() = deduped;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::Instruction;
+
+    use super::*;
+    use crate::checktyperefs::CheckTypeRefs;
+    use crate::ModuleValidator;
+
+    #[test]
+    fn duplicate_types_merged() {
+        // wast:
+        // (module
+        //   (type (func (param i32)))
+        //   (type (func (param i32)))
+        //   (table 1 funcref)
+        //   (elem (i32.const 0) 1)
+        //   (func (type 0))
+        //   (func (type 1))
+        //   (func (export "main")
+        //     i32.const 0
+        //     call 1
+        //     i32.const 0
+        //     i32.const 0
+        //     call_indirect (type 1))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0c, 0x03, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x01, 0x7f, 0x00, 0x60, 0x00, 0x00, 0x03, 0x04, 0x03, 0x00, 0x01, 0x02,
+            0x04, 0x04, 0x01, 0x70, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+            0x00, 0x02, 0x09, 0x07, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x01, 0x0a, 0x15, 0x03,
+            0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x0d, 0x00, 0x41, 0x00, 0x10, 0x01, 0x41, 0x00,
+            0x41, 0x00, 0x11, 0x01, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = DedupeTypes::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        assert_eq!(module.type_section().unwrap().types().len(), 2);
+        let type_refs: Vec<u32> = module
+            .function_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|func| func.type_ref())
+            .collect();
+        assert_eq!(type_refs, vec![0, 0, 1]);
+
+        let main = module.code_section().unwrap().bodies()[2].code().elements();
+        assert_eq!(main[1], Instruction::Call(1));
+        assert_eq!(main[4], Instruction::CallIndirect(0, 0));
+
+        let checker = CheckTypeRefs::with_defaults().unwrap();
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn no_duplicates() {
+        // wast:
+        // (module
+        //   (type (func (param i32)))
+        //   (func (type 0))
+        //   (func (export "main")
+        //     i32.const 0
+        //     call 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x00, 0x00, 0x03, 0x03, 0x02, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a, 0x0b, 0x02, 0x02, 0x00, 0x0b, 0x06, 0x00, 0x41,
+            0x00, 0x10, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = DedupeTypes::with_defaults().unwrap();
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
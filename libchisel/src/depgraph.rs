@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use parity_wasm::elements::{FuncBody, Instruction, Module};
+use parity_wasm::elements::{FuncBody, ImportCountType, Instruction, Internal, Module};
 
 /// A function dependency graph is represented as a list of "edges", or pairs of function indices
 /// (a, b) where a calls b.
@@ -13,6 +13,9 @@ pub struct Edge(u32, u32);
 /// Container struct for the function dependency graph.
 pub struct DepGraph {
     edges: HashSet<Edge>,
+    /// Functions referenced by the element segments, any of which may be the target of a
+    /// `call_indirect`.
+    table: Vec<u32>,
 }
 
 /// Private interface for managing the function dependency graph
@@ -29,12 +32,51 @@ pub trait DepGraphBuilder: DepGraphManager {
     fn build(module: &Module, entry_idx: u32) -> Result<Self, ()>
     where
         Self: std::marker::Sized;
+
+    /// Builds the dependency graph using every function referenced by the element segments as an
+    /// entry point, for modules which are entered through their table rather than a start
+    /// function or export.
+    fn build_from_table(module: &Module) -> Result<Self, ()>
+    where
+        Self: std::marker::Sized;
 }
 
 impl DepGraph {
     pub fn new() -> Self {
         DepGraph {
             edges: HashSet::new(),
+            table: Vec::new(),
+        }
+    }
+
+    /// Collects the functions referenced by the element segments of the module, without
+    /// duplicates.
+    fn table_targets(module: &Module) -> Vec<u32> {
+        let mut targets: Vec<u32> = Vec::new();
+        if let Some(section) = module.elements_section() {
+            for segment in section.entries() {
+                for member in segment.members() {
+                    if !targets.contains(member) {
+                        targets.push(*member);
+                    }
+                }
+            }
+        }
+        targets
+    }
+
+    /// Sets up an empty graph for the module, returning it together with the number of imports
+    /// and the function bodies.
+    fn prepare(module: &Module) -> Result<(Self, u32, &[FuncBody]), ()> {
+        if let Some(code_section) = module.code_section() {
+            let mut ret = DepGraph::new();
+            ret.table = DepGraph::table_targets(module);
+
+            let imports_len = module.import_count(ImportCountType::Function) as u32;
+
+            Ok((ret, imports_len, code_section.bodies()))
+        } else {
+            Err(())
         }
     }
 
@@ -42,7 +84,60 @@ impl DepGraph {
     pub fn edgecount(&self) -> usize {
         self.edges.len()
     }
-    // TODO: better access methods
+
+    /// Returns the adjacency list of the graph, mapping each calling function to the functions it
+    /// calls.
+    pub fn adjacency(&self) -> HashMap<u32, Vec<u32>> {
+        let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+        for Edge(caller, callee) in self.edges.iter() {
+            adjacency.entry(*caller).or_default().push(*callee);
+        }
+        for callees in adjacency.values_mut() {
+            callees.sort();
+        }
+        adjacency
+    }
+
+    /// Returns the functions called directly by the function at the given index, in ascending
+    /// order.
+    pub fn callees(&self, idx: u32) -> Vec<u32> {
+        let mut callees: Vec<u32> = self
+            .edges
+            .iter()
+            .filter(|Edge(caller, _)| *caller == idx)
+            .map(|Edge(_, callee)| *callee)
+            .collect();
+        callees.sort();
+        callees
+    }
+
+    /// Returns the set of functions reachable from the function at the given index, including
+    /// the function itself.
+    pub fn reachable_from(&self, idx: u32) -> HashSet<u32> {
+        let adjacency = self.adjacency();
+        let mut reachable = HashSet::new();
+        let mut pending = vec![idx];
+        while let Some(current) = pending.pop() {
+            if reachable.insert(current) {
+                if let Some(callees) = adjacency.get(&current) {
+                    pending.extend(callees);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Returns true if the function at index `to` can be reached by a chain of calls starting at
+    /// the function at index `from`.
+    pub fn is_reachable(&self, from: u32, to: u32) -> bool {
+        self.reachable_from(from).contains(&to)
+    }
+}
+
+impl Default for DepGraph {
+    fn default() -> Self {
+        DepGraph::new()
+    }
 }
 
 impl DepGraphManager for DepGraph {
@@ -61,15 +156,26 @@ impl DepGraphManager for DepGraph {
         let func_body = &bodies[code_idx];
 
         for instr in func_body.code().elements().iter() {
-            if let Instruction::Call(call_idx) = instr {
-                if self.add_edge(Edge::from((idx, *call_idx))) {
-                    self.probe(*call_idx, imports_len, bodies);
-                } else {
-                    // If the edge already exists then begin backtracking.
-                    return;
+            match instr {
+                Instruction::Call(call_idx) => {
+                    // If the edge already exists then the callee has been probed already, but
+                    // the rest of this body still has to be searched.
+                    let is_new = self.add_edge(Edge::from((idx, *call_idx)));
+                    if is_new {
+                        self.probe(*call_idx, imports_len, bodies);
+                    }
+                }
+                // The callee is not known statically, so conservatively depend on every function
+                // in the table.
+                Instruction::CallIndirect(_, _) => {
+                    for target in self.table.clone() {
+                        if self.add_edge(Edge::from((idx, target))) {
+                            self.probe(target, imports_len, bodies);
+                        }
+                    }
                 }
+                _ => (),
             }
-            // TODO: Support for call_indirect
         }
     }
 
@@ -81,21 +187,17 @@ impl DepGraphManager for DepGraph {
 
 impl DepGraphBuilder for DepGraph {
     fn build(module: &Module, entry_idx: u32) -> Result<Self, ()> {
-        if let Some(code_section) = module.code_section() {
-            let mut ret = DepGraph::new();
-
-            let imports_len: u32 = if let Some(section) = module.import_section() {
-                section.entries().len() as u32
-            } else {
-                0
-            };
-
-            ret.probe(entry_idx, imports_len, &code_section.bodies());
+        let (mut ret, imports_len, bodies) = DepGraph::prepare(module)?;
+        ret.probe(entry_idx, imports_len, bodies);
+        Ok(ret)
+    }
 
-            Ok(ret)
-        } else {
-            Err(())
+    fn build_from_table(module: &Module) -> Result<Self, ()> {
+        let (mut ret, imports_len, bodies) = DepGraph::prepare(module)?;
+        for entry_idx in ret.table.clone() {
+            ret.probe(entry_idx, imports_len, bodies);
         }
+        Ok(ret)
     }
 }
 
@@ -105,6 +207,43 @@ impl From<(u32, u32)> for Edge {
     }
 }
 
+/// Returns the functions referenced by exports, the start section and element segments.
+pub(crate) fn root_functions(module: &Module) -> Vec<u32> {
+    let mut roots = Vec::new();
+    if let Some(section) = module.export_section() {
+        for entry in section.entries() {
+            if let Internal::Function(idx) = entry.internal() {
+                roots.push(*idx);
+            }
+        }
+    }
+    if let Some(start) = module.start_section() {
+        roots.push(start);
+    }
+    if let Some(section) = module.elements_section() {
+        for segment in section.entries() {
+            roots.extend(segment.members());
+        }
+    }
+    roots
+}
+
+/// Returns the functions reachable from any of the roots by a chain of calls.
+pub(crate) fn reachable_functions(module: &Module) -> HashSet<u32> {
+    let mut reachable = HashSet::new();
+    for root in root_functions(module) {
+        if reachable.contains(&root) {
+            continue;
+        }
+        if let Ok(graph) = DepGraph::build(module, root) {
+            reachable.extend(graph.reachable_from(root));
+        } else {
+            reachable.insert(root);
+        }
+    }
+    reachable
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,6 +399,15 @@ mod tests {
         let g = DepGraph::build(&module, 0).unwrap();
 
         assert!(g.edgecount() == 7);
+
+        assert_eq!(g.callees(0), vec![1, 2]);
+        assert_eq!(g.callees(1), vec![3, 4, 5]);
+        assert!(g.callees(2).is_empty());
+        assert_eq!(g.reachable_from(0), (0..6).collect::<HashSet<u32>>());
+        assert_eq!(g.reachable_from(1), [1, 3, 4, 5].iter().cloned().collect());
+        assert_eq!(g.reachable_from(2), [2].iter().cloned().collect());
+        assert!(g.is_reachable(3, 5));
+        assert!(!g.is_reachable(2, 1));
     }
 
     #[test]
@@ -312,6 +460,13 @@ mod tests {
         let g = DepGraph::build(&module, 0).unwrap();
 
         assert!(g.edgecount() == 10);
+
+        assert_eq!(g.callees(2), vec![4, 5]);
+        assert_eq!(g.reachable_from(2), [2, 4, 5].iter().cloned().collect());
+        assert_eq!(g.reachable_from(3), (1..6).collect::<HashSet<u32>>());
+        assert!(g.is_reachable(5, 4));
+        assert!(!g.is_reachable(4, 5));
+        assert_eq!(g.adjacency().get(&4), Some(&vec![4]));
     }
 
     #[test]
@@ -379,4 +534,60 @@ mod tests {
 
         assert!(g.edgecount() == 15);
     }
+
+    #[test]
+    fn call_indirect_table_targets() {
+        // wast:
+        // (module
+        //   (type (func))
+        //   (table 2 anyfunc)
+        //   (elem (i32.const 0) 1 2)
+        //   (func (export "main")
+        //     i32.const 0
+        //     call_indirect (type 0))
+        //   (func
+        //     call 3)
+        //   (func)
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x05, 0x04, 0x00, 0x00, 0x00, 0x00, 0x04, 0x04, 0x01, 0x70, 0x00, 0x02, 0x07,
+            0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x09, 0x08, 0x01, 0x00, 0x41,
+            0x00, 0x0b, 0x02, 0x01, 0x02, 0x0a, 0x14, 0x04, 0x07, 0x00, 0x41, 0x00, 0x11, 0x00,
+            0x00, 0x0b, 0x04, 0x00, 0x10, 0x03, 0x0b, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let g = DepGraph::build(&module, 0).unwrap();
+
+        assert!(g.edges.contains(&Edge(0, 1)));
+        assert!(g.edges.contains(&Edge(0, 2)));
+        assert!(g.edges.contains(&Edge(1, 3)));
+        assert!(g.edgecount() == 3);
+    }
+
+    #[test]
+    fn build_from_table_without_entry() {
+        // wast:
+        // (module
+        //   (table 1 anyfunc)
+        //   (elem (i32.const 0) 0)
+        //   (func
+        //     call 1)
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x04, 0x04, 0x01, 0x70, 0x00, 0x01, 0x09, 0x07, 0x01,
+            0x00, 0x41, 0x00, 0x0b, 0x01, 0x00, 0x0a, 0x09, 0x02, 0x04, 0x00, 0x10, 0x01, 0x0b,
+            0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let g = DepGraph::build_from_table(&module).unwrap();
+
+        assert!(g.edges.contains(&Edge(0, 1)));
+        assert!(g.edgecount() == 1);
+    }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use parity_wasm::builder;
-use parity_wasm::elements::{CustomSection, Module};
+use parity_wasm::elements::{CustomSection, External, Instruction, Internal, Module};
 
 use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleTranslator};
 
@@ -92,7 +92,7 @@ fn deployer_code() -> Vec<u8> {
 }
 
 /// Returns a module which contains the deployable bytecode as a custom section.
-fn create_custom_deployer(payload: &[u8]) -> Result<Module, ModuleError> {
+pub(crate) fn create_custom_deployer(payload: &[u8]) -> Result<Module, ModuleError> {
     // The standard deployer code, which expects a 32 bit little endian as the trailing content
     // immediately following the payload, placed in a custom section.
     let code = deployer_code();
@@ -127,7 +127,7 @@ fn create_custom_deployer(payload: &[u8]) -> Result<Module, ModuleError> {
 
 /// Returns a module which contains the deployable bytecode as a data segment.
 #[rustfmt::skip]
-fn create_memory_deployer(payload: &[u8]) -> Module {
+pub(crate) fn create_memory_deployer(payload: &[u8]) -> Module {
     // Instructions calling finish(0, payload_len)
     let instructions = vec![
         parity_wasm::elements::Instruction::I32Const(0),
@@ -180,6 +180,76 @@ fn create_memory_deployer(payload: &[u8]) -> Module {
         .build()
 }
 
+/// Returns the payload of a module created by `create_custom_deployer`, or None if the module does
+/// not contain a well-formed `deployer` custom section.
+pub(crate) fn custom_deployer_payload(module: &Module) -> Option<Vec<u8>> {
+    let section = module.custom_sections().find(|s| s.name() == "deployer")?;
+    let data = section.payload();
+    if data.len() < 4 {
+        return None;
+    }
+
+    // The payload is followed by its length as a 32 bit little endian number.
+    let (payload, len) = data.split_at(data.len() - 4);
+    let mut len_bytes = [0u8; 4];
+    len_bytes.copy_from_slice(len);
+    if u32::from_le_bytes(len_bytes) as usize == payload.len() {
+        Some(payload.to_vec())
+    } else {
+        None
+    }
+}
+
+/// Returns the payload of a module created by `create_memory_deployer`, or None if the module does
+/// not have its layout.
+pub(crate) fn memory_deployer_payload(module: &Module) -> Option<Vec<u8>> {
+    let imports = module.import_section()?.entries();
+    if imports.len() != 1 || imports[0].module() != "ethereum" || imports[0].field() != "finish" {
+        return None;
+    }
+    match imports[0].external() {
+        External::Function(_) => (),
+        _ => return None,
+    }
+
+    let segments = module.data_section()?.entries();
+    if segments.len() != 1 {
+        return None;
+    }
+    let payload = segments[0].value();
+    match segments[0].offset() {
+        Some(offset) if offset.code() == [Instruction::I32Const(0), Instruction::End] => (),
+        _ => return None,
+    }
+
+    // The main function must call finish(0, payload_len) and nothing else.
+    let main_idx = match module
+        .export_section()?
+        .entries()
+        .iter()
+        .find(|e| e.field() == "main")?
+        .internal()
+    {
+        Internal::Function(idx) => *idx as usize,
+        _ => return None,
+    };
+    let main = module
+        .code_section()?
+        .bodies()
+        .get(main_idx.checked_sub(1)?)?;
+    let expected = [
+        Instruction::I32Const(0),
+        Instruction::I32Const(payload.len() as i32),
+        Instruction::Call(0),
+        Instruction::End,
+    ];
+    if main.code().elements() == expected {
+        Some(payload.to_vec())
+    } else {
+        None
+    }
+}
+
 impl ModuleTranslator for Deployer {
     fn translate_inplace(&self, _module: &mut Module) -> Result<bool, ModuleError> {
         Err(ModuleError::NotSupported)
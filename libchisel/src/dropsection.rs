@@ -15,12 +15,22 @@ impl From<std::num::ParseIntError> for ModuleError {
 #[derive(Debug)]
 pub enum DropSection {
     NamesSection,
+    DataSection,
     /// Name of the custom section.
     CustomSectionByName(String),
+    /// Names of several custom sections, all of which are dropped.
+    CustomSectionsByName(Vec<String>),
     /// Index of the custom section.
     CustomSectionByIndex(usize),
     /// Index of the unknown section.
     UnknownSectionByIndex(usize),
+    /// Names of the custom sections to keep. Every other custom section, including the names
+    /// section unless "name" is listed, is dropped.
+    KeepCustomSections(Vec<String>),
+    /// The start section. The start function itself is kept.
+    StartSection,
+    /// The `producers` custom section emitted by Rust/LLVM.
+    ProducersSection,
 }
 
 impl<'a> ChiselModule<'a> for DropSection {
@@ -44,11 +54,15 @@ impl<'a> ChiselModule<'a> for DropSection {
 
     fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
         // Query all possible modes
-        let modes: [(&'static str, Option<&String>); 4] = [
+        let modes: [(&'static str, Option<&String>); 8] = [
             ("names", config.get("names".into())),
+            ("data", config.get("data".into())),
             ("custom_by_name", config.get("custom_by_name".into())),
             ("custom_by_index", config.get("custom_by_index".into())),
             ("unknown_by_index", config.get("unknown_by_index".into())),
+            ("keep_custom", config.get("keep_custom".into())),
+            ("start", config.get("start".into())),
+            ("producers", config.get("producers".into())),
         ];
 
         // Filter out modes which were provided.
@@ -73,11 +87,28 @@ impl<'a> ChiselModule<'a> for DropSection {
         let (mode, val) = matches.pop().expect("Verified that one match is present");
         match mode {
             "names" => Ok(DropSection::NamesSection),
+            "data" => Ok(DropSection::DataSection),
+            "custom_by_name" if val.contains(',') => Ok(DropSection::CustomSectionsByName(
+                val.split(',')
+                    .map(|name| name.trim())
+                    .filter(|name| !name.is_empty())
+                    .map(|name| name.to_string())
+                    .collect(),
+            )),
             "custom_by_name" => Ok(DropSection::CustomSectionByName(val.clone())),
             "custom_by_index" => Ok(DropSection::CustomSectionByIndex(str::parse::<usize>(val)?)),
             "unknown_by_index" => Ok(DropSection::UnknownSectionByIndex(str::parse::<usize>(
                 val,
             )?)),
+            "keep_custom" => Ok(DropSection::KeepCustomSections(
+                val.split(',')
+                    .map(|name| name.trim())
+                    .filter(|name| !name.is_empty())
+                    .map(|name| name.to_string())
+                    .collect(),
+            )),
+            "start" => Ok(DropSection::StartSection),
+            "producers" => Ok(DropSection::ProducersSection),
             _ => panic!("Only one of the above was present in the array"),
         }
     }
@@ -92,17 +123,69 @@ fn custom_section_index_for(module: &Module, name: &str) -> Option<usize> {
     })
 }
 
+fn data_section_index(module: &Module) -> Option<usize> {
+    for (index, section) in module.sections().iter().enumerate() {
+        if let Section::Data(_) = section {
+            return Some(index);
+        }
+    }
+    None
+}
+
+fn keep_custom_sections(module: &mut Module, keep: &[String]) -> bool {
+    let is_kept = |name: &str| keep.iter().any(|kept| kept == name);
+    let sections = module.sections_mut();
+    let len_before = sections.len();
+    sections.retain(|section| match section {
+        Section::Custom(custom) => is_kept(custom.name()),
+        Section::Name(_) => is_kept("name"),
+        _ => true,
+    });
+    sections.len() != len_before
+}
+
+fn drop_custom_sections(module: &mut Module, drop: &[String]) -> bool {
+    let is_dropped = |name: &str| drop.iter().any(|dropped| dropped == name);
+    let sections = module.sections_mut();
+    let len_before = sections.len();
+    sections.retain(|section| match section {
+        Section::Custom(custom) => !is_dropped(custom.name()),
+        Section::Name(_) => !is_dropped("name"),
+        _ => true,
+    });
+    sections.len() != len_before
+}
+
 impl DropSection {
     fn find_index(&self, module: &Module) -> Option<usize> {
         match &self {
             DropSection::NamesSection => custom_section_index_for(module, "name"),
+            DropSection::DataSection => data_section_index(module),
             DropSection::CustomSectionByName(name) => custom_section_index_for(module, &name),
+            DropSection::ProducersSection => custom_section_index_for(module, "producers"),
             DropSection::CustomSectionByIndex(index) => Some(*index),
             DropSection::UnknownSectionByIndex(index) => Some(*index),
+            DropSection::CustomSectionsByName(_)
+            | DropSection::KeepCustomSections(_)
+            | DropSection::StartSection => None,
         }
     }
 
     fn drop_section(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        if let DropSection::KeepCustomSections(keep) = &self {
+            return Ok(keep_custom_sections(module, keep));
+        }
+
+        if let DropSection::CustomSectionsByName(names) = &self {
+            return Ok(drop_custom_sections(module, names));
+        }
+
+        if let DropSection::StartSection = &self {
+            let had_start = module.start_section().is_some();
+            module.clear_start_section();
+            return Ok(had_start);
+        }
+
         if let Some(index) = self.find_index(&module) {
             let sections = module.sections_mut();
             if index < sections.len() {
@@ -313,6 +396,204 @@ mod tests {
         assert!(custom_section_index_for(&module1, "name").is_none());
     }
 
+    #[test]
+    fn remove_data_section() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (data (i32.const 0) "abc")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x0b,
+            0x09, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x03, 0x61, 0x62, 0x63,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        assert!(data_section_index(&module).is_some());
+
+        let dropper = DropSection::DataSection;
+        let translated = dropper.translate(&module).unwrap().unwrap();
+        assert!(translated.data_section().is_none());
+
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, true);
+        assert!(module.data_section().is_none());
+        assert!(module.memory_section().is_some());
+    }
+
+    #[test]
+    fn keep_intact_no_data_section() {
+        let mut module = builder::module().build();
+        let dropper = DropSection::DataSection;
+        assert!(dropper.translate(&module).unwrap().is_none());
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, false);
+    }
+
+    #[test]
+    fn with_config_data() {
+        let mut conf = HashMap::new();
+        conf.insert("data".to_string(), "".to_string());
+
+        let module = DropSection::with_config(&conf).unwrap();
+        match module {
+            DropSection::DataSection => (),
+            _ => panic!("expected DataSection, got {:?}", module),
+        }
+    }
+
+    #[test]
+    fn remove_start_section() {
+        // wast:
+        // (module
+        //   (func $start)
+        //   (func (export "main"))
+        //   (start $start)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00,
+            0x01, 0x08, 0x01, 0x00, 0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x00,
+            0x0f, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x01, 0x08, 0x01, 0x00, 0x05, 0x73, 0x74, 0x61,
+            0x72, 0x74,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let mut conf = HashMap::new();
+        conf.insert("start".to_string(), "".to_string());
+        let dropper = DropSection::with_config(&conf).unwrap();
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, true);
+        assert!(module.start_section().is_none());
+        // The start function itself is kept.
+        assert_eq!(module.code_section().unwrap().bodies().len(), 2);
+    }
+
+    #[test]
+    fn keep_intact_no_start_section() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let dropper = DropSection::StartSection;
+        assert!(dropper.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn keep_one_of_three_custom_sections() {
+        let mut module = builder::module()
+            .with_section(Section::Custom(CustomSection::new(
+                "first".to_string(),
+                vec![],
+            )))
+            .with_section(Section::Custom(CustomSection::new(
+                "second".to_string(),
+                vec![],
+            )))
+            .with_section(Section::Custom(CustomSection::new(
+                "third".to_string(),
+                vec![],
+            )))
+            .build();
+
+        let mut conf = HashMap::new();
+        conf.insert("keep_custom".to_string(), "second".to_string());
+        let dropper = DropSection::with_config(&conf).unwrap();
+
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, true);
+        let names: Vec<&str> = module.custom_sections().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["second"]);
+
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, false);
+    }
+
+    #[test]
+    fn keep_custom_sections_drops_names() {
+        let input = FromHex::from_hex(
+            "0061736d010000000104016000000303020000070801046d61696e00010a
+            0a020300010b040010000b0014046e616d65010d0200047465737401046d
+            61696e",
+        )
+        .unwrap();
+
+        let module = Module::from_bytes(&input)
+            .unwrap()
+            .parse_names()
+            .expect("Should not fail");
+
+        let dropper = DropSection::KeepCustomSections(vec!["other".to_string()]);
+        let translated = dropper.translate(&module).unwrap().unwrap();
+        assert!(custom_section_index_for(&translated, "name").is_none());
+        assert!(translated.code_section().is_some());
+
+        let dropper =
+            DropSection::KeepCustomSections(vec!["other".to_string(), "name".to_string()]);
+        assert!(dropper.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn remove_producers_section() {
+        let mut module = builder::module()
+            .with_section(Section::Custom(CustomSection::new(
+                "producers".to_string(),
+                vec![0x00],
+            )))
+            .with_section(Section::Custom(CustomSection::new(
+                "other".to_string(),
+                vec![],
+            )))
+            .build();
+
+        let mut conf = HashMap::new();
+        conf.insert("producers".to_string(), "".to_string());
+        let dropper = DropSection::with_config(&conf).unwrap();
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, true);
+
+        let custom: Vec<&str> = module.custom_sections().map(|c| c.name()).collect();
+        assert_eq!(custom, vec!["other"]);
+
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, false);
+    }
+
+    #[test]
+    fn remove_custom_sections_by_name_list() {
+        let mut module = builder::module().build();
+        for name in &["name", "producers", "target_features", "other"] {
+            module
+                .sections_mut()
+                .push(Section::Custom(CustomSection::new(
+                    name.to_string(),
+                    vec![],
+                )));
+        }
+
+        let mut conf = HashMap::new();
+        conf.insert(
+            "custom_by_name".to_string(),
+            "name,producers,target_features".to_string(),
+        );
+        let dropper = DropSection::with_config(&conf).unwrap();
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, true);
+
+        let custom: Vec<&str> = module.custom_sections().map(|c| c.name()).collect();
+        assert_eq!(custom, vec!["other"]);
+
+        let did_change = dropper.translate_inplace(&mut module).unwrap();
+        assert_eq!(did_change, false);
+    }
+
     #[test]
     fn with_config_multiple_modes() {
         let mut conf = HashMap::new();
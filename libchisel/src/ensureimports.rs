@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{
+    remapimports::RemapImports, verifyimports::VerifyImports, ChiselModule, ModuleError,
+    ModuleKind, ModulePreset, ModuleTranslator, ModuleValidator,
+};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Remaps imports with `RemapImports` and then verifies the result with `VerifyImports`, both
+/// using the same preset. Fails with `ModuleError::Custom` if the remapped module does not pass
+/// verification, in which case the module is left untouched.
+pub struct EnsureImports<'a> {
+    preset: String,
+    remap: RemapImports<'a>,
+    verify: VerifyImports<'a>,
+}
+
+impl<'a> ChiselModule<'a> for EnsureImports<'a> {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "ensureimports".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            EnsureImports::with_preset(preset)
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl<'a> ModulePreset for EnsureImports<'a> {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        Ok(EnsureImports {
+            preset: preset.to_string(),
+            remap: RemapImports::with_preset(preset)?,
+            verify: VerifyImports::with_preset(preset)?,
+        })
+    }
+}
+
+impl<'a> ModuleTranslator for EnsureImports<'a> {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        if let Some(new_module) = self.translate(module)? {
+            *module = new_module;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let remapped = self.remap.translate(module)?;
+        let verified = match &remapped {
+            Some(new_module) => self.verify.validate(new_module)?,
+            None => self.verify.validate(module)?,
+        };
+
+        if verified {
+            Ok(remapped)
+        } else {
+            Err(ModuleError::Custom(format!(
+                "imports do not match preset '{}' after remapping",
+                self.preset
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remapped_and_verified() {
+        // wast:
+        // (module
+        //   (import "env" "ethereum_useGas" (func (param i64)))
+        //   (memory 1)
+        //   (export "memory" (memory 0))
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7e,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x17, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x0f, 0x65, 0x74,
+            0x68, 0x65, 0x72, 0x65, 0x75, 0x6d, 0x5f, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00,
+            0x00, 0x03, 0x02, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x06,
+            0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00,
+            0x01, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = EnsureImports::with_preset("ewasm").unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let entry = &module.import_section().unwrap().entries()[0];
+        assert_eq!(entry.module(), "ethereum");
+        assert_eq!(entry.field(), "useGas");
+    }
+
+    #[test]
+    fn unfixable_import() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func (param i64)))
+        //   (memory 1)
+        //   (export "memory" (memory 0))
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7e,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x66, 0x6f,
+            0x6f, 0x00, 0x00, 0x03, 0x02, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11,
+            0x02, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x04, 0x6d, 0x61, 0x69,
+            0x6e, 0x00, 0x01, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = EnsureImports::with_preset("ewasm").unwrap();
+        let result = translator.translate_inplace(&mut module);
+        assert!(result.is_err());
+        assert_eq!(module.import_section().unwrap().entries()[0].field(), "foo");
+    }
+}
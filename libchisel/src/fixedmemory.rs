@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Removes `memory.grow` and `memory.size` for hosts with a fixed amount of memory. Each
+/// `memory.grow` is replaced by dropping its operand and returning -1, as for a failed grow, and
+/// each `memory.size` by the declared initial size of the memory.
+pub struct FixedMemory {}
+
+impl<'a> ChiselModule<'a> for FixedMemory {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "fixedmemory".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(FixedMemory {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+/// Returns the initial size in pages of the memory, whether imported or defined.
+pub(crate) fn initial_memory_size(module: &Module) -> Option<u32> {
+    if let Some(section) = module.import_section() {
+        for entry in section.entries() {
+            if let External::Memory(memory) = entry.external() {
+                return Some(memory.limits().initial());
+            }
+        }
+    }
+    module
+        .memory_section()
+        .and_then(|section| section.entries().first())
+        .map(|memory| memory.limits().initial())
+}
+
+fn fix_memory(module: &mut Module) -> Result<bool, ModuleError> {
+    // `memory.size` cannot be replaced without a memory, so fail before any body is touched.
+    let uses_size = module.code_section().map_or(false, |code| {
+        code.bodies().iter().any(|body| {
+            body.code()
+                .elements()
+                .iter()
+                .any(|instruction| matches!(instruction, Instruction::CurrentMemory(_)))
+        })
+    });
+    let initial = match initial_memory_size(module) {
+        Some(initial) => initial,
+        None if uses_size => return Err(ModuleError::NotFound),
+        None => 0,
+    };
+
+    let mut modified = false;
+    if let Some(code) = module.code_section_mut() {
+        for body in code.bodies_mut() {
+            let instructions = body.code_mut().elements_mut();
+            let mut fixed = Vec::with_capacity(instructions.len());
+            for instruction in instructions.drain(..) {
+                match instruction {
+                    Instruction::GrowMemory(_) => {
+                        fixed.push(Instruction::Drop);
+                        fixed.push(Instruction::I32Const(-1));
+                        modified = true;
+                    }
+                    Instruction::CurrentMemory(_) => {
+                        fixed.push(Instruction::I32Const(initial as i32));
+                        modified = true;
+                    }
+                    other => fixed.push(other),
+                }
+            }
+            *instructions = fixed;
+        }
+    }
+    Ok(modified)
+}
+
+impl ModuleTranslator for FixedMemory {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        fix_memory(module)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if fix_memory(&mut ret)? {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grow_and_size_replaced() {
+        // wast:
+        // (module
+        //   (memory 2)
+        //   (func (export "main") (result i32)
+        //     i32.const 1
+        //     memory.grow
+        //     drop
+        //     memory.size)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7f, 0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x02, 0x07, 0x08, 0x01, 0x04,
+            0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x0b, 0x01, 0x09, 0x00, 0x41, 0x01, 0x40,
+            0x00, 0x1a, 0x3f, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = FixedMemory::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let code = module.code_section().unwrap().bodies()[0].code().elements();
+        assert_eq!(
+            code,
+            &[
+                Instruction::I32Const(1),
+                Instruction::Drop,
+                Instruction::I32Const(-1),
+                Instruction::Drop,
+                Instruction::I32Const(2),
+                Instruction::End,
+            ][..]
+        );
+
+        assert!(walrus::Module::from_buffer(&module.to_bytes().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn imported_memory_size() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 3))
+        //   (func (export "main") (result i32)
+        //     memory.size)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7f, 0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72,
+            0x79, 0x02, 0x00, 0x03, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61,
+            0x69, 0x6e, 0x00, 0x00, 0x0a, 0x06, 0x01, 0x04, 0x00, 0x3f, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = FixedMemory::with_defaults().unwrap();
+        let new = translator.translate(&module).unwrap().unwrap();
+        let code = new.code_section().unwrap().bodies()[0].code().elements();
+        assert_eq!(code[0], Instruction::I32Const(3));
+    }
+
+    #[test]
+    fn no_memory_instructions() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = FixedMemory::with_defaults().unwrap();
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn size_without_memory_untouched() {
+        // wast:
+        // (module
+        //   (func (export "main") (result i32)
+        //     i32.const 1
+        //     memory.grow
+        //     drop
+        //     memory.size)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7f, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00,
+            0x00, 0x0a, 0x0b, 0x01, 0x09, 0x00, 0x41, 0x01, 0x40, 0x00, 0x1a, 0x3f, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = FixedMemory::with_defaults().unwrap();
+        let result = translator.translate_inplace(&mut module);
+        assert_eq!(result, Err(ModuleError::NotFound));
+
+        // The body is left as it was.
+        let code = module.code_section().unwrap().bodies()[0].code().elements();
+        assert_eq!(
+            code,
+            &[
+                Instruction::I32Const(1),
+                Instruction::GrowMemory(0),
+                Instruction::Drop,
+                Instruction::CurrentMemory(0),
+                Instruction::End,
+            ]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Helpers for keeping function references consistent when functions are added, removed or
+//! reordered.
+
+use parity_wasm::elements::{
+    CodeSection, External, Func, FuncBody, FunctionSection, ImportCountType, Instruction, Internal,
+    Module, Section,
+};
+
+/// Returns the index of a function after the functions in `removed` are deleted, or None if the
+/// function itself was removed.
+pub(crate) fn index_after_removal(index: u32, removed: &[u32]) -> Option<u32> {
+    if removed.contains(&index) {
+        None
+    } else {
+        Some(index - removed.iter().filter(|r| **r < index).count() as u32)
+    }
+}
+
+/// Updates the start section after the functions in `removed` are deleted. The start section is
+/// cleared if the start function itself was removed. Returns true if the module was modified.
+pub(crate) fn remap_start_after_removal(module: &mut Module, removed: &[u32]) -> bool {
+    if let Some(start) = module.start_section() {
+        match index_after_removal(start, removed) {
+            Some(new_start) if new_start == start => false,
+            Some(new_start) => {
+                module.set_start_section(new_start);
+                true
+            }
+            None => {
+                module.clear_start_section();
+                true
+            }
+        }
+    } else {
+        false
+    }
+}
+
+/// Applies `remap` to every function index referenced in the module. `remap` is indexed by the
+/// old function index and must cover the whole function index space.
+pub(crate) fn remap_func_indices(module: &mut Module, remap: &[u32]) {
+    let remap_idx = |idx: u32| remap[idx as usize];
+
+    if let Some(code) = module.code_section_mut() {
+        for body in code.bodies_mut() {
+            for instruction in body.code_mut().elements_mut() {
+                if let Instruction::Call(idx) = instruction {
+                    *idx = remap_idx(*idx);
+                }
+            }
+        }
+    }
+
+    if let Some(exports) = module.export_section_mut() {
+        for entry in exports.entries_mut() {
+            if let Internal::Function(idx) = entry.internal_mut() {
+                *idx = remap_idx(*idx);
+            }
+        }
+    }
+
+    if let Some(elements) = module.elements_section_mut() {
+        for segment in elements.entries_mut() {
+            for member in segment.members_mut() {
+                *member = remap_idx(*member);
+            }
+        }
+    }
+
+    if let Some(start) = module.start_section() {
+        module.set_start_section(remap_idx(start));
+    }
+
+    if let Some(names) = module.names_section_mut() {
+        if let Some(functions) = names.functions_mut() {
+            let remapped = functions
+                .names()
+                .iter()
+                .map(|(idx, name)| (remap_idx(idx), name.clone()))
+                .collect();
+            *functions.names_mut() = remapped;
+        }
+        if let Some(locals) = names.locals_mut() {
+            let remapped = locals
+                .local_names()
+                .iter()
+                .map(|(idx, names)| (remap_idx(idx), names.clone()))
+                .collect();
+            *locals.local_names_mut() = remapped;
+        }
+    }
+}
+
+/// Drops the names of the functions in `removed` and renumbers all references to the remaining
+/// functions as if the removed ones were deleted from the function index space.
+fn renumber_after_removal(module: &mut Module, removed: &[u32]) {
+    let imports_len = module.import_count(ImportCountType::Function) as u32;
+
+    // Names of removed functions would otherwise collide with the renumbered ones.
+    if let Some(names) = module.names_section_mut() {
+        for idx in removed.iter() {
+            if let Some(functions) = names.functions_mut() {
+                functions.names_mut().remove(*idx);
+            }
+            if let Some(locals) = names.locals_mut() {
+                locals.local_names_mut().remove(*idx);
+            }
+        }
+    }
+
+    let bodies_len = module
+        .code_section()
+        .map_or(0, |code| code.bodies().len() as u32);
+    let remap: Vec<u32> = (0..imports_len + bodies_len)
+        .map(|idx| index_after_removal(idx, removed).unwrap_or(0))
+        .collect();
+    remap_func_indices(module, &remap);
+}
+
+/// Deletes the defined functions in `removed`, given in ascending order, and renumbers all
+/// references to the remaining functions. References to the removed functions must not remain.
+pub(crate) fn remove_functions(module: &mut Module, removed: &[u32]) {
+    let imports_len = module.import_count(ImportCountType::Function) as u32;
+    renumber_after_removal(module, removed);
+
+    // Remove in reverse order so the remaining positions stay valid.
+    for idx in removed.iter().rev() {
+        let code_idx = (idx - imports_len) as usize;
+        if let Some(functions) = module.function_section_mut() {
+            functions.entries_mut().remove(code_idx);
+        }
+        if let Some(code) = module.code_section_mut() {
+            code.bodies_mut().remove(code_idx);
+        }
+    }
+}
+
+/// Deletes the imported functions in `removed`, given in ascending order, and renumbers all
+/// references to the remaining functions. References to the removed imports must not remain.
+pub(crate) fn remove_imported_functions(module: &mut Module, removed: &[u32]) {
+    renumber_after_removal(module, removed);
+
+    if let Some(imports) = module.import_section_mut() {
+        // Function indices only count function imports, so walk the entries alongside them.
+        let mut func_idx = 0;
+        imports.entries_mut().retain(|entry| {
+            if let External::Function(_) = entry.external() {
+                func_idx += 1;
+                !removed.contains(&(func_idx - 1))
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// Appends a function after all other functions, so no indices need to be remapped, and returns
+/// its index.
+pub(crate) fn append_function(module: &mut Module, type_ref: u32, body: FuncBody) -> u32 {
+    if module.function_section().is_none() {
+        module
+            .insert_section(Section::Function(FunctionSection::default()))
+            .expect("insert_section should not fail");
+    }
+    if module.code_section().is_none() {
+        module
+            .insert_section(Section::Code(CodeSection::default()))
+            .expect("insert_section should not fail");
+    }
+
+    let func_index = module.functions_space() as u32;
+    module
+        .function_section_mut()
+        .expect("Function section exists")
+        .entries_mut()
+        .push(Func::new(type_ref));
+    module
+        .code_section_mut()
+        .expect("Code section exists")
+        .bodies_mut()
+        .push(body);
+    func_index
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+
+    use super::*;
+
+    #[test]
+    fn index_after_removal_shifts() {
+        assert_eq!(index_after_removal(0, &[1, 2]), Some(0));
+        assert_eq!(index_after_removal(1, &[1, 2]), None);
+        assert_eq!(index_after_removal(3, &[1, 2]), Some(1));
+        assert_eq!(index_after_removal(3, &[]), Some(3));
+    }
+
+    #[test]
+    fn start_removed_clears_section() {
+        let mut module = builder::module()
+            .function()
+            .main()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .build();
+        assert_eq!(module.start_section(), Some(0));
+
+        assert!(remap_start_after_removal(&mut module, &[0]));
+        assert_eq!(module.start_section(), None);
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{
+    ExportEntry, FuncBody, FunctionType, Instruction, Instructions, Internal, Module, ValueType,
+};
+
+use super::funcindex::append_function;
+use super::typeindex::signature_type_ref;
+use super::{
+    checkmutableglobals::global_type_by_index, ChiselModule, ModuleError, ModuleKind,
+    ModuleTranslator,
+};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Replaces every export of a mutable global `foo` with an exported function `get_foo` returning
+/// the value of the global. The global itself is no longer exported.
+pub struct GlobalGetters {}
+
+impl<'a> ChiselModule<'a> for GlobalGetters {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "globalgetters".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(GlobalGetters {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for GlobalGetters {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        replace_mutable_global_exports(module)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if replace_mutable_global_exports(&mut ret)? {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn replace_mutable_global_exports(module: &mut Module) -> Result<bool, ModuleError> {
+    // Collect the export position, global index, value type and getter name of each mutable
+    // global export.
+    let mut replaced: Vec<(usize, u32, ValueType, String)> = Vec::new();
+    if let Some(section) = module.export_section() {
+        for (position, entry) in section.entries().iter().enumerate() {
+            if let Internal::Global(index) = entry.internal() {
+                let global_type =
+                    global_type_by_index(module, *index).ok_or(ModuleError::NotFound)?;
+                if global_type.is_mutable() {
+                    let field = format!("get_{}", entry.field());
+                    if section.entries().iter().any(|other| other.field() == field) {
+                        return Err(ModuleError::Custom(format!(
+                            "cannot export getter '{}', the name is already exported",
+                            field
+                        )));
+                    }
+                    replaced.push((position, *index, global_type.content_type(), field));
+                }
+            }
+        }
+    }
+
+    if replaced.is_empty() {
+        return Ok(false);
+    }
+
+    for (position, global_index, value_type, field) in replaced {
+        let type_ref = signature_type_ref(module, FunctionType::new(vec![], Some(value_type)));
+        let func_index = append_function(
+            module,
+            type_ref,
+            FuncBody::new(
+                vec![],
+                Instructions::new(vec![Instruction::GetGlobal(global_index), Instruction::End]),
+            ),
+        );
+
+        let entries = module
+            .export_section_mut()
+            .expect("Exports were found")
+            .entries_mut();
+        entries[position] = ExportEntry::new(field, Internal::Function(func_index));
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutable_global_export_replaced() {
+        // wast:
+        // (module
+        //   (global (export "foo") (mut i32) (i32.const 1))
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x06, 0x06, 0x01, 0x7f, 0x01, 0x41, 0x01, 0x0b, 0x07, 0x0e,
+            0x02, 0x03, 0x66, 0x6f, 0x6f, 0x03, 0x00, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = GlobalGetters::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let exports = module.export_section().unwrap().entries();
+        assert_eq!(exports.len(), 2);
+        assert_eq!(exports[0].field(), "get_foo");
+        assert_eq!(*exports[0].internal(), Internal::Function(1));
+        assert_eq!(exports[1].field(), "main");
+
+        let getter = &module.code_section().unwrap().bodies()[1];
+        assert_eq!(
+            getter.code().elements(),
+            &[Instruction::GetGlobal(0), Instruction::End]
+        );
+
+        let serialized = module.to_bytes().unwrap();
+        assert!(walrus::Module::from_buffer(&serialized).is_ok());
+    }
+
+    #[test]
+    fn getter_name_taken() {
+        // wast:
+        // (module
+        //   (global (export "foo") (mut i32) (i32.const 1))
+        //   (func (export "get_foo"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x06, 0x06, 0x01, 0x7f, 0x01, 0x41, 0x01, 0x0b, 0x07, 0x11,
+            0x02, 0x03, 0x66, 0x6f, 0x6f, 0x03, 0x00, 0x07, 0x67, 0x65, 0x74, 0x5f, 0x66, 0x6f,
+            0x6f, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = GlobalGetters::with_defaults().unwrap();
+        assert!(translator.translate_inplace(&mut module).is_err());
+
+        // The module is left as it was.
+        let exports = module.export_section().unwrap().entries();
+        assert_eq!(*exports[0].internal(), Internal::Global(0));
+        assert_eq!(module.code_section().unwrap().bodies().len(), 1);
+    }
+
+    #[test]
+    fn immutable_global_export_kept() {
+        // wast:
+        // (module
+        //   (import "env" "bar" (global (mut i64)))
+        //   (global (export "foo") i32 (i32.const 1))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0c, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x03, 0x62, 0x61, 0x72, 0x03, 0x7e, 0x01, 0x06, 0x06, 0x01, 0x7f, 0x00, 0x41,
+            0x01, 0x0b, 0x07, 0x07, 0x01, 0x03, 0x66, 0x6f, 0x6f, 0x03, 0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = GlobalGetters::with_defaults().unwrap();
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
@@ -1,15 +1,16 @@
-use parity_wasm::elements::{FunctionType, ValueType};
+use parity_wasm::elements::{FunctionType, GlobalType, MemoryType, ValueType};
 
 use super::{ModuleError, ModulePreset};
 
 pub struct ImportList<'a>(Vec<ImportType<'a>>);
 
-/// Enum internally representing a type of import.
+/// Enum internally representing a type of import. Globals carry their expected value type and
+/// mutability, and memories their expected limits and shared flag.
 #[derive(Clone)]
 pub enum ImportType<'a> {
     Function(&'a str, &'a str, FunctionType),
-    Global(&'a str, &'a str),
-    Memory(&'a str, &'a str),
+    Global(&'a str, &'a str, GlobalType),
+    Memory(&'a str, &'a str, MemoryType),
     Table(&'a str, &'a str),
 }
 
@@ -17,20 +18,20 @@ impl<'a> ImportType<'a> {
     pub fn module(&self) -> &'a str {
         // FIXME: Is there a way to shorten this expression?
         match self {
-            ImportType::Function(module, _, _) => module,
-            ImportType::Global(module, _)
-            | ImportType::Memory(module, _)
-            | ImportType::Table(module, _) => module,
+            ImportType::Function(module, _, _)
+            | ImportType::Global(module, _, _)
+            | ImportType::Memory(module, _, _) => module,
+            ImportType::Table(module, _) => module,
         }
     }
 
     pub fn field(&self) -> &'a str {
         // FIXME: Is there a way to shorten this expression?
         match self {
-            ImportType::Function(_, field, _) => field,
-            ImportType::Global(_, field)
-            | ImportType::Memory(_, field)
-            | ImportType::Table(_, field) => field,
+            ImportType::Function(_, field, _)
+            | ImportType::Global(_, field, _)
+            | ImportType::Memory(_, field, _) => field,
+            ImportType::Table(_, field) => field,
         }
     }
 
@@ -341,6 +342,34 @@ impl<'a> ModulePreset for ImportList<'a> {
                     FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
                 ),
             ])),
+            // Later Scout versions pass the state roots with an explicit length.
+            "eth2_v2" => Ok(ImportList(vec![
+                ImportType::Function(
+                    "eth2",
+                    "loadPreStateRoot",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "eth2",
+                    "blockDataSize",
+                    FunctionType::new(vec![], Some(ValueType::I32)),
+                ),
+                ImportType::Function(
+                    "eth2",
+                    "blockDataCopy",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "eth2",
+                    "savePostStateRoot",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "eth2",
+                    "pushNewDeposit",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+            ])),
             "debug" => Ok(ImportList(vec![
                 ImportType::Function(
                     "debug",
@@ -393,6 +422,263 @@ impl<'a> ModulePreset for ImportList<'a> {
                     ),
                 ),
             ])),
+            // The parity pwasm-ethereum ABI. `gas` is imported by the gas metering injected by
+            // pwasm-utils.
+            "pwasm" => Ok(ImportList(vec![
+                ImportType::Function(
+                    "env",
+                    "storage_read",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "storage_write",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "balance",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "ccall",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I64,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "env",
+                    "dcall",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I64,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "env",
+                    "scall",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I64,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "env",
+                    "value",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "create",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "env",
+                    "create2",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "env",
+                    "suicide",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "blockhash",
+                    FunctionType::new(vec![ValueType::I64, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "blocknumber",
+                    FunctionType::new(vec![], Some(ValueType::I64)),
+                ),
+                ImportType::Function(
+                    "env",
+                    "coinbase",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "difficulty",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "gaslimit",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "gasleft",
+                    FunctionType::new(vec![], Some(ValueType::I64)),
+                ),
+                ImportType::Function(
+                    "env",
+                    "sender",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "address",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "origin",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "timestamp",
+                    FunctionType::new(vec![], Some(ValueType::I64)),
+                ),
+                ImportType::Function(
+                    "env",
+                    "elog",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        None,
+                    ),
+                ),
+                ImportType::Function(
+                    "env",
+                    "ret",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "input_length",
+                    FunctionType::new(vec![], Some(ValueType::I32)),
+                ),
+                ImportType::Function(
+                    "env",
+                    "fetch_input",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "panic",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "env",
+                    "debug",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], None),
+                ),
+                ImportType::Function("env", "gas", FunctionType::new(vec![ValueType::I32], None)),
+            ])),
+            "wasi_snapshot_preview1" => Ok(ImportList(vec![
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "fd_write",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "fd_read",
+                    FunctionType::new(
+                        vec![
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                            ValueType::I32,
+                        ],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "proc_exit",
+                    FunctionType::new(vec![ValueType::I32], None),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "environ_get",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], Some(ValueType::I32)),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "environ_sizes_get",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], Some(ValueType::I32)),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "args_get",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], Some(ValueType::I32)),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "args_sizes_get",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], Some(ValueType::I32)),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "clock_time_get",
+                    FunctionType::new(
+                        vec![ValueType::I32, ValueType::I64, ValueType::I32],
+                        Some(ValueType::I32),
+                    ),
+                ),
+                ImportType::Function(
+                    "wasi_snapshot_preview1",
+                    "random_get",
+                    FunctionType::new(vec![ValueType::I32, ValueType::I32], Some(ValueType::I32)),
+                ),
+            ])),
             _ => Err(ModuleError::NotSupported),
         }
     }
@@ -408,6 +694,12 @@ mod tests {
         assert!(list.lookup_by_field("useGas").is_some());
     }
 
+    #[test]
+    fn lookup_by_field_wasi_good() {
+        let list = ImportList::with_preset("wasi_snapshot_preview1").unwrap();
+        assert!(list.lookup_by_field("fd_write").is_some());
+    }
+
     #[test]
     fn lookup_by_field_ewasm_not_found() {
         let list = ImportList::with_preset("ewasm").unwrap();
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Replaces `get_global` instructions computing call arguments with the value of the global,
+/// when the global is immutable and initialized with a constant. Only globals defined in the
+/// module can be resolved, as the values of imported globals are provided by the host.
+pub struct InlineConstGlobals {}
+
+impl<'a> ChiselModule<'a> for InlineConstGlobals {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "inlineconstglobals".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(InlineConstGlobals {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for InlineConstGlobals {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(inline_const_globals(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if inline_const_globals(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the constant value of every global in the global index space, or None for globals
+/// which are imported, mutable, or not initialized with a single constant.
+fn const_global_values(module: &Module) -> Vec<Option<Instruction>> {
+    let mut values: Vec<Option<Instruction>> = Vec::new();
+
+    if let Some(section) = module.import_section() {
+        for entry in section.entries() {
+            if let External::Global(_) = entry.external() {
+                values.push(None);
+            }
+        }
+    }
+
+    if let Some(section) = module.global_section() {
+        for entry in section.entries() {
+            let value = if entry.global_type().is_mutable() {
+                None
+            } else {
+                match entry.init_expr().code() {
+                    [instruction @ Instruction::I32Const(_), Instruction::End]
+                    | [instruction @ Instruction::I64Const(_), Instruction::End]
+                    | [instruction @ Instruction::F32Const(_), Instruction::End]
+                    | [instruction @ Instruction::F64Const(_), Instruction::End] => {
+                        Some(instruction.clone())
+                    }
+                    _ => None,
+                }
+            };
+            values.push(value);
+        }
+    }
+
+    values
+}
+
+fn inline_const_globals(module: &mut Module) -> bool {
+    let values = const_global_values(module);
+    if values.iter().all(|value| value.is_none()) {
+        return false;
+    }
+
+    let mut modified = false;
+    if let Some(code) = module.code_section_mut() {
+        for body in code.bodies_mut() {
+            let instructions = body.code_mut().elements_mut();
+            // Walk backwards from each call over the instructions pushing its arguments.
+            let mut in_arguments = false;
+            for instruction in instructions.iter_mut().rev() {
+                match instruction {
+                    Instruction::Call(_) => in_arguments = true,
+                    Instruction::GetGlobal(index) if in_arguments => {
+                        if let Some(Some(value)) = values.get(*index as usize) {
+                            *instruction = value.clone();
+                            modified = true;
+                        }
+                    }
+                    // Instructions which only push a value may compute further arguments.
+                    Instruction::I32Const(_)
+                    | Instruction::I64Const(_)
+                    | Instruction::F32Const(_)
+                    | Instruction::F64Const(_)
+                    | Instruction::GetLocal(_)
+                    | Instruction::GetGlobal(_) => (),
+                    _ => in_arguments = false,
+                }
+            }
+        }
+    }
+    modified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn const_global_argument_inlined() {
+        // wast:
+        // (module
+        //   (import "env" "base" (global i32))
+        //   (import "env" "f" (func (param i32)))
+        //   (global i32 (i32.const 7))
+        //   (global (mut i32) (i32.const 9))
+        //   (func (export "main")
+        //     (call 0 (global.get 0))
+        //     (call 0 (global.get 1))
+        //     (call 0 (global.get 2)))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x15, 0x02, 0x03, 0x65, 0x6e, 0x76, 0x04, 0x62, 0x61,
+            0x73, 0x65, 0x03, 0x7f, 0x00, 0x03, 0x65, 0x6e, 0x76, 0x01, 0x66, 0x00, 0x00, 0x03,
+            0x02, 0x01, 0x01, 0x06, 0x0b, 0x02, 0x7f, 0x00, 0x41, 0x07, 0x0b, 0x7f, 0x01, 0x41,
+            0x09, 0x0b, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a, 0x10,
+            0x01, 0x0e, 0x00, 0x23, 0x00, 0x10, 0x00, 0x23, 0x01, 0x10, 0x00, 0x23, 0x02, 0x10,
+            0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = InlineConstGlobals::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        // Only the immutable global defined in the module is inlined.
+        assert_eq!(
+            module.code_section().unwrap().bodies()[0].code().elements(),
+            &[
+                Instruction::GetGlobal(0),
+                Instruction::Call(0),
+                Instruction::I32Const(7),
+                Instruction::Call(0),
+                Instruction::GetGlobal(2),
+                Instruction::Call(0),
+                Instruction::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn no_const_globals() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = InlineConstGlobals::with_defaults().unwrap();
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
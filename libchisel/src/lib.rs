@@ -5,22 +5,74 @@ use std::{error, fmt};
 
 pub mod imports;
 
+pub mod addcustomsection;
 #[cfg(feature = "binaryen")]
 pub mod binaryenopt;
+pub mod canonicalize;
+pub mod checkcallbacks;
+pub mod checkconstoffsets;
+pub mod checkdata;
+pub mod checkdeployregion;
+pub mod checkduplicateexports;
+pub mod checkentrypoint;
 pub mod checkfloat;
+pub mod checkfunccount;
+pub mod checkfunctionbodies;
+pub mod checkhascode;
+pub mod checkimportorder;
+pub mod checkimportsection;
+pub mod checklocals;
+pub mod checkmemory;
+pub mod checkmemtablecount;
+pub mod checkminmemory;
+pub mod checkmutableglobals;
+pub mod checkopcodes;
 pub mod checkstartfunc;
+pub mod checkstartnotimported;
+pub mod checktable;
+pub mod checktyperefs;
+pub mod chiselversion;
+pub mod convertdeployer;
+pub mod deadcode;
+pub mod debugstubs;
+pub mod dedupetypes;
+pub mod depgraph;
 pub mod deployer;
 pub mod dropsection;
+pub mod ensureimports;
+pub mod fixedmemory;
+pub mod globalgetters;
+pub mod inlineconstglobals;
+pub mod padsize;
+pub mod profiledropnames;
+pub mod relocatedata;
 pub mod remapimports;
 pub mod remapstart;
+pub mod removenops;
+pub mod renameexports;
+pub mod renumberstart;
 pub mod repack;
+pub mod roundtrip;
+pub mod setmemory;
 pub mod snip;
+pub mod sortexports;
+pub mod sortimports;
+pub mod stats;
+pub mod strictexports;
+pub mod stub;
+pub mod template;
 pub mod trimexports;
+pub mod trimimports;
 pub mod trimstartfunc;
+pub mod trimunreachable;
+pub mod unexportmemory;
 pub mod verifyexports;
 pub mod verifyimports;
+pub mod verifyinstructions;
+pub mod wrapmain;
 
-mod depgraph;
+mod funcindex;
+mod typeindex;
 
 #[derive(Eq, PartialEq, Debug)]
 pub enum ModuleKind {
@@ -83,6 +135,34 @@ pub trait ModulePreset {
         Self: std::marker::Sized;
 }
 
+/// Returns the presets accepted by `with_preset` of the chisel module with the given id, or an
+/// empty list if the module takes no presets.
+pub fn available_presets(module_id: &str) -> Vec<&'static str> {
+    match module_id {
+        #[cfg(feature = "binaryen")]
+        "binaryenopt" => vec!["O0", "O1", "O2", "O3", "O4", "Os", "Oz"],
+        "checkdeployregion" | "convertdeployer" | "deployer" => vec!["memory", "customsection"],
+        "checkimportorder" | "checkmemory" | "checkopcodes" | "checktable" => vec!["ewasm"],
+        "ensureimports" | "remapimports" => {
+            vec!["ewasm", "eth2", "eth2_v2", "debug", "bignum", "pwasm"]
+        }
+        "remapstart" | "stub" | "trimstartfunc" => vec!["ewasm"],
+        "template" => vec!["ewasm_stub"],
+        "trimexports" => vec!["ewasm", "pwasm"],
+        "verifyexports" => vec!["ewasm", "eth2"],
+        "verifyimports" => vec![
+            "ewasm",
+            "eth2",
+            "eth2_v2",
+            "debug",
+            "bignum",
+            "pwasm",
+            "wasi_snapshot_preview1",
+        ],
+        _ => vec![],
+    }
+}
+
 impl From<String> for ModuleError {
     fn from(error: String) -> Self {
         ModuleError::Custom(error)
@@ -258,4 +338,28 @@ mod tests {
         let result = as_trait.validate(&Module::default());
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn available_presets_remapimports() {
+        let presets = available_presets("remapimports");
+        assert!(presets.contains(&"ewasm"));
+        assert!(presets.contains(&"bignum"));
+        assert!(available_presets("repack").is_empty());
+    }
+
+    #[test]
+    fn available_presets_accepted() {
+        for preset in available_presets("remapimports") {
+            assert!(remapimports::RemapImports::with_preset(preset).is_ok());
+        }
+        for preset in available_presets("verifyimports") {
+            assert!(verifyimports::VerifyImports::with_preset(preset).is_ok());
+        }
+        for preset in available_presets("trimexports") {
+            assert!(trimexports::TrimExports::with_preset(preset).is_ok());
+        }
+        for preset in available_presets("deployer") {
+            assert!(deployer::Deployer::with_preset(preset).is_ok());
+        }
+    }
 }
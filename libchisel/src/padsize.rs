@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{CustomSection, Module, Section};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Name of the custom section holding the padding.
+const PADDING_SECTION_NAME: &str = "padding";
+
+/// Struct on which ModuleTranslator is implemented.
+/// Appends a custom section of zero bytes so that the serialized module is exactly `target_size`
+/// bytes long.
+pub struct PadSize {
+    target_size: usize,
+}
+
+impl PadSize {
+    pub fn new(target_size: usize) -> Self {
+        PadSize { target_size }
+    }
+}
+
+impl<'a> ChiselModule<'a> for PadSize {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "padsize".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(target_size) = config.get("target_size") {
+            Ok(PadSize {
+                target_size: target_size.trim().parse::<usize>()?,
+            })
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+/// Returns the length of `value` encoded as a LEB128 varuint32.
+fn varuint32_len(value: usize) -> usize {
+    let mut len = 1;
+    let mut value = value >> 7;
+    while value != 0 {
+        len += 1;
+        value >>= 7;
+    }
+    len
+}
+
+/// Returns the payload length of a padding section which grows the module by exactly `needed`
+/// bytes, if there is one.
+fn padding_payload_len(needed: usize) -> Option<usize> {
+    let name_len = varuint32_len(PADDING_SECTION_NAME.len()) + PADDING_SECTION_NAME.len();
+    // The section is encoded as its id, its length, the name and the payload.
+    (1..=5).find_map(|size_len| {
+        let payload_len = needed.checked_sub(1 + size_len + name_len)?;
+        if varuint32_len(name_len + payload_len) == size_len {
+            Some(payload_len)
+        } else {
+            None
+        }
+    })
+}
+
+impl PadSize {
+    fn pad(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        let current_size = module.clone().to_bytes()?.len();
+        if current_size > self.target_size {
+            return Err(ModuleError::Custom(format!(
+                "module is {} bytes, which exceeds the target size of {} bytes",
+                current_size, self.target_size
+            )));
+        }
+        if current_size == self.target_size {
+            return Ok(false);
+        }
+
+        let needed = self.target_size - current_size;
+        let payload_len = padding_payload_len(needed).ok_or_else(|| {
+            ModuleError::Custom(format!(
+                "cannot pad module by exactly {} bytes with a custom section",
+                needed
+            ))
+        })?;
+
+        module
+            .sections_mut()
+            .push(Section::Custom(CustomSection::new(
+                PADDING_SECTION_NAME.to_string(),
+                vec![0; payload_len],
+            )));
+        Ok(true)
+    }
+}
+
+impl ModuleTranslator for PadSize {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        self.pad(module)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if self.pad(&mut ret)? {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // wast:
+    // (module
+    //   (func (export "main"))
+    // )
+    const WASM: [u8; 34] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00, 0x03,
+        0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04,
+        0x01, 0x02, 0x00, 0x0b,
+    ];
+
+    #[test]
+    fn padded_to_target_size() {
+        for target_size in &[WASM.len() + 10, 100, 200, 1024, 65536] {
+            let mut module = Module::from_bytes(WASM).unwrap();
+            let translator = PadSize::new(*target_size);
+            let did_change = translator.translate_inplace(&mut module).unwrap();
+            assert_eq!(true, did_change);
+            assert_eq!(module.to_bytes().unwrap().len(), *target_size);
+        }
+    }
+
+    #[test]
+    fn already_target_size() {
+        let module = Module::from_bytes(WASM).unwrap();
+        let translator = PadSize::new(WASM.len());
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn exceeds_target_size() {
+        let module = Module::from_bytes(WASM).unwrap();
+        let mut config = HashMap::new();
+        config.insert("target_size".to_string(), "16".to_string());
+        let translator = PadSize::with_config(&config).unwrap();
+        assert!(translator.translate(&module).is_err());
+    }
+
+    #[test]
+    fn too_small_to_pad() {
+        // The smallest padding section takes ten bytes.
+        let module = Module::from_bytes(WASM).unwrap();
+        let translator = PadSize::new(WASM.len() + 5);
+        assert!(translator.translate(&module).is_err());
+    }
+}
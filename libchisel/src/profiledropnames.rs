@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::dropsection::DropSection;
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Drops the names section when building the `release` profile, and leaves the module untouched
+/// for any other profile. This lets a single configuration serve both release and debug builds.
+pub struct ProfileDropNames {
+    profile: String,
+}
+
+impl ProfileDropNames {
+    pub fn new(profile: &str) -> Self {
+        ProfileDropNames {
+            profile: profile.to_string(),
+        }
+    }
+
+    fn is_release(&self) -> bool {
+        self.profile == "release"
+    }
+}
+
+impl<'a> ChiselModule<'a> for ProfileDropNames {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "profiledropnames".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(profile) = config.get("profile") {
+            Ok(ProfileDropNames::new(profile))
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModuleTranslator for ProfileDropNames {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        if self.is_release() {
+            DropSection::NamesSection.translate_inplace(module)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        if self.is_release() {
+            DropSection::NamesSection.translate(module)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_hex::FromHex;
+
+    use super::*;
+
+    fn has_names(module: &Module) -> bool {
+        module.names_section().is_some()
+            || module
+                .custom_sections()
+                .any(|section| section.name() == "name")
+    }
+
+    fn with_names() -> Module {
+        let input = FromHex::from_hex(
+            "0061736d010000000104016000000303020000070801046d61696e00010a
+            0a020300010b040010000b0014046e616d65010d0200047465737401046d
+            61696e",
+        )
+        .unwrap();
+        Module::from_bytes(&input).unwrap()
+    }
+
+    #[test]
+    fn release_drops_names() {
+        let mut conf = HashMap::new();
+        conf.insert("profile".to_string(), "release".to_string());
+
+        let mut module = with_names();
+        assert!(has_names(&module));
+
+        let translator = ProfileDropNames::with_config(&conf).unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert!(!has_names(&module));
+    }
+
+    #[test]
+    fn debug_keeps_names() {
+        let mut conf = HashMap::new();
+        conf.insert("profile".to_string(), "debug".to_string());
+
+        let module = with_names();
+        let translator = ProfileDropNames::with_config(&conf).unwrap();
+        assert!(translator.translate(&module).unwrap().is_none());
+        assert!(has_names(&module));
+    }
+
+    #[test]
+    fn missing_profile() {
+        let conf = HashMap::new();
+        assert!(ProfileDropNames::with_config(&conf).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// A region of linear memory which was moved from `old_start` to `new_start`.
+#[derive(Clone, Debug, PartialEq)]
+struct Relocation {
+    old_start: u32,
+    len: u32,
+    new_start: u32,
+}
+
+/// Map of the regions of linear memory moved by a data-relocating pass. Translators which move
+/// data segments register each move here, so that `RelocateData` can rewrite the addresses used
+/// by the code afterwards.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RelocationMap {
+    entries: Vec<Relocation>,
+}
+
+impl RelocationMap {
+    pub fn new() -> Self {
+        RelocationMap {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registers that the `len` bytes at `old_start` were moved to `new_start`.
+    pub fn add(&mut self, old_start: u32, len: u32, new_start: u32) {
+        self.entries.push(Relocation {
+            old_start,
+            len,
+            new_start,
+        });
+    }
+
+    /// Returns the new location of the given address, or None if it was not moved.
+    pub fn relocate(&self, address: u32) -> Option<u32> {
+        self.entries
+            .iter()
+            .find(|r| address >= r.old_start && address - r.old_start < r.len)
+            .map(|r| r.new_start + (address - r.old_start))
+    }
+
+    /// Parses a comma-separated list of moves in the form `old_start:len:new_start`.
+    fn parse(list: &str) -> Result<Self, ModuleError> {
+        let mut ret = RelocationMap::new();
+        for entry in list.split(',').map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let fields = entry
+                .split(':')
+                .map(|field| field.trim().parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()?;
+            if fields.len() != 3 {
+                return Err(ModuleError::Custom(format!(
+                    "invalid relocation '{}', expected old_start:len:new_start",
+                    entry
+                )));
+            }
+            ret.add(fields[0], fields[1], fields[2]);
+        }
+        Ok(ret)
+    }
+}
+
+/// Struct on which ModuleTranslator is implemented.
+/// Rewrites the constant addresses of loads and stores which point into a relocated region of
+/// memory, following a pass which moved data segments.
+///
+/// Only addresses given directly by an `i32.const` are rewritten: for loads, the constant must
+/// immediately precede the load, and for stores, the stored value must be a single constant,
+/// `local.get` or `global.get` between the address and the store. The static offset of the
+/// access is taken into account.
+pub struct RelocateData {
+    map: RelocationMap,
+}
+
+impl RelocateData {
+    pub fn new(map: RelocationMap) -> Self {
+        RelocateData { map }
+    }
+}
+
+impl<'a> ChiselModule<'a> for RelocateData {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "relocatedata".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(relocations) = config.get("relocations") {
+            Ok(RelocateData::new(RelocationMap::parse(relocations)?))
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+/// Returns the static offset of a load, or None if the instruction is not a load.
+fn load_offset(instruction: &Instruction) -> Option<u32> {
+    match instruction {
+        Instruction::I32Load(_, offset)
+        | Instruction::I64Load(_, offset)
+        | Instruction::F32Load(_, offset)
+        | Instruction::F64Load(_, offset)
+        | Instruction::I32Load8S(_, offset)
+        | Instruction::I32Load8U(_, offset)
+        | Instruction::I32Load16S(_, offset)
+        | Instruction::I32Load16U(_, offset)
+        | Instruction::I64Load8S(_, offset)
+        | Instruction::I64Load8U(_, offset)
+        | Instruction::I64Load16S(_, offset)
+        | Instruction::I64Load16U(_, offset)
+        | Instruction::I64Load32S(_, offset)
+        | Instruction::I64Load32U(_, offset) => Some(*offset),
+        _ => None,
+    }
+}
+
+/// Returns the static offset of a store, or None if the instruction is not a store.
+fn store_offset(instruction: &Instruction) -> Option<u32> {
+    match instruction {
+        Instruction::I32Store(_, offset)
+        | Instruction::I64Store(_, offset)
+        | Instruction::F32Store(_, offset)
+        | Instruction::F64Store(_, offset)
+        | Instruction::I32Store8(_, offset)
+        | Instruction::I32Store16(_, offset)
+        | Instruction::I64Store8(_, offset)
+        | Instruction::I64Store16(_, offset)
+        | Instruction::I64Store32(_, offset) => Some(*offset),
+        _ => None,
+    }
+}
+
+impl RelocateData {
+    /// Returns the static offset of the memory access consuming the address pushed at `index`.
+    fn access_offset(instructions: &[Instruction], index: usize) -> Option<u32> {
+        match instructions.get(index + 1)? {
+            // A single value pushed between the address and the store.
+            Instruction::I32Const(_)
+            | Instruction::I64Const(_)
+            | Instruction::F32Const(_)
+            | Instruction::F64Const(_)
+            | Instruction::GetLocal(_)
+            | Instruction::GetGlobal(_) => store_offset(instructions.get(index + 2)?),
+            next => load_offset(next),
+        }
+    }
+
+    fn relocate(&self, module: &mut Module) -> bool {
+        let mut modified = false;
+        if let Some(code) = module.code_section_mut() {
+            for body in code.bodies_mut() {
+                let instructions = body.code_mut().elements_mut();
+                for index in 0..instructions.len() {
+                    let address = if let Instruction::I32Const(address) = instructions[index] {
+                        address as u32
+                    } else {
+                        continue;
+                    };
+                    let offset = match RelocateData::access_offset(instructions, index) {
+                        Some(offset) => offset,
+                        None => continue,
+                    };
+                    let effective = match address.checked_add(offset) {
+                        Some(effective) => effective,
+                        None => continue,
+                    };
+                    if let Some(relocated) = self.map.relocate(effective) {
+                        if let Some(new_address) = relocated.checked_sub(offset) {
+                            if new_address != address {
+                                instructions[index] = Instruction::I32Const(new_address as i32);
+                                modified = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        modified
+    }
+}
+
+impl ModuleTranslator for RelocateData {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(self.relocate(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if self.relocate(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::InitExpr;
+
+    use super::*;
+
+    #[test]
+    fn relocation_map() {
+        let mut map = RelocationMap::new();
+        map.add(16, 4, 64);
+        assert_eq!(map.relocate(15), None);
+        assert_eq!(map.relocate(16), Some(64));
+        assert_eq!(map.relocate(19), Some(67));
+        assert_eq!(map.relocate(20), None);
+    }
+
+    #[test]
+    fn moved_segment_consumers_relocated() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (data (i32.const 16) "abcd")
+        //   (func (export "main") (result i32)
+        //     i32.const 17
+        //     i32.const 5
+        //     i32.store8
+        //     i32.const 16
+        //     i32.load offset=2
+        //     i32.const 16
+        //     drop)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7f, 0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04,
+            0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x13, 0x01, 0x11, 0x00, 0x41, 0x11, 0x41,
+            0x05, 0x3a, 0x00, 0x00, 0x41, 0x10, 0x28, 0x02, 0x02, 0x41, 0x10, 0x1a, 0x0b, 0x0b,
+            0x0a, 0x01, 0x00, 0x41, 0x10, 0x0b, 0x04, 0x61, 0x62, 0x63, 0x64,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        // A data-moving pass moves the segment to offset 64 and registers the move.
+        module.data_section_mut().unwrap().entries_mut()[0]
+            .offset_mut()
+            .replace(InitExpr::new(vec![
+                Instruction::I32Const(64),
+                Instruction::End,
+            ]));
+        let mut map = RelocationMap::new();
+        map.add(16, 4, 64);
+
+        let translator = RelocateData::new(map);
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let code = module.code_section().unwrap().bodies()[0].code().elements();
+        assert_eq!(code[0], Instruction::I32Const(65));
+        assert_eq!(code[3], Instruction::I32Const(64));
+        // Not used as an address.
+        assert_eq!(code[5], Instruction::I32Const(16));
+
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(false, did_change);
+    }
+
+    #[test]
+    fn with_config_relocations() {
+        let mut conf = HashMap::new();
+        conf.insert("relocations".to_string(), "16:4:64, 100:8:0".to_string());
+        let translator = RelocateData::with_config(&conf).unwrap();
+        assert_eq!(translator.map.relocate(102), Some(2));
+
+        conf.insert("relocations".to_string(), "16:4".to_string());
+        assert!(RelocateData::with_config(&conf).is_err());
+    }
+}
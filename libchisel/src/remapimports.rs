@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use parity_wasm::elements::{ImportEntry, ImportSection, Module};
@@ -9,11 +10,33 @@ use super::{
 pub struct RemapImports<'a> {
     /// A list of import sets to remap.
     interfaces: Vec<ImportInterface<'a>>,
+    /// Whether imports are remapped to or from their namespaced form.
+    direction: Direction,
+}
+
+/// Enum representing the direction in which RemapImports rewrites imports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    /// Remap prefixed `env` imports, e.g. `env.ethereum_useGas`, to their namespace, e.g.
+    /// `ethereum.useGas`.
+    Forward,
+    /// Flatten namespaced imports back into prefixed `env` imports.
+    Reverse,
 }
 
 /// A pair containing a list of imports for RemapImports to remap against, and an optional string with which all
 /// imports are expected to be prefixed.
-pub struct ImportInterface<'a>(ImportList<'a>, Option<&'a str>);
+pub struct ImportInterface<'a> {
+    imports: InterfaceImports<'a>,
+    prefix: Option<Cow<'a, str>>,
+}
+
+/// The imports of an interface: either a compiled-in list, or a namespace and the fields it
+/// provides, given at runtime.
+enum InterfaceImports<'a> {
+    List(ImportList<'a>),
+    Fields(String, Vec<String>),
+}
 
 impl<'a> ChiselModule<'a> for RemapImports<'a> {
     type ObjectReference = &'a dyn ModuleTranslator;
@@ -35,51 +58,106 @@ impl<'a> ChiselModule<'a> for RemapImports<'a> {
     }
 
     fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
-        if let Some(preset) = config.get("preset") {
-            RemapImports::with_preset(preset)
-        } else {
-            Err(ModuleError::NotSupported)
+        // The prefix of each preset interface can be overridden with `<preset>_prefix`, e.g.
+        // `debug_prefix=dbg_` remaps `env.dbg_print32` to `debug.print32`.
+        let mut interfaces = match config.get("preset") {
+            Some(preset) => preset_names(preset)
+                .iter()
+                .map(|name| {
+                    let interface = ImportInterface::with_preset(name)?;
+                    Ok(match config.get(&format!("{}_prefix", name)) {
+                        Some(prefix) => interface.with_prefix(prefix.trim()),
+                        None => interface,
+                    })
+                })
+                .collect::<Result<Vec<ImportInterface>, ModuleError>>()?,
+            None => Vec::new(),
+        };
+
+        // A custom interface is described by its namespace, the fields it provides and the
+        // prefix used for them in `env`.
+        if let Some(namespace) = config.get("namespace") {
+            let fields = config.get("fields").ok_or(ModuleError::NotSupported)?;
+            interfaces.push(ImportInterface::with_fields(
+                namespace.trim().to_string(),
+                config.get("prefix").map(|prefix| prefix.trim().to_string()),
+                fields
+                    .split(',')
+                    .map(|field| field.trim().to_string())
+                    .filter(|field| !field.is_empty())
+                    .collect(),
+            ));
+        }
+
+        if interfaces.is_empty() {
+            return Err(ModuleError::NotSupported);
+        }
+
+        let remapimports = RemapImports::new(interfaces);
+        match config.get("direction").map(|d| d.as_str()) {
+            None | Some("forward") => Ok(remapimports),
+            Some("reverse") => Ok(remapimports.reversed()),
+            Some(_) => Err(ModuleError::NotSupported),
         }
     }
 }
 
 impl<'a> ModulePreset for RemapImports<'a> {
     fn with_preset(preset: &str) -> Result<Self, ModuleError> {
-        let mut interface_set: Vec<ImportInterface> = Vec::new();
-
-        // Accept a comma-separated list of presets.
-        let presets: String = preset
-            .chars()
-            .filter(|c| *c != '_' && *c != ' ' && *c != '\n' && *c != '\t')
-            .collect();
-        for preset_individual in presets.split(',') {
-            match preset_individual {
-                "ewasm" => interface_set.push(ImportInterface::new(
-                    ImportList::with_preset("ewasm")?,
-                    Some("ethereum_"),
-                )),
-                "eth2" => interface_set.push(ImportInterface::new(
-                    ImportList::with_preset("eth2")?,
-                    Some("eth2_"),
-                )),
-                "debug" => interface_set.push(ImportInterface::new(
-                    ImportList::with_preset("debug")?,
-                    Some("debug_"),
-                )),
-                "bignum" => interface_set.push(ImportInterface::new(
-                    ImportList::with_preset("bignum")?,
-                    Some("bignum_"),
-                )),
-                _ => return Err(ModuleError::NotSupported),
-            }
-        }
+        let interface_set = preset_names(preset)
+            .iter()
+            .map(|name| ImportInterface::with_preset(name))
+            .collect::<Result<Vec<ImportInterface>, ModuleError>>()?;
 
         Ok(RemapImports {
             interfaces: interface_set,
+            direction: Direction::Forward,
         })
     }
 }
 
+impl<'a> ModulePreset for ImportInterface<'a> {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            "ewasm" => Ok(ImportInterface::new(
+                ImportList::with_preset("ewasm")?,
+                Some("ethereum_"),
+            )),
+            "eth2" => Ok(ImportInterface::new(
+                ImportList::with_preset("eth2")?,
+                Some("eth2_"),
+            )),
+            "eth2_v2" => Ok(ImportInterface::new(
+                ImportList::with_preset("eth2_v2")?,
+                Some("eth2_"),
+            )),
+            "debug" => Ok(ImportInterface::new(
+                ImportList::with_preset("debug")?,
+                Some("debug_"),
+            )),
+            "bignum" => Ok(ImportInterface::new(
+                ImportList::with_preset("bignum")?,
+                Some("bignum_"),
+            )),
+            // pwasm imports live in `env` themselves, so only the prefix is removed.
+            "pwasm" => Ok(ImportInterface::new(
+                ImportList::with_preset("pwasm")?,
+                Some("pwasm_"),
+            )),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+/// Splits a comma-separated list of presets into their names.
+fn preset_names(preset: &str) -> Vec<String> {
+    let presets: String = preset
+        .chars()
+        .filter(|c| *c != ' ' && *c != '\n' && *c != '\t')
+        .collect();
+    presets.split(',').map(|name| name.to_string()).collect()
+}
+
 impl<'a> ModuleTranslator for RemapImports<'a> {
     fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
         let mut was_mutated = false;
@@ -126,24 +204,80 @@ impl<'a> ModuleTranslator for RemapImports<'a> {
 
 impl<'a> ImportInterface<'a> {
     pub fn new(imports: ImportList<'a>, prefix: Option<&'a str>) -> Self {
-        ImportInterface(imports, prefix)
+        ImportInterface {
+            imports: InterfaceImports::List(imports),
+            prefix: prefix.map(Cow::Borrowed),
+        }
+    }
+
+    /// Builds an interface without a compiled-in import list: each of `fields` is imported from
+    /// `namespace`.
+    pub fn with_fields(namespace: String, prefix: Option<String>, fields: Vec<String>) -> Self {
+        ImportInterface {
+            imports: InterfaceImports::Fields(namespace, fields),
+            prefix: prefix.map(Cow::Owned),
+        }
+    }
+
+    /// Returns the same interface, expecting imports to carry `prefix` instead. An empty prefix
+    /// matches the fields as they are.
+    pub fn with_prefix(self, prefix: &str) -> Self {
+        ImportInterface {
+            imports: self.imports,
+            prefix: if prefix.is_empty() {
+                None
+            } else {
+                Some(Cow::Owned(prefix.to_string()))
+            },
+        }
     }
 
     pub fn prefix(&self) -> Option<&str> {
-        self.1
+        self.prefix.as_deref()
+    }
+
+    /// Returns the compiled-in import list, or None if the interface was built from fields.
+    pub fn imports(&self) -> Option<&ImportList<'a>> {
+        match &self.imports {
+            InterfaceImports::List(list) => Some(list),
+            InterfaceImports::Fields(_, _) => None,
+        }
     }
 
-    pub fn imports(&self) -> &ImportList<'a> {
-        &self.0
+    /// Returns the namespace and field of the interface import with the given field.
+    fn lookup(&self, field: &str) -> Option<(&str, &str)> {
+        match &self.imports {
+            InterfaceImports::List(list) => list
+                .lookup_by_field(field)
+                .map(|import| (import.module(), import.field())),
+            InterfaceImports::Fields(namespace, fields) => fields
+                .iter()
+                .find(|f| *f == field)
+                .map(|f| (namespace.as_str(), f.as_str())),
+        }
     }
 }
 
 impl<'a> RemapImports<'a> {
-    // NOTE: 'new()' is currently unused in the library but useful in the future.
-    #[allow(dead_code)]
     fn new(interfaces: Vec<ImportInterface<'a>>) -> Self {
         RemapImports {
             interfaces: interfaces,
+            direction: Direction::Forward,
+        }
+    }
+
+    /// Returns the same remapper, rewriting imports in the opposite direction. In reverse,
+    /// namespaced imports listed in an interface are flattened into `env` imports named by the
+    /// interface prefix followed by the field, e.g. `ethereum.useGas` becomes
+    /// `env.ethereum_useGas`.
+    pub fn reversed(self) -> Self {
+        let direction = match self.direction {
+            Direction::Forward => Direction::Reverse,
+            Direction::Reverse => Direction::Forward,
+        };
+        RemapImports {
+            interfaces: self.interfaces,
+            direction,
         }
     }
 
@@ -155,19 +289,20 @@ impl<'a> RemapImports<'a> {
         mutflag: &mut bool,
         interface: &ImportInterface,
     ) -> ImportEntry {
+        if self.direction == Direction::Reverse {
+            return self.flatten_from_list(entry, mutflag, interface);
+        }
+
         match interface.prefix() {
             Some(prefix) => {
                 let prefix_len = prefix.len();
                 if entry.field().len() > prefix_len && prefix == &entry.field()[..prefix_len] {
                     // Look for a matching remappable import and mutate if found.
-                    if let Some(import) = interface
-                        .imports()
-                        .lookup_by_field(&entry.field()[prefix_len..])
-                    {
+                    if let Some((module, field)) = interface.lookup(&entry.field()[prefix_len..]) {
                         *mutflag = true;
                         return ImportEntry::new(
-                            import.module().into(),
-                            import.field().into(),
+                            module.into(),
+                            field.into(),
                             entry.external().clone(),
                         );
                     }
@@ -175,19 +310,36 @@ impl<'a> RemapImports<'a> {
                 entry.clone()
             }
             None => {
-                if let Some(import) = interface.imports().lookup_by_field(&entry.field()) {
+                if let Some((module, field)) = interface.lookup(&entry.field()) {
                     *mutflag = true;
-                    ImportEntry::new(
-                        import.module().into(),
-                        import.field().into(),
-                        entry.external().clone(),
-                    )
+                    ImportEntry::new(module.into(), field.into(), entry.external().clone())
                 } else {
                     entry.clone()
                 }
             }
         }
     }
+
+    /// Takes an import entry and returns either the same entry or, if it is listed in the
+    /// interface, its flattened `env` form. Sets the mutation flag if was flattened.
+    fn flatten_from_list(
+        &self,
+        entry: &ImportEntry,
+        mutflag: &mut bool,
+        interface: &ImportInterface,
+    ) -> ImportEntry {
+        if let Some((module, _)) = interface.lookup(entry.field()) {
+            if module == entry.module() {
+                *mutflag = true;
+                return ImportEntry::new(
+                    "env".into(),
+                    format!("{}{}", interface.prefix().unwrap_or(""), entry.field()),
+                    *entry.external(),
+                );
+            }
+        }
+        entry.clone()
+    }
 }
 
 #[cfg(test)]
@@ -416,4 +568,240 @@ mod tests {
 
         assert_eq!(verifier.validate(&new), Ok(true));
     }
+
+    #[test]
+    fn remap_reverse_roundtrip() {
+        // wast:
+        // (module
+        //   (type (;0;) (func (result i64)))
+        //   (type (;1;) (func (param i32 i32 i32)))
+        //   (type (;2;) (func (param i32)))
+        //   (import "env" "ethereum_getGasLeft" (func (;0;) (type 0)))
+        //   (import "env" "bignum_mul256" (func (;1;) (type 1)))
+        //   (import "env" "debug_printStorage" (func (;2;) (type 2)))
+        //   (memory 1)
+        //   (func $main)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        // )
+
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x12, 0x04, 0x60, 0x00, 0x01,
+            0x7e, 0x60, 0x03, 0x7f, 0x7f, 0x7f, 0x00, 0x60, 0x01, 0x7f, 0x00, 0x60, 0x00, 0x00,
+            0x02, 0x48, 0x03, 0x03, 0x65, 0x6e, 0x76, 0x13, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65,
+            0x75, 0x6d, 0x5f, 0x67, 0x65, 0x74, 0x47, 0x61, 0x73, 0x4c, 0x65, 0x66, 0x74, 0x00,
+            0x00, 0x03, 0x65, 0x6e, 0x76, 0x0d, 0x62, 0x69, 0x67, 0x6e, 0x75, 0x6d, 0x5f, 0x6d,
+            0x75, 0x6c, 0x32, 0x35, 0x36, 0x00, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x12, 0x64, 0x65,
+            0x62, 0x75, 0x67, 0x5f, 0x70, 0x72, 0x69, 0x6e, 0x74, 0x53, 0x74, 0x6f, 0x72, 0x61,
+            0x67, 0x65, 0x00, 0x02, 0x03, 0x02, 0x01, 0x03, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07,
+            0x11, 0x02, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x03, 0x06, 0x6d, 0x65, 0x6d, 0x6f,
+            0x72, 0x79, 0x02, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        let remapper = RemapImports::with_preset("ewasm, bignum, debug").unwrap();
+        assert!(remapper.translate_inplace(&mut module).unwrap());
+        let imports = module.import_section().unwrap().entries();
+        assert_eq!(imports[0].module(), "ethereum");
+        assert_eq!(imports[0].field(), "getGasLeft");
+
+        let reverse = remapper.reversed();
+        assert!(reverse.translate_inplace(&mut module).unwrap());
+        assert_eq!(module.to_bytes().unwrap(), wasm);
+
+        // Flattened imports are left alone by the reverse remapper.
+        let module = Module::from_bytes(&wasm).unwrap();
+        assert!(reverse.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn remap_pwasm_roundtrip() {
+        // wast:
+        // (module
+        //   (import "env" "pwasm_ret" (func $ret (param i32 i32)))
+        //   (import "env" "pwasm_sender" (func $sender (param i32)))
+        //   (export "call" (func $call))
+        //   (func $call)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0d, 0x03, 0x60, 0x02, 0x7f,
+            0x7f, 0x00, 0x60, 0x01, 0x7f, 0x00, 0x60, 0x00, 0x00, 0x02, 0x24, 0x02, 0x03, 0x65,
+            0x6e, 0x76, 0x09, 0x70, 0x77, 0x61, 0x73, 0x6d, 0x5f, 0x72, 0x65, 0x74, 0x00, 0x00,
+            0x03, 0x65, 0x6e, 0x76, 0x0c, 0x70, 0x77, 0x61, 0x73, 0x6d, 0x5f, 0x73, 0x65, 0x6e,
+            0x64, 0x65, 0x72, 0x00, 0x01, 0x03, 0x02, 0x01, 0x02, 0x07, 0x08, 0x01, 0x04, 0x63,
+            0x61, 0x6c, 0x6c, 0x00, 0x02, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        let remapper = RemapImports::with_preset("pwasm").unwrap();
+        assert!(remapper.translate_inplace(&mut module).unwrap());
+        let imports = module.import_section().unwrap().entries();
+        assert_eq!(imports[0].module(), "env");
+        assert_eq!(imports[0].field(), "ret");
+        assert_eq!(imports[1].module(), "env");
+        assert_eq!(imports[1].field(), "sender");
+
+        let verifier = VerifyImports::with_preset("pwasm").unwrap();
+        assert_eq!(verifier.validate(&module), Ok(true));
+
+        let reverse = remapper.reversed();
+        assert!(reverse.translate_inplace(&mut module).unwrap());
+        assert_eq!(module.to_bytes().unwrap(), wasm);
+    }
+
+    #[test]
+    fn remap_eth2_v2() {
+        // wast:
+        // (module
+        //   (import "env" "eth2_savePostStateRoot" (func $savePostStateRoot (param i32 i32)))
+        //   (export "main" (func $main))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x09, 0x02, 0x60, 0x02, 0x7f,
+            0x7f, 0x00, 0x60, 0x00, 0x00, 0x02, 0x1e, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x16, 0x65,
+            0x74, 0x68, 0x32, 0x5f, 0x73, 0x61, 0x76, 0x65, 0x50, 0x6f, 0x73, 0x74, 0x53, 0x74,
+            0x61, 0x74, 0x65, 0x52, 0x6f, 0x6f, 0x74, 0x00, 0x00, 0x03, 0x02, 0x01, 0x01, 0x07,
+            0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a, 0x04, 0x01, 0x02, 0x00,
+            0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let remapper = RemapImports::with_preset("eth2_v2").unwrap();
+        assert!(remapper.translate_inplace(&mut module).unwrap());
+        let imports = module.import_section().unwrap().entries();
+        assert_eq!(imports[0].module(), "eth2");
+        assert_eq!(imports[0].field(), "savePostStateRoot");
+
+        let verifier = VerifyImports::with_preset("eth2_v2").unwrap();
+        assert_eq!(verifier.validate(&module), Ok(true));
+        let verifier = VerifyImports::with_preset("eth2").unwrap();
+        assert_eq!(verifier.validate(&module), Ok(false));
+    }
+
+    #[test]
+    fn remap_reverse_with_config() {
+        // wast:
+        // (module
+        //   (import "ethereum" "useGas" (func (param i64)))
+        // )
+        let input = FromHex::from_hex(
+            "
+            0061736d0100000001050160017e0002130108657468657265756d067573
+            654761730000
+        ",
+        )
+        .unwrap();
+        let mut module = Module::from_bytes(&input).unwrap();
+
+        let mut conf = HashMap::new();
+        conf.insert("preset".to_string(), "ewasm".to_string());
+        conf.insert("direction".to_string(), "reverse".to_string());
+        let did_change = RemapImports::with_config(&conf)
+            .unwrap()
+            .translate_inplace(&mut module)
+            .unwrap();
+        assert!(did_change);
+
+        let import = &module.import_section().unwrap().entries()[0];
+        assert_eq!(import.module(), "env");
+        assert_eq!(import.field(), "ethereum_useGas");
+
+        conf.insert("direction".to_string(), "sideways".to_string());
+        assert!(RemapImports::with_config(&conf).is_err());
+    }
+
+    #[test]
+    fn remap_custom_interface() {
+        // wast:
+        // (module
+        //   (import "env" "host_foo" (func))
+        //   (import "env" "host_bar" (func (param i32)))
+        //   (import "env" "other_foo" (func))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x00, 0x00,
+            0x60, 0x01, 0x7f, 0x00, 0x02, 0x2f, 0x03, 0x03, 0x65, 0x6e, 0x76, 0x08, 0x68, 0x6f,
+            0x73, 0x74, 0x5f, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x03, 0x65, 0x6e, 0x76, 0x08, 0x68,
+            0x6f, 0x73, 0x74, 0x5f, 0x62, 0x61, 0x72, 0x00, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x09,
+            0x6f, 0x74, 0x68, 0x65, 0x72, 0x5f, 0x66, 0x6f, 0x6f, 0x00, 0x00,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        let mut conf = HashMap::new();
+        conf.insert("namespace".to_string(), "host".to_string());
+        conf.insert("prefix".to_string(), "host_".to_string());
+        conf.insert("fields".to_string(), "foo, baz".to_string());
+        let remapper = RemapImports::with_config(&conf).unwrap();
+        assert!(remapper.translate_inplace(&mut module).unwrap());
+
+        let imports = module.import_section().unwrap().entries();
+        assert_eq!(imports[0].module(), "host");
+        assert_eq!(imports[0].field(), "foo");
+        // Not one of the listed fields.
+        assert_eq!(imports[1].module(), "env");
+        assert_eq!(imports[1].field(), "host_bar");
+        // Not carrying the prefix.
+        assert_eq!(imports[2].module(), "env");
+        assert_eq!(imports[2].field(), "other_foo");
+
+        let reverse = remapper.reversed();
+        assert!(reverse.translate_inplace(&mut module).unwrap());
+        assert_eq!(module.to_bytes().unwrap(), wasm);
+    }
+
+    #[test]
+    fn remap_preset_custom_prefix() {
+        // wast:
+        // (module
+        //   (import "env" "dbg_print32" (func (param i32)))
+        //   (import "env" "debug_print64" (func (param i64)))
+        //   (import "env" "ethereum_useGas" (func (param i64)))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x09, 0x02, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x01, 0x7e, 0x00, 0x02, 0x3d, 0x03, 0x03, 0x65, 0x6e, 0x76, 0x0b, 0x64,
+            0x62, 0x67, 0x5f, 0x70, 0x72, 0x69, 0x6e, 0x74, 0x33, 0x32, 0x00, 0x00, 0x03, 0x65,
+            0x6e, 0x76, 0x0d, 0x64, 0x65, 0x62, 0x75, 0x67, 0x5f, 0x70, 0x72, 0x69, 0x6e, 0x74,
+            0x36, 0x34, 0x00, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x0f, 0x65, 0x74, 0x68, 0x65, 0x72,
+            0x65, 0x75, 0x6d, 0x5f, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x01,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        let mut conf = HashMap::new();
+        conf.insert("preset".to_string(), "ewasm, debug".to_string());
+        conf.insert("debug_prefix".to_string(), "dbg_".to_string());
+        let remapper = RemapImports::with_config(&conf).unwrap();
+        assert!(remapper.translate_inplace(&mut module).unwrap());
+
+        let imports = module.import_section().unwrap().entries();
+        assert_eq!(imports[0].module(), "debug");
+        assert_eq!(imports[0].field(), "print32");
+        // The default prefix no longer applies.
+        assert_eq!(imports[1].module(), "env");
+        assert_eq!(imports[1].field(), "debug_print64");
+        assert_eq!(imports[2].module(), "ethereum");
+        assert_eq!(imports[2].field(), "useGas");
+    }
+
+    #[test]
+    fn custom_interface_missing_fields() {
+        let mut conf = HashMap::new();
+        conf.insert("namespace".to_string(), "host".to_string());
+        conf.insert("prefix".to_string(), "host_".to_string());
+        assert!(RemapImports::with_config(&conf).is_err());
+
+        conf.insert("fields".to_string(), "foo".to_string());
+        let remapper = RemapImports::with_config(&conf).unwrap();
+        assert_eq!(remapper.interfaces.len(), 1);
+        assert!(remapper.interfaces[0].imports().is_none());
+
+        conf.insert("preset".to_string(), "ewasm".to_string());
+        let remapper = RemapImports::with_config(&conf).unwrap();
+        assert_eq!(remapper.interfaces.len(), 2);
+    }
 }
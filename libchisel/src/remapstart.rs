@@ -4,13 +4,17 @@ use parity_wasm::elements::{ExportEntry, ExportSection, Internal, Module, Sectio
 
 use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleTranslator};
 
-pub struct RemapStart;
+/// Struct on which ModuleTranslator is implemented.
+/// Exports the start function as `main`. The start section is removed unless `keep_start` is set.
+pub struct RemapStart {
+    keep_start: bool,
+}
 
 impl ModulePreset for RemapStart {
     fn with_preset(preset: &str) -> Result<Self, ModuleError> {
         match preset {
             // TODO refactor this later
-            "ewasm" => Ok(RemapStart {}),
+            "ewasm" => Ok(RemapStart { keep_start: false }),
             _ => Err(ModuleError::NotSupported),
         }
     }
@@ -32,27 +36,31 @@ impl<'a> ChiselModule<'a> for RemapStart {
     }
 
     fn with_defaults() -> Result<Self, ModuleError> {
-        Ok(RemapStart {})
+        Ok(RemapStart { keep_start: false })
     }
 
-    // FIXME: drop this, no need for preset here
+    // FIXME: drop the preset, it selects the default behaviour
     fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
         if let Some(preset) = config.get("preset") {
-            RemapStart::with_preset(preset)
-        } else {
-            Err(ModuleError::NotSupported)
+            RemapStart::with_preset(preset)?;
         }
+        let keep_start = match config.get("keep_start").map(|value| value.as_str()) {
+            None | Some("false") => false,
+            Some("true") => true,
+            Some(_) => return Err(ModuleError::NotSupported),
+        };
+        Ok(RemapStart { keep_start })
     }
 }
 
 impl ModuleTranslator for RemapStart {
     fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
-        Ok(remap_start(module))
+        Ok(remap_start(module, self.keep_start))
     }
 
     fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
         let mut ret = module.clone();
-        if remap_start(&mut ret) {
+        if remap_start(&mut ret, self.keep_start) {
             Ok(Some(ret))
         } else {
             Ok(None)
@@ -60,8 +68,9 @@ impl ModuleTranslator for RemapStart {
     }
 }
 
-/// Replace an exported function with another function, or export if unexported.
-fn remap_or_export_main(module: &mut Module, export_name: &str, func_idx: u32) {
+/// Replace an exported function with another function, or export if unexported. Returns false if
+/// the function was already exported under that name.
+fn remap_or_export_main(module: &mut Module, export_name: &str, func_idx: u32) -> bool {
     let new_func_export = ExportEntry::new(export_name.to_string(), Internal::Function(func_idx));
 
     if let Some(export_section) = module.export_section_mut() {
@@ -72,6 +81,9 @@ fn remap_or_export_main(module: &mut Module, export_name: &str, func_idx: u32) {
             .iter_mut()
             .position(|e| e.field() == export_name)
         {
+            if export_section[main_export_loc] == new_func_export {
+                return false;
+            }
             export_section[main_export_loc] = new_func_export;
         } else {
             export_section.push(new_func_export);
@@ -85,13 +97,17 @@ fn remap_or_export_main(module: &mut Module, export_name: &str, func_idx: u32) {
             .insert_section(new_export_section)
             .expect("insert_section should not fail");
     }
+    true
 }
 
-fn remap_start(module: &mut Module) -> bool {
+fn remap_start(module: &mut Module, keep_start: bool) -> bool {
     if let Some(start_func_idx) = module.start_section() {
         // Look for an export "main". If found, replace it with an export of the function to
         // which the start section points.
-        remap_or_export_main(module, "main", start_func_idx);
+        let remapped = remap_or_export_main(module, "main", start_func_idx);
+        if keep_start {
+            return remapped;
+        }
 
         // Remove the start section, leaving the "main" export as the entry point.
         module.clear_start_section();
@@ -305,4 +321,56 @@ mod tests {
             .find(|e| e.field() == "main")
             .is_some());
     }
+
+    #[test]
+    fn keep_start_section() {
+        //wat:
+        //(module
+        //    (import "env" "ethereum_useGas" (func (param i64)))
+        //    (memory 1)
+        //    (export "main" (func $main))
+        //    (export "memory" (memory 0))
+        //    (func $main2)
+        //    (func $main)
+        //    (start $main2)
+        //)
+
+        let wasm: Vec<u8> = FromHex::from_hex(
+            "0061736d0100000001080260017e0060
+000002170103656e760f657468657265756d5f75736547617300000303020101050301000107110
+2046d61696e0001066d656d6f727902000801020a070202000b02000b0020046e616d65010e0201
+046d61696e02056d61696e320209030001000001000200",
+        )
+        .unwrap();
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let start_idx = module
+            .start_section()
+            .expect("Module missing start function");
+
+        let mut conf = HashMap::new();
+        conf.insert("keep_start".to_string(), "true".to_string());
+        let remapper = RemapStart::with_config(&conf).unwrap();
+
+        let res = remapper.translate_inplace(&mut module).unwrap();
+        assert!(res, "module was not modified");
+        assert_eq!(module.start_section(), Some(start_idx));
+        assert!(module
+            .export_section()
+            .expect("Module missing export section")
+            .entries()
+            .iter()
+            .any(|e| e.field() == "main" && *e.internal() == Internal::Function(start_idx)));
+
+        // The start function is already exported as main now.
+        let res = remapper.translate_inplace(&mut module).unwrap();
+        assert!(!res, "module was modified");
+    }
+
+    #[test]
+    fn invalid_keep_start() {
+        let mut conf = HashMap::new();
+        conf.insert("keep_start".to_string(), "yes".to_string());
+        assert!(RemapStart::with_config(&conf).is_err());
+    }
 }
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Removes all `nop` instructions from function bodies.
+pub struct RemoveNops {}
+
+impl<'a> ChiselModule<'a> for RemoveNops {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "removenops".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(RemoveNops {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for RemoveNops {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(remove_nops(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if remove_nops(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn remove_nops(module: &mut Module) -> bool {
+    let mut modified = false;
+    if let Some(code) = module.code_section_mut() {
+        for body in code.bodies_mut() {
+            let instructions = body.code_mut().elements_mut();
+            let len_before = instructions.len();
+            instructions.retain(|instruction| *instruction != Instruction::Nop);
+            modified |= instructions.len() != len_before;
+        }
+    }
+    modified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_nops(module: &Module) -> usize {
+        module
+            .code_section()
+            .unwrap()
+            .bodies()
+            .iter()
+            .flat_map(|body| body.code().elements())
+            .filter(|instruction| **instruction == Instruction::Nop)
+            .count()
+    }
+
+    #[test]
+    fn nops_removed() {
+        // wast:
+        // (module
+        //   (func (export "main")
+        //     nop
+        //     i32.const 1
+        //     nop
+        //     drop
+        //     block
+        //       nop
+        //     end
+        //     nop)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x0e, 0x01, 0x0c, 0x00, 0x01, 0x41, 0x01, 0x01, 0x1a, 0x02, 0x40, 0x01, 0x0b,
+            0x01, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        assert_eq!(count_nops(&module), 4);
+
+        let translator = RemoveNops::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(count_nops(&module), 0);
+        assert_eq!(
+            module.code_section().unwrap().bodies()[0]
+                .code()
+                .elements()
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn no_nops() {
+        // wast:
+        // (module
+        //   (func (export "main")
+        //     i32.const 1
+        //     drop)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x07, 0x01, 0x05, 0x00, 0x41, 0x01, 0x1a, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = RemoveNops::with_defaults().unwrap();
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
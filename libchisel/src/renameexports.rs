@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Renames exports according to a map from old to new field names, leaving the exported
+/// function, global, memory or table untouched. Renames are applied simultaneously, so two
+/// exports may swap names.
+pub struct RenameExports(HashMap<String, String>);
+
+impl RenameExports {
+    pub fn new(renames: HashMap<String, String>) -> Self {
+        RenameExports(renames)
+    }
+}
+
+impl<'a> ChiselModule<'a> for RenameExports {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "renameexports".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        // `from` and `to` are comma-separated lists of names, paired by position.
+        let (from, to) = match (config.get("from"), config.get("to")) {
+            (Some(from), Some(to)) => (parse_names(from), parse_names(to)),
+            _ => return Err(ModuleError::NotSupported),
+        };
+        if from.len() != to.len() {
+            return Err(ModuleError::Custom(format!(
+                "'from' lists {} names but 'to' lists {}",
+                from.len(),
+                to.len()
+            )));
+        }
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = from.iter().find(|name| !seen.insert(name.as_str())) {
+            return Err(ModuleError::Custom(format!(
+                "'from' lists '{}' more than once",
+                duplicate
+            )));
+        }
+        Ok(RenameExports(from.into_iter().zip(to).collect()))
+    }
+}
+
+fn parse_names(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+impl ModuleTranslator for RenameExports {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        let exports = match module.export_section_mut() {
+            Some(section) => section.entries_mut(),
+            None => return Ok(false),
+        };
+
+        let renamed: Vec<String> = exports
+            .iter()
+            .map(|entry| {
+                self.0
+                    .get(entry.field())
+                    .cloned()
+                    .unwrap_or_else(|| entry.field().to_string())
+            })
+            .collect();
+
+        // Renaming onto a name which is still in use would produce an invalid module, so the
+        // module is left untouched.
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = renamed.iter().find(|name| !seen.insert(name.as_str())) {
+            return Err(ModuleError::Custom(format!(
+                "duplicate export '{}' after renaming",
+                duplicate
+            )));
+        }
+
+        let mut modified = false;
+        for (entry, new_name) in exports.iter_mut().zip(renamed) {
+            if new_name != entry.field() {
+                *entry.field_mut() = new_name;
+                modified = true;
+            }
+        }
+
+        Ok(modified)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if self.translate_inplace(&mut ret)? {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::Internal;
+
+    use super::*;
+
+    fn export_names(module: &Module) -> Vec<(String, Internal)> {
+        module
+            .export_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| (entry.field().to_string(), *entry.internal()))
+            .collect()
+    }
+
+    #[test]
+    fn call_renamed_to_main() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func $helper)
+        //   (func $call)
+        //   (export "_call" (func $call))
+        //   (export "memory" (memory 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x12, 0x02, 0x05,
+            0x5f, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x00, 0x16, 0x04,
+            0x6e, 0x61, 0x6d, 0x65, 0x01, 0x0f, 0x02, 0x00, 0x06, 0x68, 0x65, 0x6c, 0x70, 0x65,
+            0x72, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let mut renames = HashMap::new();
+        renames.insert("_call".to_string(), "main".to_string());
+        let translator = RenameExports::new(renames);
+
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(
+            export_names(&module),
+            vec![
+                ("main".to_string(), Internal::Function(1)),
+                ("memory".to_string(), Internal::Memory(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_matching_export() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func $helper)
+        //   (func $call)
+        //   (export "_call" (func $call))
+        //   (export "memory" (memory 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x12, 0x02, 0x05,
+            0x5f, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x00, 0x16, 0x04,
+            0x6e, 0x61, 0x6d, 0x65, 0x01, 0x0f, 0x02, 0x00, 0x06, 0x68, 0x65, 0x6c, 0x70, 0x65,
+            0x72, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut config = HashMap::new();
+        config.insert("from".to_string(), "deploy".to_string());
+        config.insert("to".to_string(), "main".to_string());
+        let translator = RenameExports::with_config(&config).unwrap();
+
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn swap_with_config() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func $helper)
+        //   (func $call)
+        //   (export "_call" (func $call))
+        //   (export "memory" (memory 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x12, 0x02, 0x05,
+            0x5f, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x00, 0x16, 0x04,
+            0x6e, 0x61, 0x6d, 0x65, 0x01, 0x0f, 0x02, 0x00, 0x06, 0x68, 0x65, 0x6c, 0x70, 0x65,
+            0x72, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let mut config = HashMap::new();
+        config.insert("from".to_string(), "_call, memory".to_string());
+        config.insert("to".to_string(), "memory, _call".to_string());
+        let translator = RenameExports::with_config(&config).unwrap();
+
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(
+            export_names(&module),
+            vec![
+                ("memory".to_string(), Internal::Function(1)),
+                ("_call".to_string(), Internal::Memory(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_onto_existing_export() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func $helper)
+        //   (func $call)
+        //   (export "_call" (func $call))
+        //   (export "memory" (memory 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x12, 0x02, 0x05,
+            0x5f, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x00, 0x16, 0x04,
+            0x6e, 0x61, 0x6d, 0x65, 0x01, 0x0f, 0x02, 0x00, 0x06, 0x68, 0x65, 0x6c, 0x70, 0x65,
+            0x72, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let mut renames = HashMap::new();
+        renames.insert("_call".to_string(), "memory".to_string());
+        let translator = RenameExports::new(renames);
+
+        assert!(translator.translate_inplace(&mut module).is_err());
+        // The module is left as it was.
+        assert_eq!(
+            export_names(&module),
+            vec![
+                ("_call".to_string(), Internal::Function(1)),
+                ("memory".to_string(), Internal::Memory(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn mismatched_config_lists() {
+        let mut config = HashMap::new();
+        config.insert("from".to_string(), "_call,deploy".to_string());
+        config.insert("to".to_string(), "main".to_string());
+        assert!(RenameExports::with_config(&config).is_err());
+    }
+
+    #[test]
+    fn repeated_from_name() {
+        let mut config = HashMap::new();
+        config.insert("from".to_string(), "_call,_call".to_string());
+        config.insert("to".to_string(), "main,deploy".to_string());
+        assert!(RenameExports::with_config(&config).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{
+    funcindex::remap_start_after_removal, ChiselModule, ModuleError, ModuleKind, ModuleTranslator,
+};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Renumbers the start function reference after functions were removed from the module, and
+/// drops a start section which points outside of the function index space.
+pub struct RenumberStart {
+    /// Indices of the functions that were removed, as they were before removal.
+    removed: Vec<u32>,
+}
+
+impl RenumberStart {
+    pub fn new(removed: Vec<u32>) -> Self {
+        RenumberStart { removed }
+    }
+}
+
+impl<'a> ChiselModule<'a> for RenumberStart {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "renumberstart".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(RenumberStart {
+            removed: Vec::new(),
+        })
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        let removed = if let Some(removed) = config.get("removed") {
+            removed
+                .split(',')
+                .map(|idx| idx.trim().parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()?
+        } else {
+            Vec::new()
+        };
+        Ok(RenumberStart { removed })
+    }
+}
+
+impl RenumberStart {
+    fn renumber_start(&self, module: &mut Module) -> bool {
+        let mut modified = remap_start_after_removal(module, &self.removed);
+
+        // A start index past the end of the function index space cannot be recovered.
+        if let Some(start) = module.start_section() {
+            if start as usize >= module.functions_space() {
+                module.clear_start_section();
+                modified = true;
+            }
+        }
+        modified
+    }
+}
+
+impl ModuleTranslator for RenumberStart {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(self.renumber_start(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if self.renumber_start(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_renumbered_after_removal() {
+        // wast:
+        // (module
+        //   (func $a)
+        //   (func $b)
+        //   (func $start)
+        //   (start $start)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x04, 0x03, 0x00, 0x00, 0x00, 0x08, 0x01, 0x02, 0x0a, 0x0a, 0x03, 0x02, 0x00,
+            0x0b, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        // Remove $a, which comes before the start function.
+        module
+            .function_section_mut()
+            .unwrap()
+            .entries_mut()
+            .remove(0);
+        module.code_section_mut().unwrap().bodies_mut().remove(0);
+
+        let mut config = HashMap::new();
+        config.insert("removed".to_string(), "0".to_string());
+        let translator = RenumberStart::with_config(&config).unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(module.start_section(), Some(1));
+    }
+
+    #[test]
+    fn start_unchanged() {
+        // wast:
+        // (module
+        //   (func $a)
+        //   (func $b)
+        //   (func $start)
+        //   (start $start)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x04, 0x03, 0x00, 0x00, 0x00, 0x08, 0x01, 0x02, 0x0a, 0x0a, 0x03, 0x02, 0x00,
+            0x0b, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = RenumberStart::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(false, did_change);
+        assert_eq!(module.start_section(), Some(2));
+    }
+
+    #[test]
+    fn stale_start_dropped() {
+        // wast:
+        // (module
+        //   (func $a)
+        //   (func $b)
+        //   (func $start)
+        //   (start $start)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x04, 0x03, 0x00, 0x00, 0x00, 0x08, 0x01, 0x02, 0x0a, 0x0a, 0x03, 0x02, 0x00,
+            0x0b, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+
+        // Remove a function without renumbering, leaving the start index out of range.
+        module
+            .function_section_mut()
+            .unwrap()
+            .entries_mut()
+            .remove(0);
+        module.code_section_mut().unwrap().bodies_mut().remove(0);
+
+        let translator = RenumberStart::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(module.start_section(), None);
+    }
+}
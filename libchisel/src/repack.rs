@@ -1,11 +1,16 @@
 use std::collections::HashMap;
 
 use parity_wasm::builder;
-use parity_wasm::elements::Module;
+use parity_wasm::elements::{Module, Section};
 
 use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
 
-pub struct Repack;
+/// Struct on which ModuleTranslator is implemented.
+/// Rebuilds the module through the parity-wasm builder. Custom sections, including the names
+/// section, are dropped by the builder and carried over afterwards unless `keep_custom` is false.
+pub struct Repack {
+    keep_custom: bool,
+}
 
 impl<'a> ChiselModule<'a> for Repack {
     type ObjectReference = &'a dyn ModuleTranslator;
@@ -23,11 +28,16 @@ impl<'a> ChiselModule<'a> for Repack {
     }
 
     fn with_defaults() -> Result<Self, ModuleError> {
-        Ok(Repack {})
+        Ok(Repack { keep_custom: true })
     }
 
-    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
-        Err(ModuleError::NotSupported)
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        let keep_custom = match config.get("keep_custom").map(|value| value.as_str()) {
+            None | Some("true") => true,
+            Some("false") => false,
+            Some(_) => return Err(ModuleError::NotSupported),
+        };
+        Ok(Repack { keep_custom })
     }
 }
 
@@ -37,11 +47,40 @@ impl ModuleTranslator for Repack {
     }
 
     fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
-        // TODO: check in names section is carried over.
-        let module = module.clone();
-        let module = builder::from_module(module).build();
-        Ok(Some(module))
+        let mut repacked = builder::from_module(module.clone()).build();
+        if self.keep_custom {
+            restore_custom_sections(module, &mut repacked);
+        }
+        Ok(Some(repacked))
+    }
+}
+
+/// Copies the custom sections and the names section of `original` into `repacked`, each placed
+/// after as many other sections as preceded it in the original module.
+fn restore_custom_sections(original: &Module, repacked: &mut Module) {
+    let mut custom = Vec::new();
+    let mut preceding = 0;
+    for section in original.sections() {
+        match section {
+            Section::Custom(_) | Section::Name(_) => custom.push((preceding, section.clone())),
+            _ => preceding += 1,
+        }
     }
+    if custom.is_empty() {
+        return;
+    }
+
+    let sections = std::mem::take(repacked.sections_mut());
+    let mut custom = custom.into_iter().peekable();
+    let mut restored = Vec::with_capacity(sections.len() + custom.len());
+    for (index, section) in sections.into_iter().enumerate() {
+        while let Some((_, custom_section)) = custom.next_if(|(preceding, _)| *preceding <= index) {
+            restored.push(custom_section);
+        }
+        restored.push(section);
+    }
+    restored.extend(custom.map(|(_, custom_section)| custom_section));
+    *repacked.sections_mut() = restored;
 }
 
 #[cfg(test)]
@@ -112,6 +151,9 @@ mod tests {
             .push(parity_wasm::elements::Section::Custom(custom));
 
         let repack = Repack::with_defaults().unwrap();
+        assert_eq!(module, repack.translate(&module).unwrap().unwrap());
+
+        let repack = Repack { keep_custom: false };
         assert_ne!(module, repack.translate(&module).unwrap().unwrap());
     }
 
@@ -130,8 +172,46 @@ mod tests {
             .expect("parsing the names section failed");
         assert_eq!(module.names_section().is_some(), true);
         let repack = Repack::with_defaults().unwrap();
-        // Repack drops names section too.
+        let output = repack.translate(&module).unwrap().unwrap();
+        assert_eq!(output.has_names_section(), true);
+        assert_eq!(output, module);
+
+        let mut conf = HashMap::new();
+        conf.insert("keep_custom".to_string(), "false".to_string());
+        let repack = Repack::with_config(&conf).unwrap();
+        // The builder alone drops the names section.
         let output = repack.translate(&module).unwrap().unwrap();
         assert_eq!(output.has_names_section(), false);
     }
+
+    #[test]
+    fn producers_section_kept() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func (export "main"))
+        //   (@custom "producers" "\01\0clanguage\01\04Rust\00")
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, 0x00, 0x1b, 0x09,
+            0x70, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x65, 0x72, 0x73, 0x01, 0x0c, 0x6c, 0x61, 0x6e,
+            0x67, 0x75, 0x61, 0x67, 0x65, 0x01, 0x04, 0x52, 0x75, 0x73, 0x74, 0x00,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let repack = Repack::with_defaults().unwrap();
+        let output = repack.translate(&module).unwrap().unwrap();
+        assert_eq!(output.clone().to_bytes().unwrap(), wasm);
+        let custom: Vec<&str> = output.custom_sections().map(|c| c.name()).collect();
+        assert_eq!(custom, vec!["producers"]);
+    }
+
+    #[test]
+    fn invalid_keep_custom() {
+        let mut conf = HashMap::new();
+        conf.insert("keep_custom".to_string(), "yes".to_string());
+        assert!(Repack::with_config(&conf).is_err());
+    }
 }
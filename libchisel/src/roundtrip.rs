@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that the module survives a serialize-deserialize-serialize round trip byte-for-byte.
+///
+/// A module which cannot be serialized at all fails with `ModuleError::Custom` describing the
+/// serialization error. A module whose serialized form cannot be deserialized again (for example
+/// because the function and code sections disagree in length), or whose second serialization
+/// differs from the first, is reported as invalid.
+pub struct RoundTrip {}
+
+impl<'a> ChiselModule<'a> for RoundTrip {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "roundtrip".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(RoundTrip {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for RoundTrip {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let first = module.clone().to_bytes()?;
+
+        let second = match Module::from_bytes(&first) {
+            Ok(reparsed) => reparsed.to_bytes()?,
+            Err(_) => return Ok(false),
+        };
+
+        Ok(first == second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::{Func, FunctionSection, Section};
+
+    use super::*;
+
+    #[test]
+    fn well_formed_module_good() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = RoundTrip::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn inconsistent_code_section() {
+        // A function entry without a matching body serializes fine, but is rejected when read
+        // back.
+        let mut module = builder::module().build();
+        module
+            .insert_section(Section::Function(FunctionSection::with_entries(vec![
+                Func::new(0),
+            ])))
+            .unwrap();
+
+        let checker = RoundTrip::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{External, MemorySection, MemoryType, Module, Section};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Forces the limits of the defined memory to `initial` pages and, if set, a `maximum` number of
+/// pages. Without a maximum, any declared maximum is cleared. A memory section is created if the
+/// module has none. Imported memories are not supported.
+pub struct SetMemoryLimits {
+    initial: u32,
+    maximum: Option<u32>,
+}
+
+impl SetMemoryLimits {
+    pub fn new(initial: u32, maximum: Option<u32>) -> Self {
+        SetMemoryLimits { initial, maximum }
+    }
+
+    fn memory_type(&self, shared: bool) -> MemoryType {
+        let mut memory = MemoryType::new(self.initial, self.maximum);
+        memory.set_shared(shared);
+        memory
+    }
+}
+
+impl<'a> ChiselModule<'a> for SetMemoryLimits {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "setmemory".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        let initial = config
+            .get("initial")
+            .ok_or(ModuleError::NotSupported)?
+            .trim()
+            .parse::<u32>()?;
+        let maximum = match config.get("maximum") {
+            Some(maximum) => Some(maximum.trim().parse::<u32>()?),
+            None => None,
+        };
+        if maximum.filter(|maximum| *maximum < initial).is_some() {
+            return Err(ModuleError::Custom(
+                "maximum memory size is smaller than the initial size".to_string(),
+            ));
+        }
+        Ok(SetMemoryLimits::new(initial, maximum))
+    }
+}
+
+impl ModuleTranslator for SetMemoryLimits {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        if let Some(section) = module.import_section() {
+            for entry in section.entries() {
+                if let External::Memory(_) = entry.external() {
+                    return Err(ModuleError::Custom(
+                        "cannot set the limits of an imported memory".to_string(),
+                    ));
+                }
+            }
+        }
+
+        if let Some(section) = module.memory_section_mut() {
+            if let Some(memory) = section.entries_mut().first_mut() {
+                let limits = memory.limits();
+                if limits.initial() == self.initial && limits.maximum() == self.maximum {
+                    return Ok(false);
+                }
+                *memory = self.memory_type(limits.shared());
+                return Ok(true);
+            }
+            section.entries_mut().push(self.memory_type(false));
+            return Ok(true);
+        }
+
+        module.insert_section(Section::Memory(MemorySection::with_entries(vec![
+            self.memory_type(false)
+        ])))?;
+        Ok(true)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if self.translate_inplace(&mut ret)? {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_limits(module: &Module) -> (u32, Option<u32>) {
+        let limits = module.memory_section().unwrap().entries()[0].limits();
+        (limits.initial(), limits.maximum())
+    }
+
+    #[test]
+    fn maximum_added() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = SetMemoryLimits::new(1, Some(10));
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+        assert_eq!(memory_limits(&module), (1, Some(10)));
+
+        // Applying the same limits again is a no-op.
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(false, did_change);
+    }
+
+    #[test]
+    fn maximum_cleared_with_config() {
+        // wast:
+        // (module
+        //   (memory 1 10)
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x04, 0x01, 0x01, 0x01, 0x0a, 0x07, 0x08, 0x01, 0x04,
+            0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut config = HashMap::new();
+        config.insert("initial".to_string(), "2".to_string());
+        let translator = SetMemoryLimits::with_config(&config).unwrap();
+        let result = translator.translate(&module).unwrap().unwrap();
+        assert_eq!(memory_limits(&result), (2, None));
+    }
+
+    #[test]
+    fn memory_section_created() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = SetMemoryLimits::new(1, Some(1));
+        let result = translator.translate(&module).unwrap().unwrap();
+        assert_eq!(memory_limits(&result), (1, Some(1)));
+
+        // The new section must be placed where a parser expects it.
+        let bytes = result.to_bytes().unwrap();
+        assert!(Module::from_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn imported_memory() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 1))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = SetMemoryLimits::new(1, Some(10));
+        assert!(translator.translate(&module).is_err());
+    }
+
+    #[test]
+    fn invalid_config() {
+        let mut config = HashMap::new();
+        config.insert("initial".to_string(), "2".to_string());
+        config.insert("maximum".to_string(), "1".to_string());
+        assert!(SetMemoryLimits::with_config(&config).is_err());
+
+        config.insert("maximum".to_string(), "ten".to_string());
+        assert!(SetMemoryLimits::with_config(&config).is_err());
+    }
+}
@@ -46,6 +46,16 @@ impl<'a> ChiselModule<'a> for Snip {
         options.snip_rust_panicking_code =
             check_bool_option(&config, "snip_rust_panicking_code", true);
         options.skip_producers_section = check_bool_option(&config, "skip_producers_section", true);
+        // Functions whose name, as given by the names section, matches any of these regular
+        // expressions are snipped as well.
+        if let Some(patterns) = config.get("snip_patterns") {
+            options.patterns = patterns
+                .split(',')
+                .map(|pattern| pattern.trim())
+                .filter(|pattern| !pattern.is_empty())
+                .map(|pattern| pattern.to_string())
+                .collect();
+        }
         Ok(Snip { 0: options })
     }
 }
@@ -112,4 +122,50 @@ mod tests {
             .expect("new module to be returned");
         assert!(module.to_bytes().unwrap().len() < wasm.len());
     }
+
+    #[test]
+    fn snip_by_pattern() {
+        // (module
+        // (import "env" "ethereum_useGas" (func (param i64)))
+        // (memory 1)
+        // (export "main" (func $main))
+        // (export "memory" (memory 0))
+        // (func $main
+        //     (call $std::panicking::rust_panic_with_hook::h12b7239ed4348eae)
+        //     (call $core::fmt::write::h9f284ae8e8e9b94a)
+        // )
+        // (func $std::panicking::rust_panic_with_hook::h12b7239ed4348eae)
+        // (func $core::fmt::write::h9f284ae8e8e9b94a)
+        // )
+        let wasm: Vec<u8> = FromHex::from_hex(
+            "0061736d0100000001080260017e0060000002170103656e760f65746865
+7265756d5f75736547617300000304030101010503010001071102046d61
+696e0001066d656d6f727902000a10030600100210030b0300010b030001
+0b007f046e616d650178040011696d706f72742466756e6374696f6e2430
+01046d61696e02377374643a3a70616e69636b696e673a3a727573745f70
+616e69635f776974685f686f6f6b3a3a6831326237323339656434333438
+6561650323636f72653a3a666d743a3a77726974653a3a68396632383461
+65386538653962393461",
+        )
+        .unwrap();
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut config = HashMap::new();
+        config.insert("snip_rust_fmt_code".to_string(), "false".to_string());
+        config.insert("snip_rust_panicking_code".to_string(), "false".to_string());
+        config.insert(
+            "snip_patterns".to_string(),
+            "core::fmt::write::.*, alloc::.*".to_string(),
+        );
+        let snip = Snip::with_config(&config).unwrap();
+        assert_eq!(snip.0.patterns.len(), 2);
+
+        let module = snip
+            .translate(&module)
+            .expect("translation to be succesful")
+            .expect("new module to be returned");
+        // Only core::fmt::write is removed, the panicking function is kept.
+        assert_eq!(module.code_section().unwrap().bodies().len(), 2);
+        assert!(module.to_bytes().unwrap().len() < wasm.len());
+    }
 }
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{ExportEntry, Internal, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Orders the export section by kind (functions, tables, memories, then globals) and then by
+/// field name, for reproducible builds. The exported items are not renumbered.
+pub struct SortExports {}
+
+impl<'a> ChiselModule<'a> for SortExports {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "sortexports".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(SortExports {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for SortExports {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(sort_exports(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if sort_exports(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The sort key of an export: the kind in binary encoding order, then the field name.
+fn export_key(entry: &ExportEntry) -> (u8, &str) {
+    let kind = match entry.internal() {
+        Internal::Function(_) => 0,
+        Internal::Table(_) => 1,
+        Internal::Memory(_) => 2,
+        Internal::Global(_) => 3,
+    };
+    (kind, entry.field())
+}
+
+fn sort_exports(module: &mut Module) -> bool {
+    if let Some(section) = module.export_section_mut() {
+        let entries = section.entries_mut();
+        if entries
+            .windows(2)
+            .all(|pair| export_key(&pair[0]) <= export_key(&pair[1]))
+        {
+            return false;
+        }
+        entries.sort_by(|a, b| export_key(a).cmp(&export_key(b)));
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shuffled_exports_sorted() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (global i32 (i32.const 0))
+        //   (func $init)
+        //   (func $main)
+        //   (export "memory" (memory 0))
+        //   (export "version" (global 0))
+        //   (export "main" (func $main))
+        //   (export "init" (func $init))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x06, 0x06, 0x01, 0x7f,
+            0x00, 0x41, 0x00, 0x0b, 0x07, 0x22, 0x04, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x07, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x03, 0x00, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x01, 0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x00, 0x0a, 0x07,
+            0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = SortExports::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let exports: Vec<(&str, &Internal)> = module
+            .export_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| (entry.field(), entry.internal()))
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                ("init", &Internal::Function(0)),
+                ("main", &Internal::Function(1)),
+                ("memory", &Internal::Memory(0)),
+                ("version", &Internal::Global(0)),
+            ]
+        );
+
+        // Sorting again is a no-op.
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn already_sorted() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (func $main)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = SortExports::with_defaults().unwrap();
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
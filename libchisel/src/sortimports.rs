@@ -0,0 +1,418 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{
+    DataSegment, ElementSegment, External, ImportEntry, InitExpr, Instruction, Internal, Module,
+};
+
+use super::funcindex::remap_func_indices;
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Orders the import section by module namespace and then by field name. Imports come first in
+/// each index space, so every reference to an imported function, table, memory or global is
+/// renumbered to follow its import.
+pub struct SortImports {}
+
+impl<'a> ChiselModule<'a> for SortImports {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "sortimports".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(SortImports {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for SortImports {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(sort_imports(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if sort_imports(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The sort key of an import: the module namespace, then the field name.
+fn import_key(entry: &ImportEntry) -> (&str, &str) {
+    (entry.module(), entry.field())
+}
+
+/// The index space an import belongs to, in binary encoding order.
+fn external_kind(external: &External) -> usize {
+    match external {
+        External::Function(_) => 0,
+        External::Table(_) => 1,
+        External::Memory(_) => 2,
+        External::Global(_) => 3,
+    }
+}
+
+/// Old to new index mappings, covering the whole index space of each kind.
+struct IndexRemap {
+    tables: Vec<u32>,
+    memories: Vec<u32>,
+    globals: Vec<u32>,
+}
+
+impl IndexRemap {
+    fn table(&self, idx: u32) -> u32 {
+        self.tables.get(idx as usize).cloned().unwrap_or(idx)
+    }
+
+    fn memory(&self, idx: u32) -> u32 {
+        self.memories.get(idx as usize).cloned().unwrap_or(idx)
+    }
+
+    fn global(&self, idx: u32) -> u32 {
+        self.globals.get(idx as usize).cloned().unwrap_or(idx)
+    }
+}
+
+fn remap_init_expr(init_expr: &mut InitExpr, remap: &IndexRemap) {
+    for instruction in init_expr.code_mut() {
+        if let Instruction::GetGlobal(idx) = instruction {
+            *idx = remap.global(*idx);
+        }
+    }
+}
+
+/// Applies `remap` to every table, memory and global index referenced in the module.
+fn remap_other_indices(module: &mut Module, remap: &IndexRemap) {
+    if let Some(code) = module.code_section_mut() {
+        for body in code.bodies_mut() {
+            for instruction in body.code_mut().elements_mut() {
+                match instruction {
+                    Instruction::GetGlobal(idx) | Instruction::SetGlobal(idx) => {
+                        *idx = remap.global(*idx)
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    if let Some(globals) = module.global_section_mut() {
+        for entry in globals.entries_mut() {
+            remap_init_expr(entry.init_expr_mut(), remap);
+        }
+    }
+
+    if let Some(exports) = module.export_section_mut() {
+        for entry in exports.entries_mut() {
+            match entry.internal_mut() {
+                Internal::Table(idx) => *idx = remap.table(*idx),
+                Internal::Memory(idx) => *idx = remap.memory(*idx),
+                Internal::Global(idx) => *idx = remap.global(*idx),
+                Internal::Function(_) => (),
+            }
+        }
+    }
+
+    // Segments do not expose their table or memory index mutably, so they are rebuilt.
+    if let Some(elements) = module.elements_section_mut() {
+        for segment in elements.entries_mut() {
+            let mut offset = segment.offset().clone();
+            if let Some(offset) = offset.as_mut() {
+                remap_init_expr(offset, remap);
+            }
+            let mut remapped = ElementSegment::new(
+                remap.table(segment.index()),
+                offset,
+                segment.members().to_vec(),
+            );
+            remapped.set_passive(segment.passive());
+            *segment = remapped;
+        }
+    }
+
+    if let Some(data) = module.data_section_mut() {
+        for segment in data.entries_mut() {
+            let mut offset = segment.offset().clone();
+            if let Some(offset) = offset.as_mut() {
+                remap_init_expr(offset, remap);
+            }
+            let mut remapped = DataSegment::new(
+                remap.memory(segment.index()),
+                offset,
+                segment.value().to_vec(),
+            );
+            remapped.set_passive(segment.passive());
+            *segment = remapped;
+        }
+    }
+}
+
+fn sort_imports(module: &mut Module) -> bool {
+    let entries = match module.import_section() {
+        Some(section) => section.entries(),
+        None => return false,
+    };
+    if entries
+        .windows(2)
+        .all(|pair| import_key(&pair[0]) <= import_key(&pair[1]))
+    {
+        return false;
+    }
+
+    // The position of every import within its own index space, before and after sorting.
+    let mut old_indices = Vec::with_capacity(entries.len());
+    let mut counts = [0u32; 4];
+    for entry in entries {
+        let kind = external_kind(entry.external());
+        old_indices.push(counts[kind]);
+        counts[kind] += 1;
+    }
+
+    // A stable sort keeps duplicate imports in their relative order.
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by(|a, b| import_key(&entries[*a]).cmp(&import_key(&entries[*b])));
+
+    let defined = [
+        module
+            .function_section()
+            .map_or(0, |section| section.entries().len()),
+        module
+            .table_section()
+            .map_or(0, |section| section.entries().len()),
+        module
+            .memory_section()
+            .map_or(0, |section| section.entries().len()),
+        module
+            .global_section()
+            .map_or(0, |section| section.entries().len()),
+    ];
+    // Defined items follow the imports and keep their indices.
+    let mut remaps: Vec<Vec<u32>> = (0..4)
+        .map(|kind| (0..counts[kind] + defined[kind] as u32).collect())
+        .collect();
+    let mut new_counts = [0u32; 4];
+    for position in order.iter() {
+        let kind = external_kind(entries[*position].external());
+        remaps[kind][old_indices[*position] as usize] = new_counts[kind];
+        new_counts[kind] += 1;
+    }
+
+    let sorted: Vec<ImportEntry> = order
+        .iter()
+        .map(|position| entries[*position].clone())
+        .collect();
+    *module
+        .import_section_mut()
+        .expect("Import section is present")
+        .entries_mut() = sorted;
+
+    remap_func_indices(module, &remaps[0]);
+
+    let globals = remaps.pop().expect("Four index spaces");
+    let memories = remaps.pop().expect("Four index spaces");
+    let tables = remaps.pop().expect("Four index spaces");
+    remap_other_indices(
+        module,
+        &IndexRemap {
+            tables,
+            memories,
+            globals,
+        },
+    );
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::{GlobalType, Instructions, MemoryType, ValueType};
+
+    use super::*;
+
+    fn import(namespace: &str, field: &str, external: External) -> ImportEntry {
+        ImportEntry::new(namespace.to_string(), field.to_string(), external)
+    }
+
+    fn global(namespace: &str, field: &str) -> ImportEntry {
+        import(
+            namespace,
+            field,
+            External::Global(GlobalType::new(ValueType::I32, false)),
+        )
+    }
+
+    /// Returns the import field of every global read and function called in the first body.
+    fn resolve_references(module: &Module) -> Vec<String> {
+        let imports = module.import_section().unwrap().entries();
+        let nth_import = |kind: usize, idx: u32| {
+            imports
+                .iter()
+                .filter(|entry| external_kind(entry.external()) == kind)
+                .nth(idx as usize)
+                .map_or("<defined>".to_string(), |entry| entry.field().to_string())
+        };
+        module.code_section().unwrap().bodies()[0]
+            .code()
+            .elements()
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Call(idx) => Some(nth_import(0, *idx)),
+                Instruction::GetGlobal(idx) => Some(nth_import(3, *idx)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn interleaved_imports_sorted() {
+        let mut module = builder::module()
+            .with_import(import("ethereum", "useGas", External::Function(0)))
+            .with_import(global("env", "zeta"))
+            .with_import(import("env", "print", External::Function(0)))
+            .with_import(import("ethereum", "finish", External::Function(0)))
+            .with_import(global("env", "alpha"))
+            .with_import(import(
+                "env",
+                "memory",
+                External::Memory(MemoryType::new(1, None)),
+            ))
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::GetGlobal(1),
+                Instruction::Drop,
+                Instruction::GetGlobal(2),
+                Instruction::Drop,
+                Instruction::Call(0),
+                Instruction::Call(1),
+                Instruction::Call(2),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .global()
+            .value_type()
+            .i32()
+            .init_expr(Instruction::GetGlobal(0))
+            .build()
+            .table()
+            .with_min(1)
+            .with_element(0, vec![0])
+            .build()
+            .with_data_segment(DataSegment::new(
+                0,
+                Some(InitExpr::new(vec![
+                    Instruction::GetGlobal(1),
+                    Instruction::End,
+                ])),
+                vec![0x2a],
+            ))
+            .export()
+            .field("main")
+            .internal()
+            .func(3)
+            .build()
+            .export()
+            .field("alpha")
+            .internal()
+            .global(1)
+            .build()
+            .build();
+        module.set_start_section(2);
+
+        let before = resolve_references(&module);
+        assert_eq!(
+            before,
+            vec!["alpha", "<defined>", "useGas", "print", "finish"]
+        );
+
+        let translator = SortImports::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let imports: Vec<(&str, &str)> = module
+            .import_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(import_key)
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("env", "alpha"),
+                ("env", "memory"),
+                ("env", "print"),
+                ("env", "zeta"),
+                ("ethereum", "finish"),
+                ("ethereum", "useGas"),
+            ]
+        );
+
+        // Every reference still resolves to the same import.
+        assert_eq!(resolve_references(&module), before);
+        assert_eq!(module.start_section(), Some(1));
+        assert_eq!(
+            module.elements_section().unwrap().entries()[0].members(),
+            &[2]
+        );
+        assert_eq!(
+            module.global_section().unwrap().entries()[0]
+                .init_expr()
+                .code(),
+            &[Instruction::GetGlobal(1), Instruction::End]
+        );
+        assert_eq!(
+            module.data_section().unwrap().entries()[0]
+                .offset()
+                .as_ref()
+                .unwrap()
+                .code(),
+            &[Instruction::GetGlobal(0), Instruction::End]
+        );
+        let exports: Vec<&Internal> = module
+            .export_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.internal())
+            .collect();
+        assert_eq!(exports, vec![&Internal::Function(3), &Internal::Global(0)]);
+
+        assert!(walrus::Module::from_buffer(&module.clone().to_bytes().unwrap()).is_ok());
+
+        // Sorting again is a no-op.
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn already_sorted() {
+        let module = builder::module()
+            .with_import(import("env", "print", External::Function(0)))
+            .with_import(import("ethereum", "finish", External::Function(0)))
+            .with_import(import("ethereum", "useGas", External::Function(0)))
+            .build();
+
+        let translator = SortImports::with_defaults().unwrap();
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{
+    serialize, BulkInstruction, Instruction, Module, Section, SignExtInstruction,
+};
+
+/// Size and content statistics of a module, as returned by `module_stats`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModuleStats {
+    /// The serialized size of each section in module order, including its id and size prefix.
+    /// Custom sections are listed under their own name.
+    pub section_sizes: Vec<(String, usize)>,
+    /// The number of functions defined in the module. Imported functions are not counted.
+    pub function_count: usize,
+    /// The number of imports of any kind.
+    pub import_count: usize,
+    /// The number of exports of any kind.
+    pub export_count: usize,
+    /// How often each instruction occurs across all function bodies, keyed by its text format
+    /// name. SIMD and atomic instructions are counted as `simd` and `atomic` respectively.
+    pub opcodes: HashMap<&'static str, usize>,
+}
+
+/// Collects the section sizes, function, import and export counts, and the instruction
+/// histogram of a module.
+pub fn module_stats(module: &Module) -> ModuleStats {
+    let section_sizes = module
+        .sections()
+        .iter()
+        .map(|section| {
+            // A section which fails to serialize on its own is reported as empty.
+            let size = serialize(section.clone()).map_or(0, |bytes| bytes.len());
+            (section_name(section), size)
+        })
+        .collect();
+
+    let mut opcodes = HashMap::new();
+    if let Some(code) = module.code_section() {
+        for body in code.bodies() {
+            for instruction in body.code().elements() {
+                *opcodes.entry(opcode_name(instruction)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    ModuleStats {
+        section_sizes,
+        function_count: module
+            .function_section()
+            .map_or(0, |section| section.entries().len()),
+        import_count: module
+            .import_section()
+            .map_or(0, |section| section.entries().len()),
+        export_count: module
+            .export_section()
+            .map_or(0, |section| section.entries().len()),
+        opcodes,
+    }
+}
+
+fn section_name(section: &Section) -> String {
+    match section {
+        Section::Unparsed { id, .. } => format!("unknown({})", id),
+        Section::Custom(custom) => custom.name().to_string(),
+        Section::Type(_) => "type".to_string(),
+        Section::Import(_) => "import".to_string(),
+        Section::Function(_) => "function".to_string(),
+        Section::Table(_) => "table".to_string(),
+        Section::Memory(_) => "memory".to_string(),
+        Section::Global(_) => "global".to_string(),
+        Section::Export(_) => "export".to_string(),
+        Section::Start(_) => "start".to_string(),
+        Section::Element(_) => "element".to_string(),
+        Section::DataCount(_) => "datacount".to_string(),
+        Section::Code(_) => "code".to_string(),
+        Section::Data(_) => "data".to_string(),
+        Section::Name(_) => "name".to_string(),
+        Section::Reloc(reloc) => reloc.name().to_string(),
+    }
+}
+
+/// Returns the text format name of an instruction.
+fn opcode_name(instruction: &Instruction) -> &'static str {
+    match instruction {
+        Instruction::Unreachable => "unreachable",
+        Instruction::Nop => "nop",
+        Instruction::Block(..) => "block",
+        Instruction::Loop(..) => "loop",
+        Instruction::If(..) => "if",
+        Instruction::Else => "else",
+        Instruction::End => "end",
+        Instruction::Br(..) => "br",
+        Instruction::BrIf(..) => "br_if",
+        Instruction::BrTable(..) => "br_table",
+        Instruction::Return => "return",
+        Instruction::Call(..) => "call",
+        Instruction::CallIndirect(..) => "call_indirect",
+        Instruction::Drop => "drop",
+        Instruction::Select => "select",
+        Instruction::GetLocal(..) => "local.get",
+        Instruction::SetLocal(..) => "local.set",
+        Instruction::TeeLocal(..) => "local.tee",
+        Instruction::GetGlobal(..) => "global.get",
+        Instruction::SetGlobal(..) => "global.set",
+        Instruction::I32Load(..) => "i32.load",
+        Instruction::I64Load(..) => "i64.load",
+        Instruction::F32Load(..) => "f32.load",
+        Instruction::F64Load(..) => "f64.load",
+        Instruction::I32Load8S(..) => "i32.load8_s",
+        Instruction::I32Load8U(..) => "i32.load8_u",
+        Instruction::I32Load16S(..) => "i32.load16_s",
+        Instruction::I32Load16U(..) => "i32.load16_u",
+        Instruction::I64Load8S(..) => "i64.load8_s",
+        Instruction::I64Load8U(..) => "i64.load8_u",
+        Instruction::I64Load16S(..) => "i64.load16_s",
+        Instruction::I64Load16U(..) => "i64.load16_u",
+        Instruction::I64Load32S(..) => "i64.load32_s",
+        Instruction::I64Load32U(..) => "i64.load32_u",
+        Instruction::I32Store(..) => "i32.store",
+        Instruction::I64Store(..) => "i64.store",
+        Instruction::F32Store(..) => "f32.store",
+        Instruction::F64Store(..) => "f64.store",
+        Instruction::I32Store8(..) => "i32.store8",
+        Instruction::I32Store16(..) => "i32.store16",
+        Instruction::I64Store8(..) => "i64.store8",
+        Instruction::I64Store16(..) => "i64.store16",
+        Instruction::I64Store32(..) => "i64.store32",
+        Instruction::CurrentMemory(..) => "memory.size",
+        Instruction::GrowMemory(..) => "memory.grow",
+        Instruction::I32Const(..) => "i32.const",
+        Instruction::I64Const(..) => "i64.const",
+        Instruction::F32Const(..) => "f32.const",
+        Instruction::F64Const(..) => "f64.const",
+        Instruction::I32Eqz => "i32.eqz",
+        Instruction::I32Eq => "i32.eq",
+        Instruction::I32Ne => "i32.ne",
+        Instruction::I32LtS => "i32.lt_s",
+        Instruction::I32LtU => "i32.lt_u",
+        Instruction::I32GtS => "i32.gt_s",
+        Instruction::I32GtU => "i32.gt_u",
+        Instruction::I32LeS => "i32.le_s",
+        Instruction::I32LeU => "i32.le_u",
+        Instruction::I32GeS => "i32.ge_s",
+        Instruction::I32GeU => "i32.ge_u",
+        Instruction::I64Eqz => "i64.eqz",
+        Instruction::I64Eq => "i64.eq",
+        Instruction::I64Ne => "i64.ne",
+        Instruction::I64LtS => "i64.lt_s",
+        Instruction::I64LtU => "i64.lt_u",
+        Instruction::I64GtS => "i64.gt_s",
+        Instruction::I64GtU => "i64.gt_u",
+        Instruction::I64LeS => "i64.le_s",
+        Instruction::I64LeU => "i64.le_u",
+        Instruction::I64GeS => "i64.ge_s",
+        Instruction::I64GeU => "i64.ge_u",
+        Instruction::F32Eq => "f32.eq",
+        Instruction::F32Ne => "f32.ne",
+        Instruction::F32Lt => "f32.lt",
+        Instruction::F32Gt => "f32.gt",
+        Instruction::F32Le => "f32.le",
+        Instruction::F32Ge => "f32.ge",
+        Instruction::F64Eq => "f64.eq",
+        Instruction::F64Ne => "f64.ne",
+        Instruction::F64Lt => "f64.lt",
+        Instruction::F64Gt => "f64.gt",
+        Instruction::F64Le => "f64.le",
+        Instruction::F64Ge => "f64.ge",
+        Instruction::I32Clz => "i32.clz",
+        Instruction::I32Ctz => "i32.ctz",
+        Instruction::I32Popcnt => "i32.popcnt",
+        Instruction::I32Add => "i32.add",
+        Instruction::I32Sub => "i32.sub",
+        Instruction::I32Mul => "i32.mul",
+        Instruction::I32DivS => "i32.div_s",
+        Instruction::I32DivU => "i32.div_u",
+        Instruction::I32RemS => "i32.rem_s",
+        Instruction::I32RemU => "i32.rem_u",
+        Instruction::I32And => "i32.and",
+        Instruction::I32Or => "i32.or",
+        Instruction::I32Xor => "i32.xor",
+        Instruction::I32Shl => "i32.shl",
+        Instruction::I32ShrS => "i32.shr_s",
+        Instruction::I32ShrU => "i32.shr_u",
+        Instruction::I32Rotl => "i32.rotl",
+        Instruction::I32Rotr => "i32.rotr",
+        Instruction::I64Clz => "i64.clz",
+        Instruction::I64Ctz => "i64.ctz",
+        Instruction::I64Popcnt => "i64.popcnt",
+        Instruction::I64Add => "i64.add",
+        Instruction::I64Sub => "i64.sub",
+        Instruction::I64Mul => "i64.mul",
+        Instruction::I64DivS => "i64.div_s",
+        Instruction::I64DivU => "i64.div_u",
+        Instruction::I64RemS => "i64.rem_s",
+        Instruction::I64RemU => "i64.rem_u",
+        Instruction::I64And => "i64.and",
+        Instruction::I64Or => "i64.or",
+        Instruction::I64Xor => "i64.xor",
+        Instruction::I64Shl => "i64.shl",
+        Instruction::I64ShrS => "i64.shr_s",
+        Instruction::I64ShrU => "i64.shr_u",
+        Instruction::I64Rotl => "i64.rotl",
+        Instruction::I64Rotr => "i64.rotr",
+        Instruction::F32Abs => "f32.abs",
+        Instruction::F32Neg => "f32.neg",
+        Instruction::F32Ceil => "f32.ceil",
+        Instruction::F32Floor => "f32.floor",
+        Instruction::F32Trunc => "f32.trunc",
+        Instruction::F32Nearest => "f32.nearest",
+        Instruction::F32Sqrt => "f32.sqrt",
+        Instruction::F32Add => "f32.add",
+        Instruction::F32Sub => "f32.sub",
+        Instruction::F32Mul => "f32.mul",
+        Instruction::F32Div => "f32.div",
+        Instruction::F32Min => "f32.min",
+        Instruction::F32Max => "f32.max",
+        Instruction::F32Copysign => "f32.copysign",
+        Instruction::F64Abs => "f64.abs",
+        Instruction::F64Neg => "f64.neg",
+        Instruction::F64Ceil => "f64.ceil",
+        Instruction::F64Floor => "f64.floor",
+        Instruction::F64Trunc => "f64.trunc",
+        Instruction::F64Nearest => "f64.nearest",
+        Instruction::F64Sqrt => "f64.sqrt",
+        Instruction::F64Add => "f64.add",
+        Instruction::F64Sub => "f64.sub",
+        Instruction::F64Mul => "f64.mul",
+        Instruction::F64Div => "f64.div",
+        Instruction::F64Min => "f64.min",
+        Instruction::F64Max => "f64.max",
+        Instruction::F64Copysign => "f64.copysign",
+        Instruction::I32WrapI64 => "i32.wrap_i64",
+        Instruction::I32TruncSF32 => "i32.trunc_f32_s",
+        Instruction::I32TruncUF32 => "i32.trunc_f32_u",
+        Instruction::I32TruncSF64 => "i32.trunc_f64_s",
+        Instruction::I32TruncUF64 => "i32.trunc_f64_u",
+        Instruction::I64ExtendSI32 => "i64.extend_i32_s",
+        Instruction::I64ExtendUI32 => "i64.extend_i32_u",
+        Instruction::I64TruncSF32 => "i64.trunc_f32_s",
+        Instruction::I64TruncUF32 => "i64.trunc_f32_u",
+        Instruction::I64TruncSF64 => "i64.trunc_f64_s",
+        Instruction::I64TruncUF64 => "i64.trunc_f64_u",
+        Instruction::F32ConvertSI32 => "f32.convert_i32_s",
+        Instruction::F32ConvertUI32 => "f32.convert_i32_u",
+        Instruction::F32ConvertSI64 => "f32.convert_i64_s",
+        Instruction::F32ConvertUI64 => "f32.convert_i64_u",
+        Instruction::F32DemoteF64 => "f32.demote_f64",
+        Instruction::F64ConvertSI32 => "f64.convert_i32_s",
+        Instruction::F64ConvertUI32 => "f64.convert_i32_u",
+        Instruction::F64ConvertSI64 => "f64.convert_i64_s",
+        Instruction::F64ConvertUI64 => "f64.convert_i64_u",
+        Instruction::F64PromoteF32 => "f64.promote_f32",
+        Instruction::I32ReinterpretF32 => "i32.reinterpret_f32",
+        Instruction::I64ReinterpretF64 => "i64.reinterpret_f64",
+        Instruction::F32ReinterpretI32 => "f32.reinterpret_i32",
+        Instruction::F64ReinterpretI64 => "f64.reinterpret_i64",
+        Instruction::Atomics(_) => "atomic",
+        Instruction::Simd(_) => "simd",
+        Instruction::SignExt(instruction) => match instruction {
+            SignExtInstruction::I32Extend8S => "i32.extend8_s",
+            SignExtInstruction::I32Extend16S => "i32.extend16_s",
+            SignExtInstruction::I64Extend8S => "i64.extend8_s",
+            SignExtInstruction::I64Extend16S => "i64.extend16_s",
+            SignExtInstruction::I64Extend32S => "i64.extend32_s",
+        },
+        Instruction::Bulk(instruction) => match instruction {
+            BulkInstruction::MemoryInit(_) => "memory.init",
+            BulkInstruction::MemoryDrop(_) => "data.drop",
+            BulkInstruction::MemoryCopy => "memory.copy",
+            BulkInstruction::MemoryFill => "memory.fill",
+            BulkInstruction::TableInit(_) => "table.init",
+            BulkInstruction::TableDrop(_) => "elem.drop",
+            BulkInstruction::TableCopy => "table.copy",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_i32_add() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (memory 1)
+        //   (func (export "main") (result i32)
+        //     i32.const 1
+        //     i32.const 2
+        //     i32.add
+        //     i32.const 3
+        //     i32.add)
+        //   (func (param i32) (result i32)
+        //     local.get 0
+        //     local.get 0
+        //     i32.add)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0d, 0x03, 0x60, 0x00, 0x00,
+            0x60, 0x00, 0x01, 0x7f, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x02, 0x0b, 0x01, 0x03, 0x65,
+            0x6e, 0x76, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x03, 0x03, 0x02, 0x01, 0x02, 0x05,
+            0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01,
+            0x0a, 0x14, 0x02, 0x0a, 0x00, 0x41, 0x01, 0x41, 0x02, 0x6a, 0x41, 0x03, 0x6a, 0x0b,
+            0x07, 0x00, 0x20, 0x00, 0x20, 0x00, 0x6a, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let stats = module_stats(&module);
+        assert_eq!(stats.opcodes.get("i32.add"), Some(&3));
+        assert_eq!(stats.opcodes.get("i32.const"), Some(&3));
+        assert_eq!(stats.opcodes.get("local.get"), Some(&2));
+        assert_eq!(stats.opcodes.get("end"), Some(&2));
+        assert_eq!(stats.opcodes.get("i64.add"), None);
+        assert_eq!(stats.function_count, 2);
+        assert_eq!(stats.import_count, 1);
+        assert_eq!(stats.export_count, 1);
+    }
+
+    #[test]
+    fn section_sizes_add_up() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (memory 1)
+        //   (func (export "main") (result i32)
+        //     i32.const 1
+        //     i32.const 2
+        //     i32.add
+        //     i32.const 3
+        //     i32.add)
+        //   (func (param i32) (result i32)
+        //     local.get 0
+        //     local.get 0
+        //     i32.add)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0d, 0x03, 0x60, 0x00, 0x00,
+            0x60, 0x00, 0x01, 0x7f, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x02, 0x0b, 0x01, 0x03, 0x65,
+            0x6e, 0x76, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x03, 0x03, 0x02, 0x01, 0x02, 0x05,
+            0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01,
+            0x0a, 0x14, 0x02, 0x0a, 0x00, 0x41, 0x01, 0x41, 0x02, 0x6a, 0x41, 0x03, 0x6a, 0x0b,
+            0x07, 0x00, 0x20, 0x00, 0x20, 0x00, 0x6a, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let stats = module_stats(&module);
+        let names: Vec<&str> = stats
+            .section_sizes
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["type", "import", "function", "memory", "export", "code"]
+        );
+        // The sections make up everything but the 8 byte preamble.
+        let total: usize = stats.section_sizes.iter().map(|(_, size)| size).sum();
+        assert_eq!(total + 8, wasm.len());
+    }
+}
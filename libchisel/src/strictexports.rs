@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{FunctionType, Internal, Module};
+
+use super::{
+    verifyexports::func_sig_by_index, ChiselModule, ModuleError, ModuleKind, ModuleValidator,
+};
+
+/// Struct on which ModuleValidator is implemented.
+/// Checks that `main` is the only exported function and that `memory` is exported, as required
+/// by strict ewasm deployments. Any additional function export makes the module invalid, and is
+/// named by `find_extra_export`.
+pub struct StrictExports {}
+
+impl StrictExports {
+    /// Returns the name of the first function export other than `main`, if any.
+    pub fn find_extra_export<'m>(&self, module: &'m Module) -> Option<&'m str> {
+        module
+            .export_section()?
+            .entries()
+            .iter()
+            .find(|e| match e.internal() {
+                Internal::Function(_) => e.field() != "main",
+                _ => false,
+            })
+            .map(|e| e.field())
+    }
+}
+
+impl<'a> ChiselModule<'a> for StrictExports {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "strictexports".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(StrictExports {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for StrictExports {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        let exports = if let Some(section) = module.export_section() {
+            section.entries()
+        } else {
+            return Ok(false);
+        };
+
+        if self.find_extra_export(module).is_some() {
+            return Ok(false);
+        }
+
+        let has_main = exports.iter().any(|e| match e.internal() {
+            Internal::Function(idx) if e.field() == "main" => {
+                func_sig_by_index(module, *idx) == Some(&FunctionType::default())
+            }
+            _ => false,
+        });
+        let has_memory = exports.iter().any(|e| match e.internal() {
+            Internal::Memory(_) => e.field() == "memory",
+            _ => false,
+        });
+
+        Ok(has_main && has_memory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_and_memory_good() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = StrictExports::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn extra_global_export_good() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (global i32 (i32.const 0))
+        //   (export "main" (func 0))
+        //   (export "memory" (memory 0))
+        //   (export "counter" (global 0))
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x06, 0x06, 0x01, 0x7f, 0x00,
+            0x41, 0x00, 0x0b, 0x07, 0x1b, 0x03, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x06,
+            0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x07, 0x63, 0x6f, 0x75, 0x6e, 0x74,
+            0x65, 0x72, 0x03, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = StrictExports::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn no_exports() {
+        // wast:
+        // (module)
+        let wasm: Vec<u8> = vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = StrictExports::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn extra_function_export() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func 0))
+        //   (export "memory" (memory 0))
+        //   (export "foo" (func 1))
+        //   (func)
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x03, 0x02, 0x00, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x17, 0x03, 0x04,
+            0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02,
+            0x00, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x01, 0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02,
+            0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = StrictExports::with_defaults().unwrap();
+        let result = checker.validate(&module);
+        assert_eq!(result, Ok(false));
+        assert_eq!(checker.find_extra_export(&module), Some("foo"));
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{
+    CodeSection, ExportEntry, ExportSection, External, Func, FuncBody, FunctionSection,
+    FunctionType, ImportEntry, ImportSection, Instruction, Instructions, Internal, MemorySection,
+    MemoryType, Module, Section, Type, TypeSection,
+};
+
+use super::imports::ImportList;
+use super::{ChiselModule, ModuleCreator, ModuleError, ModuleKind, ModulePreset};
+
+/// Struct on which ModuleCreator is implemented.
+/// Creates a skeleton module exporting an empty `main` and a single page `memory`, importing the
+/// selected host functions of the interface.
+pub struct Stub {
+    /// Module, field and signature of each imported host function.
+    imports: Vec<(String, String, FunctionType)>,
+}
+
+impl Stub {
+    /// Returns a stub for the interface given by `preset`, importing the named host functions.
+    pub fn with_imports(preset: &str, names: &[&str]) -> Result<Self, ModuleError> {
+        if preset != "ewasm" {
+            return Err(ModuleError::NotSupported);
+        }
+        let interface = ImportList::with_preset(preset)?;
+
+        let mut imports = Vec::with_capacity(names.len());
+        for name in names {
+            let import = interface
+                .lookup_by_field(name)
+                .ok_or(ModuleError::NotFound)?;
+            let signature = import.signature().map_err(|_| ModuleError::NotFound)?;
+            imports.push((
+                import.module().to_string(),
+                import.field().to_string(),
+                signature.clone(),
+            ));
+        }
+        Ok(Stub { imports })
+    }
+}
+
+impl<'a> ChiselModule<'a> for Stub {
+    type ObjectReference = &'a dyn ModuleCreator;
+
+    fn id(&'a self) -> String {
+        "stub".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Creator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            let names: Vec<&str> = config
+                .get("imports")
+                .map(|imports| {
+                    imports
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+            Stub::with_imports(preset, &names)
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModulePreset for Stub {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        Stub::with_imports(preset, &[])
+    }
+}
+
+impl ModuleCreator for Stub {
+    fn create(&self) -> Result<Module, ModuleError> {
+        // The signatures of the host functions come first, followed by the one of `main`.
+        let mut types: Vec<Type> = self
+            .imports
+            .iter()
+            .map(|(_, _, signature)| Type::Function(signature.clone()))
+            .collect();
+        let main_type = types.len() as u32;
+        types.push(Type::Function(FunctionType::new(vec![], None)));
+
+        let imports: Vec<ImportEntry> = self
+            .imports
+            .iter()
+            .enumerate()
+            .map(|(index, (module, field, _))| {
+                ImportEntry::new(
+                    module.clone(),
+                    field.clone(),
+                    External::Function(index as u32),
+                )
+            })
+            .collect();
+        let main_index = imports.len() as u32;
+
+        let mut sections = vec![Section::Type(TypeSection::with_types(types))];
+        if !imports.is_empty() {
+            sections.push(Section::Import(ImportSection::with_entries(imports)));
+        }
+        sections.push(Section::Function(FunctionSection::with_entries(vec![
+            Func::new(main_type),
+        ])));
+        sections.push(Section::Memory(MemorySection::with_entries(vec![
+            MemoryType::new(1, None),
+        ])));
+        sections.push(Section::Export(ExportSection::with_entries(vec![
+            ExportEntry::new("main".to_string(), Internal::Function(main_index)),
+            ExportEntry::new("memory".to_string(), Internal::Memory(0)),
+        ])));
+        sections.push(Section::Code(CodeSection::with_bodies(vec![
+            FuncBody::new(vec![], Instructions::new(vec![Instruction::End])),
+        ])));
+
+        Ok(Module::new(sections))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifyexports::VerifyExports;
+    use crate::verifyimports::VerifyImports;
+    use crate::ModuleValidator;
+
+    fn verifies_ewasm(module: &Module) -> bool {
+        let imports_valid = VerifyImports::with_preset("ewasm")
+            .unwrap()
+            .validate(module)
+            .unwrap();
+        let exports_valid = VerifyExports::with_preset("ewasm")
+            .unwrap()
+            .validate(module)
+            .unwrap();
+        imports_valid && exports_valid
+    }
+
+    #[test]
+    fn ewasm_stub_verifies() {
+        let module = Stub::with_preset("ewasm").unwrap().create().unwrap();
+        assert!(module.import_section().is_none());
+        assert!(verifies_ewasm(&module));
+
+        // The module survives serialization.
+        let serialized = module.to_bytes().unwrap();
+        assert!(Module::from_bytes(&serialized).is_ok());
+    }
+
+    #[test]
+    fn ewasm_stub_with_imports() {
+        let mut conf = HashMap::new();
+        conf.insert("preset".to_string(), "ewasm".to_string());
+        conf.insert("imports".to_string(), "useGas, finish".to_string());
+        let module = Stub::with_config(&conf).unwrap().create().unwrap();
+
+        let fields: Vec<&str> = module
+            .import_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.field())
+            .collect();
+        assert_eq!(fields, vec!["useGas", "finish"]);
+        assert!(verifies_ewasm(&module));
+    }
+
+    #[test]
+    fn unknown_host_function() {
+        assert!(Stub::with_imports("ewasm", &["foo"]).is_err());
+        assert!(Stub::with_preset("eth2").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::Module;
+
+use super::{ChiselModule, ModuleCreator, ModuleError, ModuleKind, ModulePreset};
+
+/// Enum on which ModuleCreator is implemented.
+/// Creates a minimal valid module for the selected interface, to be used as a starting point.
+pub enum Template {
+    EwasmStub,
+}
+
+impl<'a> ChiselModule<'a> for Template {
+    type ObjectReference = &'a dyn ModuleCreator;
+
+    fn id(&'a self) -> String {
+        "template".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Creator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        if let Some(preset) = config.get("preset") {
+            Template::with_preset(preset)
+        } else {
+            Err(ModuleError::NotSupported)
+        }
+    }
+}
+
+impl ModulePreset for Template {
+    fn with_preset(preset: &str) -> Result<Self, ModuleError> {
+        match preset {
+            "ewasm_stub" => Ok(Template::EwasmStub),
+            _ => Err(ModuleError::NotSupported),
+        }
+    }
+}
+
+/*
+(module
+  (import "ethereum" "finish" (func (param i32 i32)))
+  (memory 1)
+  (export "memory" (memory 0))
+  (export "main" (func 1))
+  (func
+    (call 0 (i32.const 0) (i32.const 0))
+  )
+)
+*/
+fn ewasm_stub_code() -> Vec<u8> {
+    vec![
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x09, 0x02, 0x60, 0x02, 0x7f, 0x7f,
+        0x00, 0x60, 0x00, 0x00, 0x02, 0x13, 0x01, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65, 0x75,
+        0x6d, 0x06, 0x66, 0x69, 0x6e, 0x69, 0x73, 0x68, 0x00, 0x00, 0x03, 0x02, 0x01, 0x01, 0x05,
+        0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02,
+        0x00, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a, 0x0a, 0x01, 0x08, 0x00, 0x41, 0x00,
+        0x41, 0x00, 0x10, 0x00, 0x0b,
+    ]
+}
+
+impl ModuleCreator for Template {
+    fn create(&self) -> Result<Module, ModuleError> {
+        match self {
+            Template::EwasmStub => Ok(Module::from_bytes(ewasm_stub_code())?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifyexports::VerifyExports;
+    use crate::verifyimports::VerifyImports;
+    use crate::ModuleValidator;
+
+    #[test]
+    fn ewasm_stub_verifies() {
+        let module = Template::with_preset("ewasm_stub")
+            .unwrap()
+            .create()
+            .unwrap();
+
+        let imports_valid = VerifyImports::with_preset("ewasm")
+            .unwrap()
+            .validate(&module)
+            .unwrap();
+        assert_eq!(true, imports_valid);
+
+        let exports_valid = VerifyExports::with_preset("ewasm")
+            .unwrap()
+            .validate(&module)
+            .unwrap();
+        assert_eq!(true, exports_valid);
+    }
+
+    #[test]
+    fn unknown_preset() {
+        assert!(Template::with_preset("foo").is_err());
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 
-use parity_wasm::elements::{ExportEntry, ExportSection, Internal, Module};
+use parity_wasm::elements::{ExportEntry, ExportSection, FunctionType, Internal, Module};
 
+use super::verifyexports::func_sig_by_index;
 use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleTranslator};
 
-/// Struct containing a list of valid exports.
+/// Struct containing a list of valid exports. Function entries may carry the signature the
+/// exported function must have.
 struct ExportWhitelist {
-    pub entries: Vec<ExportEntry>,
+    pub entries: Vec<(ExportEntry, Option<FunctionType>)>,
 }
 
 /// Wrapper struct implementing ModuleTranslator.
@@ -55,13 +57,21 @@ impl ModulePreset for ExportWhitelist {
         match preset {
             "ewasm" => Ok(ExportWhitelist {
                 entries: vec![
-                    //NOTE: function signatures are not checked yet
-                    ExportEntry::new("main".to_string(), Internal::Function(0)),
-                    ExportEntry::new("memory".to_string(), Internal::Memory(0)),
+                    (
+                        ExportEntry::new("main".to_string(), Internal::Function(0)),
+                        Some(FunctionType::default()),
+                    ),
+                    (
+                        ExportEntry::new("memory".to_string(), Internal::Memory(0)),
+                        None,
+                    ),
                 ],
             }),
             "pwasm" => Ok(ExportWhitelist {
-                entries: vec![ExportEntry::new("_call".to_string(), Internal::Function(0))],
+                entries: vec![(
+                    ExportEntry::new("_call".to_string(), Internal::Function(0)),
+                    None,
+                )],
             }),
             _ => Err(ModuleError::NotSupported),
         }
@@ -76,13 +86,21 @@ impl ExportWhitelist {
         }
     }
 
-    /// Looks up a given export entry in the whitelist and returns true if it is valid.
-    fn lookup(&self, export: &ExportEntry) -> bool {
+    /// Looks up a given export entry in the whitelist and returns true if it is valid. If the
+    /// whitelist specifies a signature, the signature of the exported function is resolved in the
+    /// module and compared.
+    fn lookup(&self, module: &Module, export: &ExportEntry) -> bool {
         self.entries
             .iter()
-            .find(|matched_export| {
+            .find(|(matched_export, sig)| {
                 export.field() == matched_export.field()
                     && cmp_internal_variant(export.internal(), matched_export.internal())
+                    && match (export.internal(), sig) {
+                        (Internal::Function(index), Some(sig)) => {
+                            func_sig_by_index(module, *index) == Some(sig)
+                        }
+                        _ => true,
+                    }
             })
             .is_some()
     }
@@ -92,25 +110,28 @@ impl TrimExports {
     /// Iterates over the export section, if there is one, and removes
     /// unnecessary entries.
     fn trim_exports(&self, module: &mut Module) -> bool {
-        if let Some(section) = module.export_section_mut() {
+        let new_section = if let Some(section) = module.export_section() {
             let new_section = ExportSection::with_entries(
                 section
                     .entries()
                     .iter()
                     .cloned()
-                    .filter(|entry| self.whitelist.lookup(entry))
+                    .filter(|entry| self.whitelist.lookup(module, entry))
                     .collect(),
             );
 
-            if new_section.entries().len() < section.entries().len() {
-                *section = new_section;
-                return true;
+            if new_section.entries().len() == section.entries().len() {
+                return false;
             }
-
-            false
+            new_section
         } else {
-            false
-        }
+            return false;
+        };
+
+        *module
+            .export_section_mut()
+            .expect("Export section checked above") = new_section;
+        true
     }
 }
 
@@ -148,6 +169,7 @@ impl ModuleTranslator for TrimExports {
 #[cfg(test)]
 mod tests {
     use parity_wasm::builder;
+    use parity_wasm::elements::ValueType;
 
     use super::*;
 
@@ -209,6 +231,59 @@ mod tests {
         assert_eq!(true, did_change);
     }
 
+    #[test]
+    fn builder_main_wrong_signature_ewasm() {
+        let mut module = builder::module()
+            .function()
+            .signature()
+            .with_param(ValueType::I32)
+            .build()
+            .body()
+            .build()
+            .build()
+            .export()
+            .field("main")
+            .internal()
+            .func(0)
+            .build()
+            .export()
+            .field("memory")
+            .internal()
+            .memory(0)
+            .build()
+            .build();
+
+        let trimmer = TrimExports::with_preset("ewasm").unwrap();
+        let did_change = trimmer.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let exports = module.export_section().unwrap().entries();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].field(), "memory");
+    }
+
+    #[test]
+    fn builder_unchecked_signature_pwasm() {
+        let mut module = builder::module()
+            .function()
+            .signature()
+            .with_param(ValueType::I32)
+            .build()
+            .body()
+            .build()
+            .build()
+            .export()
+            .field("_call")
+            .internal()
+            .func(0)
+            .build()
+            .build();
+
+        let trimmer = TrimExports::with_preset("pwasm").unwrap();
+        let did_change = trimmer.translate_inplace(&mut module).unwrap();
+        assert_eq!(false, did_change);
+    }
+
     #[test]
     fn builder_no_export_ewasm() {
         let mut module = builder::module()
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+
+use parity_wasm::elements::{ImportCountType, Instruction, Module};
+
+use super::depgraph::reachable_functions;
+use super::{
+    funcindex::remove_imported_functions, ChiselModule, ModuleError, ModuleKind, ModuleTranslator,
+};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Removes imported functions which are never referenced: not called by any function body, nor
+/// exported, nor the start function, nor a member of an element segment. The remaining functions
+/// are renumbered.
+pub struct TrimImports {}
+
+impl<'a> ChiselModule<'a> for TrimImports {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "trimimports".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(TrimImports {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for TrimImports {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(trim_imports(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if trim_imports(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the indices of the imported functions which are never referenced, in ascending order.
+pub fn unused_imports(module: &Module) -> Vec<u32> {
+    let imports_len = module.import_count(ImportCountType::Function) as u32;
+
+    // Everything reachable from the roots, plus any direct call: a function which is itself dead
+    // may still call an import, and removing that import would leave a dangling reference.
+    let mut referenced: HashSet<u32> = reachable_functions(module);
+    if let Some(code) = module.code_section() {
+        for body in code.bodies() {
+            for instruction in body.code().elements() {
+                if let Instruction::Call(idx) = instruction {
+                    referenced.insert(*idx);
+                }
+            }
+        }
+    }
+
+    (0..imports_len)
+        .filter(|idx| !referenced.contains(idx))
+        .collect()
+}
+
+fn trim_imports(module: &mut Module) -> bool {
+    let removed = unused_imports(module);
+    if removed.is_empty() {
+        return false;
+    }
+
+    remove_imported_functions(module, &removed);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{External, Internal};
+
+    use super::*;
+
+    #[test]
+    fn unused_use_gas_removed() {
+        // wast:
+        // (module
+        //   (import "ethereum" "useGas" (func (param i64)))
+        //   (import "ethereum" "getGasLeft" (func (result i64)))
+        //   (import "ethereum" "finish" (func (param i32 i32)))
+        //   (memory 1)
+        //   (table 1 funcref)
+        //   (elem (i32.const 0) $callback)
+        //   (start $init)
+        //   (func $init)
+        //   (func $callback
+        //     call 1
+        //     drop)
+        //   (func $main (export "main")
+        //     i32.const 0
+        //     i32.const 0
+        //     call 2
+        //     call $callback)
+        //   (export "memory" (memory 0))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x11, 0x04, 0x60, 0x01, 0x7e,
+            0x00, 0x60, 0x00, 0x01, 0x7e, 0x60, 0x02, 0x7f, 0x7f, 0x00, 0x60, 0x00, 0x00, 0x02,
+            0x3b, 0x03, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65, 0x75, 0x6d, 0x06, 0x75, 0x73,
+            0x65, 0x47, 0x61, 0x73, 0x00, 0x00, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65, 0x75,
+            0x6d, 0x0a, 0x67, 0x65, 0x74, 0x47, 0x61, 0x73, 0x4c, 0x65, 0x66, 0x74, 0x00, 0x01,
+            0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65, 0x75, 0x6d, 0x06, 0x66, 0x69, 0x6e, 0x69,
+            0x73, 0x68, 0x00, 0x02, 0x03, 0x04, 0x03, 0x03, 0x03, 0x03, 0x04, 0x04, 0x01, 0x70,
+            0x00, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d, 0x61, 0x69,
+            0x6e, 0x00, 0x05, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x08, 0x01,
+            0x03, 0x09, 0x07, 0x01, 0x00, 0x41, 0x00, 0x0b, 0x01, 0x04, 0x0a, 0x15, 0x03, 0x02,
+            0x00, 0x0b, 0x05, 0x00, 0x10, 0x01, 0x1a, 0x0b, 0x0a, 0x00, 0x41, 0x00, 0x41, 0x00,
+            0x10, 0x02, 0x10, 0x04, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        assert_eq!(unused_imports(&module), vec![0]);
+
+        let translator = TrimImports::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let imports: Vec<&str> = module
+            .import_section()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.field())
+            .collect();
+        assert_eq!(imports, vec!["getGasLeft", "finish"]);
+        match module.import_section().unwrap().entries()[0].external() {
+            External::Function(type_ref) => assert_eq!(*type_ref, 1),
+            _ => panic!("Import must be a function"),
+        }
+
+        // Every later function moves down by one.
+        assert_eq!(module.start_section(), Some(2));
+        assert_eq!(
+            module.elements_section().unwrap().entries()[0].members(),
+            &[3]
+        );
+        let bodies = module.code_section().unwrap().bodies();
+        assert_eq!(bodies[1].code().elements()[0], Instruction::Call(0));
+        assert_eq!(bodies[2].code().elements()[2], Instruction::Call(1));
+        assert_eq!(bodies[2].code().elements()[3], Instruction::Call(3));
+
+        let reparsed = Module::from_bytes(&module.clone().to_bytes().unwrap()).unwrap();
+        let main = reparsed.export_section().unwrap().entries()[0].internal();
+        assert_eq!(*main, Internal::Function(4));
+
+        // Trimming again is a no-op.
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn import_called_from_dead_code_kept() {
+        // wast:
+        // (module
+        //   (import "ethereum" "useGas" (func (param i64)))
+        //   (func $unused
+        //     i64.const 1
+        //     call 0)
+        //   (func $main (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7e,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x13, 0x01, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65,
+            0x75, 0x6d, 0x06, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x00, 0x03, 0x03, 0x02,
+            0x01, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x02, 0x0a, 0x0b,
+            0x02, 0x06, 0x00, 0x42, 0x01, 0x10, 0x00, 0x0b, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = TrimImports::with_defaults().unwrap();
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{ImportCountType, Instruction, Module};
+
+use super::depgraph::reachable_functions;
+use super::{funcindex::remove_functions, ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Removes functions whose body consists of nothing but `unreachable`, as left behind by `snip`,
+/// if they cannot be reached from an export, the start function or the table and no remaining
+/// function calls them. The remaining functions are renumbered.
+pub struct TrimUnreachable {}
+
+impl<'a> ChiselModule<'a> for TrimUnreachable {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "trimunreachable".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(TrimUnreachable {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for TrimUnreachable {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(trim_unreachable(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if trim_unreachable(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn trim_unreachable(module: &mut Module) -> bool {
+    let imports_len = module.import_count(ImportCountType::Function) as u32;
+    let reachable = reachable_functions(module);
+
+    let code = if let Some(code) = module.code_section() {
+        code
+    } else {
+        return false;
+    };
+
+    let mut removed: Vec<u32> = code
+        .bodies()
+        .iter()
+        .enumerate()
+        .filter(|(_, body)| body.code().elements() == [Instruction::Unreachable, Instruction::End])
+        .map(|(code_idx, _)| imports_len + code_idx as u32)
+        .filter(|idx| !reachable.contains(idx))
+        .collect();
+
+    // Dead functions which are kept may still call a candidate, so keep every candidate called
+    // from a function outside the removed set, until the set stops changing.
+    loop {
+        let called: Vec<u32> = code
+            .bodies()
+            .iter()
+            .enumerate()
+            .filter(|(code_idx, _)| !removed.contains(&(imports_len + *code_idx as u32)))
+            .flat_map(|(_, body)| body.code().elements().iter())
+            .filter_map(|instruction| match instruction {
+                Instruction::Call(idx) => Some(*idx),
+                _ => None,
+            })
+            .collect();
+        let len_before = removed.len();
+        removed.retain(|idx| !called.contains(idx));
+        if removed.len() == len_before {
+            break;
+        }
+    }
+
+    if removed.is_empty() {
+        return false;
+    }
+
+    remove_functions(module, &removed);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreachable_only_function_removed() {
+        // wast:
+        // (module
+        //   (import "env" "foo" (func))
+        //   (func (export "main")
+        //     call 0
+        //     call 3)
+        //   (func
+        //     unreachable)
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0b, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x66, 0x6f, 0x6f, 0x00, 0x00, 0x03,
+            0x04, 0x03, 0x00, 0x00, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00,
+            0x01, 0x0a, 0x0f, 0x03, 0x06, 0x00, 0x10, 0x00, 0x10, 0x03, 0x0b, 0x03, 0x00, 0x00,
+            0x0b, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = TrimUnreachable::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let bodies = module.code_section().unwrap().bodies();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(module.function_section().unwrap().entries().len(), 2);
+        assert_eq!(
+            bodies[0].code().elements(),
+            &[Instruction::Call(0), Instruction::Call(2), Instruction::End]
+        );
+        assert!(walrus::Module::from_buffer(&module.to_bytes().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn reachable_unreachable_only_function_kept() {
+        // wast:
+        // (module
+        //   (func (export "main")
+        //     call 1)
+        //   (func
+        //     unreachable)
+        //   (func (export "other")
+        //     unreachable)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x04, 0x03, 0x00, 0x00, 0x00, 0x07, 0x10, 0x02, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+            0x00, 0x00, 0x05, 0x6f, 0x74, 0x68, 0x65, 0x72, 0x00, 0x02, 0x0a, 0x0e, 0x03, 0x04,
+            0x00, 0x10, 0x01, 0x0b, 0x03, 0x00, 0x00, 0x0b, 0x03, 0x00, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = TrimUnreachable::with_defaults().unwrap();
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+
+    #[test]
+    fn called_from_dead_function_kept() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        //   (func
+        //     unreachable)
+        //   (func
+        //     call 1)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x04, 0x03, 0x00, 0x00, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+            0x00, 0x00, 0x0a, 0x0d, 0x03, 0x02, 0x00, 0x0b, 0x03, 0x00, 0x00, 0x0b, 0x04, 0x00,
+            0x10, 0x01, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = TrimUnreachable::with_defaults().unwrap();
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+}
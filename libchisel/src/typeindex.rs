@@ -0,0 +1,98 @@
+//! Helpers for keeping type references consistent when types are added, removed or merged.
+
+use parity_wasm::elements::{
+    External, FunctionType, Instruction, Module, Section, Type, TypeSection,
+};
+
+/// Returns the index of the given signature, adding it to the type section if it is not present.
+pub(crate) fn signature_type_ref(module: &mut Module, signature: FunctionType) -> u32 {
+    if module.type_section().is_none() {
+        module
+            .insert_section(Section::Type(TypeSection::default()))
+            .expect("insert_section should not fail");
+    }
+
+    let types = module
+        .type_section_mut()
+        .expect("Type section exists")
+        .types_mut();
+    if let Some(index) = types.iter().position(|Type::Function(ty)| *ty == signature) {
+        index as u32
+    } else {
+        types.push(Type::Function(signature));
+        (types.len() - 1) as u32
+    }
+}
+
+/// Rewrites every type reference, in the import and function sections and in `call_indirect`
+/// instructions, from its old index to `remap[old]`. References outside `remap` are dangling and
+/// are left unchanged. The type section itself is left untouched.
+pub(crate) fn renumber_type_refs(module: &mut Module, remap: &[u32]) {
+    let remap_idx = |idx: u32| remap.get(idx as usize).cloned().unwrap_or(idx);
+
+    if let Some(section) = module.import_section_mut() {
+        for entry in section.entries_mut() {
+            if let External::Function(type_ref) = entry.external_mut() {
+                *type_ref = remap_idx(*type_ref);
+            }
+        }
+    }
+    if let Some(section) = module.function_section_mut() {
+        for func in section.entries_mut() {
+            *func.type_ref_mut() = remap_idx(func.type_ref());
+        }
+    }
+    if let Some(section) = module.code_section_mut() {
+        for body in section.bodies_mut() {
+            for instruction in body.code_mut().elements_mut() {
+                if let Instruction::CallIndirect(type_ref, _) = instruction {
+                    *type_ref = remap_idx(*type_ref);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::Instructions;
+
+    use super::*;
+
+    #[test]
+    fn dangling_call_indirect_unchanged() {
+        let mut module = builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(0),
+                Instruction::CallIndirect(0, 0),
+                Instruction::I32Const(0),
+                Instruction::CallIndirect(7, 0),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .build();
+
+        renumber_type_refs(&mut module, &[1]);
+
+        assert_eq!(
+            module.function_section().unwrap().entries()[0].type_ref(),
+            1
+        );
+        assert_eq!(
+            module.code_section().unwrap().bodies()[0].code().elements(),
+            &[
+                Instruction::I32Const(0),
+                Instruction::CallIndirect(1, 0),
+                Instruction::I32Const(0),
+                Instruction::CallIndirect(7, 0),
+                Instruction::End,
+            ]
+        );
+    }
+}
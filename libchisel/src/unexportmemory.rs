@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{Internal, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Struct on which ModuleTranslator is implemented.
+/// Removes the `memory` export, for artifacts whose memory is provided by the host. The memory
+/// itself, whether defined or imported, is kept.
+pub struct UnexportMemory {
+    unexport_memory: bool,
+}
+
+impl UnexportMemory {
+    pub fn new(unexport_memory: bool) -> Self {
+        UnexportMemory { unexport_memory }
+    }
+}
+
+impl<'a> ChiselModule<'a> for UnexportMemory {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "unexportmemory".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(UnexportMemory {
+            unexport_memory: true,
+        })
+    }
+
+    fn with_config(config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        let unexport_memory = if let Some(value) = config.get("unexport_memory") {
+            value == "true"
+        } else {
+            true
+        };
+        Ok(UnexportMemory { unexport_memory })
+    }
+}
+
+impl UnexportMemory {
+    fn unexport(&self, module: &mut Module) -> bool {
+        if !self.unexport_memory {
+            return false;
+        }
+
+        if let Some(section) = module.export_section_mut() {
+            let entries = section.entries_mut();
+            let len_before = entries.len();
+            entries.retain(|entry| match entry.internal() {
+                Internal::Memory(_) => entry.field() != "memory",
+                _ => true,
+            });
+            entries.len() != len_before
+        } else {
+            false
+        }
+    }
+}
+
+impl ModuleTranslator for UnexportMemory {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        Ok(self.unexport(module))
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if self.unexport(&mut ret) {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defined_memory_unexported() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let mut config = HashMap::new();
+        config.insert("unexport_memory".to_string(), "true".to_string());
+        let translator = UnexportMemory::with_config(&config).unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let exports = module.export_section().unwrap().entries();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].field(), "main");
+        assert_eq!(module.memory_section().unwrap().entries().len(), 1);
+    }
+
+    #[test]
+    fn imported_memory_unexported() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 1))
+        //   (export "memory" (memory 0))
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x01, 0x03, 0x02, 0x01, 0x00, 0x07, 0x11, 0x02, 0x06, 0x6d, 0x65, 0x6d,
+            0x6f, 0x72, 0x79, 0x02, 0x00, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04,
+            0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = UnexportMemory::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let exports = module.export_section().unwrap().entries();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].field(), "main");
+        assert_eq!(module.import_section().unwrap().entries().len(), 1);
+        assert_eq!(module.memory_space(), 1);
+    }
+
+    #[test]
+    fn unexport_disabled() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 1))
+        //   (export "memory" (memory 0))
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x01, 0x03, 0x02, 0x01, 0x00, 0x07, 0x11, 0x02, 0x06, 0x6d, 0x65, 0x6d,
+            0x6f, 0x72, 0x79, 0x02, 0x00, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04,
+            0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = UnexportMemory::new(false);
+        let result = translator.translate(&module).unwrap();
+        assert!(result.is_none());
+    }
+}
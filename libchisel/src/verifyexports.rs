@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
 use parity_wasm::elements::{
-    ExportSection, External, FunctionSection, FunctionType, ImportSection, Internal, Module, Type,
+    ExportEntry, ExportSection, External, FunctionSection, FunctionType, ImportSection, Internal,
+    Module, Type,
 };
 
 use super::{ChiselModule, ModuleError, ModuleKind, ModulePreset, ModuleValidator};
@@ -14,9 +15,10 @@ pub enum ExportType<'a> {
     Table(&'a str),
 }
 
-/// Trait over ExportType that lets a caller check if it is exported in a given module.
+/// Trait over ExportType that lets a caller check if it is exported in a given module, given the
+/// module's exports by name.
 trait IsExported {
-    fn is_exported(&self, module: &Module) -> bool;
+    fn is_exported(&self, module: &Module, exports: &HashMap<&str, &ExportEntry>) -> bool;
 }
 
 /// Struct on which ModuleValidator is implemented.
@@ -63,22 +65,49 @@ impl<'a> ModulePreset for VerifyExports<'a> {
                 ],
                 allow_unlisted: false,
             }),
+            // Eth2 execution environments only export their entry point; the memory is accessed
+            // by the host through the imported functions.
+            "eth2" => Ok(VerifyExports {
+                entries: vec![ExportType::Function("main", FunctionType::default())],
+                allow_unlisted: false,
+            }),
             _ => Err(ModuleError::NotSupported),
         }
     }
 }
 
+impl<'a> VerifyExports<'a> {
+    /// Builds a validator whose entries are exactly the current exports of the given module, with
+    /// function signatures resolved. Unlisted exports are not allowed.
+    pub fn from_module(module: &'a Module) -> Result<Self, ModuleError> {
+        let mut entries = Vec::new();
+        if let Some(section) = module.export_section() {
+            for export in section.entries() {
+                entries.push(match export.internal() {
+                    Internal::Function(index) => {
+                        let sig = func_sig_by_index(module, *index).ok_or(ModuleError::NotFound)?;
+                        ExportType::Function(export.field(), sig.clone())
+                    }
+                    Internal::Global(_) => ExportType::Global(export.field()),
+                    Internal::Memory(_) => ExportType::Memory(export.field()),
+                    Internal::Table(_) => ExportType::Table(export.field()),
+                });
+            }
+        }
+
+        Ok(VerifyExports {
+            entries,
+            allow_unlisted: false,
+        })
+    }
+}
+
 impl<'a> ModuleValidator for VerifyExports<'a> {
     fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
-        // FIXME: This validating algorithm runs in O(n^2). Needs to be optimized
-        let required_exports_not_found = self
-            .entries
-            .iter()
-            .map(|e| e.is_exported(module))
-            .find(|e| *e == false)
-            .is_some();
+        let exports = exports_by_name(module);
+        let required_exports_found = self.entries.iter().all(|e| e.is_exported(module, &exports));
 
-        if required_exports_not_found {
+        if !required_exports_found {
             return Ok(false);
         }
 
@@ -96,66 +125,51 @@ impl<'a> ModuleValidator for VerifyExports<'a> {
     }
 }
 
-impl<'a> IsExported for ExportType<'a> {
-    fn is_exported(&self, module: &Module) -> bool {
-        if let Some(section) = module.export_section() {
-            match self {
-                ExportType::Function(field, sig) => has_func_export(module, field, sig),
-                ExportType::Global(field) => has_global_export(section, field),
-                ExportType::Memory(field) => has_memory_export(section, field),
-                ExportType::Table(field) => has_table_export(section, field),
-            }
-        } else {
-            false
-        }
-    }
-}
-
-// NOTE: has_*_export is implemented with repeating code because you can't implement a trait for
-// enum variants, as they are not types. Furthermore, having one helper for non-func exports would
-// be ugly because information about the export type must still be passed down to check that an
-// export is of the correct kind.
-
-/// Checks if a global is exported with the given name.
-fn has_global_export(section: &ExportSection, field: &str) -> bool {
-    if let Some(ref export) = section.entries().iter().find(|e| e.field() == field) {
-        match export.internal() {
-            Internal::Global(_index) => true,
-            _ => false,
+impl<'a> ExportType<'a> {
+    fn field(&self) -> &'a str {
+        match self {
+            ExportType::Function(field, _)
+            | ExportType::Global(field)
+            | ExportType::Memory(field)
+            | ExportType::Table(field) => field,
         }
-    } else {
-        false
     }
 }
 
-/// Checks if a memory is exported with the given name.
-fn has_memory_export(section: &ExportSection, field: &str) -> bool {
-    if let Some(ref export) = section.entries().iter().find(|e| e.field() == field) {
-        match export.internal() {
-            Internal::Memory(_index) => true,
+impl<'a> IsExported for ExportType<'a> {
+    fn is_exported(&self, module: &Module, exports: &HashMap<&str, &ExportEntry>) -> bool {
+        let export = match exports.get(self.field()) {
+            Some(export) => export,
+            None => return false,
+        };
+        match (self, export.internal()) {
+            (ExportType::Function(_, sig), Internal::Function(index)) => {
+                func_sig_by_index(module, *index) == Some(sig)
+            }
+            (ExportType::Global(_), Internal::Global(_))
+            | (ExportType::Memory(_), Internal::Memory(_))
+            | (ExportType::Table(_), Internal::Table(_)) => true,
             _ => false,
         }
-    } else {
-        false
     }
 }
 
-/// Checks if a table is exported with the given name.
-fn has_table_export(section: &ExportSection, field: &str) -> bool {
-    if let Some(ref export) = section.entries().iter().find(|e| e.field() == field) {
-        match export.internal() {
-            Internal::Table(_index) => true,
-            _ => false,
+/// Maps the module's export names to their entries. If a name is exported more than once, the
+/// first entry wins.
+fn exports_by_name(module: &Module) -> HashMap<&str, &ExportEntry> {
+    let mut exports = HashMap::new();
+    if let Some(section) = module.export_section() {
+        for entry in section.entries() {
+            exports.entry(entry.field()).or_insert(entry);
         }
-    } else {
-        false
     }
+    exports
 }
 
 // NOTE: this is kind of hacked on. It works, but a refactor would make it more in line with the other
 // helpers.
 /// Checks if a function is exported with the given name.
-fn has_func_export(module: &Module, field: &str, sig: &FunctionType) -> bool {
+pub(crate) fn has_func_export(module: &Module, field: &str, sig: &FunctionType) -> bool {
     if let Some(section) = module.export_section() {
         match func_export_index_by_name(section, field) {
             Some(index) => {
@@ -173,28 +187,27 @@ fn has_func_export(module: &Module, field: &str, sig: &FunctionType) -> bool {
 }
 
 /// Resolves a function's signature from its internal index.
-fn func_sig_by_index(module: &Module, index: u32) -> Option<&FunctionType> {
-    if let Some(func_section) = module.function_section() {
-        match (module.type_section(), module.import_section()) {
-            // If we have function imports in the section, subtract them from the function's index
-            // because their signatures are not in the types section.
-            (Some(type_section), Some(import_section)) => match type_section.types()[func_type_ref(
-                &func_section,
-                index - func_import_section_len(import_section),
-            )] {
-                Type::Function(ref ret) => Some(ret),
-            },
-            // If no function imports are present, no need to subtract them.
-            (Some(type_section), None) => {
-                match type_section.types()[func_type_ref(&func_section, index)] {
-                    Type::Function(ref ret) => Some(ret),
-                }
-            }
-            (None, Some(_import_section)) => None,
-            (None, None) => None,
-        }
+pub(crate) fn func_sig_by_index(module: &Module, index: u32) -> Option<&FunctionType> {
+    let type_section = module.type_section()?;
+    let imported_funcs = module.import_section().map_or(0, func_import_section_len);
+
+    // Imported functions come first in the index space and carry their own type reference.
+    let type_ref = if index < imported_funcs {
+        module
+            .import_section()?
+            .entries()
+            .iter()
+            .filter_map(|e| match e.external() {
+                External::Function(type_ref) => Some(*type_ref as usize),
+                _ => None,
+            })
+            .nth(index as usize)?
     } else {
-        None
+        func_type_ref(module.function_section()?, index - imported_funcs)
+    };
+
+    match type_section.types().get(type_ref)? {
+        Type::Function(ref ret) => Some(ret),
     }
 }
 
@@ -230,6 +243,9 @@ fn func_export_index_by_name(exports: &ExportSection, field_str: &str) -> Option
 
 #[cfg(test)]
 mod tests {
+    use parity_wasm::builder;
+    use parity_wasm::elements::ValueType;
+
     use super::*;
 
     #[test]
@@ -471,4 +487,185 @@ mod tests {
         let result = checker.validate(&module).unwrap();
         assert_eq!(true, result);
     }
+
+    #[test]
+    fn from_module_round_trip() {
+        // wast:
+        // (module
+        //   (import "ethereum" "useGas" (func (param i64)))
+        //   (import "env" "memory" (memory 1))
+        //   (global (mut i32) (i32.const 0))
+        //   (export "main" (func 1))
+        //   (export "useGas" (func 0))
+        //   (export "memory" (memory 0))
+        //   (export "counter" (global 0))
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7e,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x21, 0x02, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65,
+            0x75, 0x6d, 0x06, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x00, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x01, 0x03, 0x02, 0x01,
+            0x01, 0x06, 0x06, 0x01, 0x7f, 0x01, 0x41, 0x00, 0x0b, 0x07, 0x24, 0x04, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x01, 0x06, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x00,
+            0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x07, 0x63, 0x6f, 0x75, 0x6e,
+            0x74, 0x65, 0x72, 0x03, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let spec = VerifyExports::from_module(&module).unwrap();
+        assert_eq!(spec.entries.len(), 4);
+        assert_eq!(true, spec.validate(&module).unwrap());
+
+        // The same spec must reject a module with a different export surface.
+        let other = Module::from_bytes([0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(false, spec.validate(&other).unwrap());
+    }
+
+    #[test]
+    fn hundred_exports() {
+        let mut module_builder = builder::module();
+        for _ in 0..100 {
+            module_builder = module_builder
+                .function()
+                .signature()
+                .build()
+                .body()
+                .build()
+                .build();
+        }
+        let names: Vec<String> = (0..100).map(|i| format!("f{}", i)).collect();
+        for (index, name) in names.iter().enumerate() {
+            module_builder = module_builder
+                .export()
+                .field(name)
+                .internal()
+                .func(index as u32)
+                .build();
+        }
+        let module = module_builder.build();
+
+        let mut checker = VerifyExports {
+            entries: names
+                .iter()
+                .map(|name| ExportType::Function(name, FunctionType::default()))
+                .collect(),
+            allow_unlisted: false,
+        };
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        // One export left unlisted.
+        checker.entries.pop();
+        assert_eq!(false, checker.validate(&module).unwrap());
+        checker.allow_unlisted = true;
+        assert_eq!(true, checker.validate(&module).unwrap());
+
+        // The last export listed with the wrong kind or signature.
+        checker.entries.push(ExportType::Global("f99"));
+        assert_eq!(false, checker.validate(&module).unwrap());
+        checker.entries.pop();
+        checker.entries.push(ExportType::Function(
+            "f99",
+            FunctionType::new(vec![], Some(ValueType::I32)),
+        ));
+        assert_eq!(false, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn main_only_eth2() {
+        // wast:
+        // (module
+        //   (import "eth2" "loadPreStateRoot" (func $loadPreStateRoot (param i32)))
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x19, 0x01, 0x04, 0x65, 0x74, 0x68, 0x32, 0x10, 0x6c,
+            0x6f, 0x61, 0x64, 0x50, 0x72, 0x65, 0x53, 0x74, 0x61, 0x74, 0x65, 0x52, 0x6f, 0x6f,
+            0x74, 0x00, 0x00, 0x03, 0x02, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08,
+            0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyExports::with_preset("eth2").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+
+        // The ewasm preset additionally requires the memory export.
+        let checker = VerifyExports::with_preset("ewasm").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn missing_main_export_eth2() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "_main" (func $main))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x09, 0x01, 0x05, 0x5f,
+            0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyExports::with_preset("eth2").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn memory_export_disallowed_eth2() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyExports::with_preset("eth2").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn opaque_module() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyExports::with_preset("ewasm").unwrap();
+        assert_eq!(checker.id(), "verifyexports");
+
+        let opaque: &dyn ChiselModule<ObjectReference = &dyn ModuleValidator> =
+            &checker as &dyn ChiselModule<ObjectReference = &dyn ModuleValidator>;
+        assert_eq!(opaque.kind(), ModuleKind::Validator);
+
+        let as_trait: &dyn ModuleValidator = opaque.as_abstract();
+        assert_eq!(as_trait.validate(&module), Ok(true));
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use parity_wasm::elements::{External, FunctionType, ImportSection, Module, Type};
+use parity_wasm::elements::{External, FunctionType, Module, Type};
 
 use super::{
     imports::{ImportList, ImportType},
@@ -8,17 +8,13 @@ use super::{
 };
 
 /// Enum representing the state of an import in a module.
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ImportStatus {
     Good,
     NotFound,
     Malformed,
-}
-
-/// Trait over ImportType that lets a caller check if it is imported in a given module, and
-/// verifies its type signature is correct.
-trait IsImported {
-    fn is_imported(&self, module: &Module) -> bool;
+    /// Imported by the module, but not on the list.
+    Unlisted,
 }
 
 /// Trait over ImportType that checks an import's type signature in the case that it is imported.
@@ -70,7 +66,7 @@ impl<'a> ModulePreset for VerifyImports<'a> {
         let mut import_set = ImportList::new();
         let presets: String = preset
             .chars()
-            .filter(|c| *c != ' ' && *c != '_' && *c != '\n' && *c != '\t')
+            .filter(|c| *c != ' ' && *c != '\n' && *c != '\t')
             .collect();
 
         for preset_individual in presets.split(',') {
@@ -86,6 +82,39 @@ impl<'a> ModulePreset for VerifyImports<'a> {
     }
 }
 
+impl<'a> VerifyImports<'a> {
+    /// Builds a validator whose list is exactly the current imports of the given module, with
+    /// function signatures resolved. All listed imports are required and no others are allowed.
+    pub fn from_module(module: &'a Module) -> Result<Self, ModuleError> {
+        let mut entries = Vec::new();
+        if let Some(section) = module.import_section() {
+            for e in section.entries() {
+                entries.push(match e.external() {
+                    External::Function(idx) => ImportType::Function(
+                        e.module(),
+                        e.field(),
+                        imported_func_sig_by_index(module, *idx as usize)
+                            .ok_or(ModuleError::NotFound)?,
+                    ),
+                    External::Global(global_type) => {
+                        ImportType::Global(e.module(), e.field(), *global_type)
+                    }
+                    External::Memory(memory_type) => {
+                        ImportType::Memory(e.module(), e.field(), *memory_type)
+                    }
+                    External::Table(_) => ImportType::Table(e.module(), e.field()),
+                });
+            }
+        }
+
+        Ok(VerifyImports {
+            list: ImportList::with_entries(entries),
+            require_all: true,
+            allow_unlisted: false,
+        })
+    }
+}
+
 // Utility functions used in tests to get more coverage
 #[cfg(test)]
 impl<'a> VerifyImports<'a> {
@@ -98,138 +127,100 @@ impl<'a> VerifyImports<'a> {
     }
 }
 
-impl<'a> ModuleValidator for VerifyImports<'a> {
-    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
-        let import_section_len = if let Some(section) = module.import_section() {
-            section.entries().len()
-        } else {
-            0
-        };
+impl<'a> VerifyImports<'a> {
+    /// Reports the status of every listed import as a (module, field, status) triple, in list
+    /// order. Unless unlisted imports are allowed, each import of the module which is not on the
+    /// list follows as `ImportStatus::Unlisted`.
+    pub fn validate_verbose(
+        &self,
+        module: &Module,
+    ) -> Result<Vec<(String, String, ImportStatus)>, ModuleError> {
+        let mut report: Vec<(String, String, ImportStatus)> = self
+            .list
+            .entries()
+            .iter()
+            .map(|e| {
+                (
+                    e.module().to_string(),
+                    e.field().to_string(),
+                    e.check(module),
+                )
+            })
+            .collect();
 
-        Ok(match (self.require_all, self.allow_unlisted) {
-            // Check that all listed imports exist and are correct.
-            (true, true) => self
-                .list
-                .entries()
-                .iter()
-                .map(|e| e.is_imported(module))
-                .find(|e| *e == false)
-                .is_none(),
-            // Check that all listed imports exist, are correct, and are the only imports in the
-            // module.
-            (true, false) => {
-                self.list
-                    .entries()
-                    .iter()
-                    .map(|e| e.is_imported(module))
-                    .find(|e| *e == false)
-                    .is_none()
-                    && (self.list.entries().len() == import_section_len)
-            }
-            // Check that the imports which are both listed and imported are of correct type.
-            (false, true) => self
-                .list
-                .entries()
-                .iter()
-                .map(|e| e.check(module))
-                .find(|e| *e == ImportStatus::Malformed)
-                .is_none(),
-            (false, false) => {
-                // Check that all existent imports are listed and correct.
-                let checklist: Vec<ImportStatus> = self
+        if !self.allow_unlisted {
+            if let Some(section) = module.import_section() {
+                // Only the first import with a listed name is checked against the list, so any
+                // repeated import counts as unlisted.
+                let mut listed: Vec<(&str, &str)> = self
                     .list
                     .entries()
                     .iter()
-                    .map(|e| e.check(module))
+                    .map(|e| (e.module(), e.field()))
                     .collect();
-                let valid_entries_count = checklist
-                    .iter()
-                    .filter(|e| **e == ImportStatus::Good)
-                    .count();
-
-                // Proof: If the number of valid entries is equal to the number of existing entries, all
-                // entries are valid.
-                //
-                // If an import entry is valid, it exists; If the number of existent imports is
-                // equal to the number of valid imports, all existent imports are valid; If all
-                // existent imports are valid, there are no existent invalid imports; qed
-                valid_entries_count == import_section_len
+                for entry in section.entries() {
+                    match listed
+                        .iter()
+                        .position(|(m, f)| *m == entry.module() && *f == entry.field())
+                    {
+                        Some(position) => {
+                            listed.remove(position);
+                        }
+                        None => report.push((
+                            entry.module().to_string(),
+                            entry.field().to_string(),
+                            ImportStatus::Unlisted,
+                        )),
+                    }
+                }
             }
-        })
+        }
+
+        Ok(report)
     }
 }
 
-impl<'a> IsImported for ImportType<'a> {
-    fn is_imported(&self, module: &Module) -> bool {
-        if let Some(section) = module.import_section() {
-            match self {
-                ImportType::Function(namespace, field, sig) => {
-                    has_func_import(module, namespace, field, sig)
-                }
-                ImportType::Global(namespace, field) => {
-                    has_global_import(section, namespace, field)
-                }
-                ImportType::Memory(namespace, field) => {
-                    has_memory_import(section, namespace, field)
-                }
-                ImportType::Table(namespace, field) => has_table_import(section, namespace, field),
-            }
-        } else {
-            false
-        }
+impl<'a> ModuleValidator for VerifyImports<'a> {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        Ok(self
+            .validate_verbose(module)?
+            .iter()
+            .all(|(_, _, status)| match status {
+                ImportStatus::Good => true,
+                // Listed imports may be missing unless all of them are required.
+                ImportStatus::NotFound => !self.require_all,
+                ImportStatus::Malformed | ImportStatus::Unlisted => false,
+            }))
     }
 }
 
 impl<'a> ImportCheck for ImportType<'a> {
     fn check(&self, module: &Module) -> ImportStatus {
-        // Destructure self here so that it is easier to manipulate individual fields later.
-        let (module_str, field_str, func_sig) = match self {
-            ImportType::Function(namespace, field, sig) => (namespace, field, Some(sig)),
-            ImportType::Global(namespace, field) => (namespace, field, None),
-            ImportType::Memory(namespace, field) => (namespace, field, None),
-            ImportType::Table(namespace, field) => (namespace, field, None),
-        };
-
         if let Some(section) = module.import_section() {
-            // Find an entry that matches self. If the name matches, check the namespace and/or
-            // signature.
+            // Find an entry that matches self. If the name matches, check the kind and the
+            // signature, global type or memory limits.
             if let Some(entry) = section
                 .entries()
                 .iter()
-                .find(|e| e.field() == *field_str && *module_str == e.module())
+                .find(|e| e.field() == self.field() && self.module() == e.module())
             {
-                match entry.external() {
-                    External::Function(idx) => {
-                        let sig = func_sig.expect("Function entry missing signature!");
-                        if *sig == imported_func_sig_by_index(module, *idx as usize) {
-                            ImportStatus::Good
-                        } else {
-                            ImportStatus::Malformed
-                        }
+                let good = match (self, entry.external()) {
+                    (ImportType::Function(_, _, sig), External::Function(idx)) => {
+                        imported_func_sig_by_index(module, *idx as usize).as_ref() == Some(sig)
                     }
-                    // NOTE: There may be a better way to do mappings between enum variants.
-                    // Just check import variant here.
-                    External::Global(_idx) => {
-                        if let ImportType::Global(_n, _f) = self {
-                            ImportStatus::Good
-                        } else {
-                            ImportStatus::Malformed
-                        }
+                    (ImportType::Global(_, _, expected), External::Global(actual)) => {
+                        expected == actual
                     }
-                    External::Memory(_idx) => {
-                        if let ImportType::Memory(_n, _f) = self {
-                            ImportStatus::Good
-                        } else {
-                            ImportStatus::Malformed
-                        }
-                    }
-                    External::Table(_idx) => {
-                        if let ImportType::Table(_n, _f) = self {
-                            ImportStatus::Good
-                        } else {
-                            ImportStatus::Malformed
-                        }
+                    (ImportType::Memory(_, _, expected), External::Memory(actual)) => {
+                        expected == actual
                     }
+                    (ImportType::Table(_, _), External::Table(_)) => true,
+                    _ => false,
+                };
+                if good {
+                    ImportStatus::Good
+                } else {
+                    ImportStatus::Malformed
                 }
             } else {
                 ImportStatus::NotFound
@@ -240,85 +231,18 @@ impl<'a> ImportCheck for ImportType<'a> {
     }
 }
 
-fn has_global_import(section: &ImportSection, namespace: &str, field: &str) -> bool {
-    if let Some(import) = section
-        .entries()
-        .iter()
-        .find(|e| e.module() == namespace && e.field() == field)
-    {
-        match import.external() {
-            External::Global(_globaltype) => true,
-            _ => false,
-        }
-    } else {
-        false
-    }
-}
-
-fn has_memory_import(section: &ImportSection, namespace: &str, field: &str) -> bool {
-    if let Some(import) = section
-        .entries()
-        .iter()
-        .find(|e| e.module() == namespace && e.field() == field)
-    {
-        match import.external() {
-            External::Memory(_memorytype) => true,
-            _ => false,
-        }
-    } else {
-        false
-    }
-}
-
-fn has_table_import(section: &ImportSection, namespace: &str, field: &str) -> bool {
-    if let Some(import) = section
-        .entries()
-        .iter()
-        .find(|e| e.module() == namespace && e.field() == field)
-    {
-        match import.external() {
-            External::Table(_tabletype) => true,
-            _ => false,
-        }
-    } else {
-        false
-    }
-}
-
-fn has_func_import(module: &Module, namespace: &str, field: &str, sig: &FunctionType) -> bool {
-    if let Some(section) = module.import_section() {
-        if let Some(import) = section
-            .entries()
-            .iter()
-            .find(|e| e.module() == namespace && e.field() == field)
-        {
-            match import.external() {
-                External::Function(index) => {
-                    imported_func_sig_by_index(module, *index as usize) == *sig
-                }
-                _ => false,
-            }
-        } else {
-            false
-        }
-    } else {
-        false
-    }
-}
-
-/// Resolves an imported function's signature from its callable index.
-pub fn imported_func_sig_by_index(module: &Module, index: usize) -> FunctionType {
-    module.import_section().expect("No function section found");
-    let type_section = module.type_section().expect("No type section found");
-
-    match type_section.types()[index] {
-        Type::Function(ref func_type) => func_type.clone(),
+/// Resolves an imported function's signature from its type index. Returns None if the type
+/// index is out of range.
+pub fn imported_func_sig_by_index(module: &Module, index: usize) -> Option<FunctionType> {
+    match module.type_section()?.types().get(index)? {
+        Type::Function(func_type) => Some(func_type.clone()),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use parity_wasm::elements::ValueType;
+    use parity_wasm::builder;
+    use parity_wasm::elements::{GlobalType, ImportEntry, MemoryType, ValueType};
 
     use super::*;
 
@@ -394,6 +318,58 @@ mod tests {
         assert_eq!(false, result);
     }
 
+    #[test]
+    fn one_import_ok_wasi() {
+        // wast:
+        // (module
+        //   (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0c, 0x02, 0x60, 0x04, 0x7f,
+            0x7f, 0x7f, 0x7f, 0x01, 0x7f, 0x60, 0x00, 0x00, 0x02, 0x23, 0x01, 0x16, 0x77, 0x61,
+            0x73, 0x69, 0x5f, 0x73, 0x6e, 0x61, 0x70, 0x73, 0x68, 0x6f, 0x74, 0x5f, 0x70, 0x72,
+            0x65, 0x76, 0x69, 0x65, 0x77, 0x31, 0x08, 0x66, 0x64, 0x5f, 0x77, 0x72, 0x69, 0x74,
+            0x65, 0x00, 0x00, 0x03, 0x02, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11,
+            0x02, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72,
+            0x79, 0x02, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyImports::with_preset("wasi_snapshot_preview1").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn one_import_bad_arity_wasi() {
+        // wast:
+        // (module
+        //   (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32) (result i32)))
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0b, 0x02, 0x60, 0x03, 0x7f,
+            0x7f, 0x7f, 0x01, 0x7f, 0x60, 0x00, 0x00, 0x02, 0x23, 0x01, 0x16, 0x77, 0x61, 0x73,
+            0x69, 0x5f, 0x73, 0x6e, 0x61, 0x70, 0x73, 0x68, 0x6f, 0x74, 0x5f, 0x70, 0x72, 0x65,
+            0x76, 0x69, 0x65, 0x77, 0x31, 0x08, 0x66, 0x64, 0x5f, 0x77, 0x72, 0x69, 0x74, 0x65,
+            0x00, 0x00, 0x03, 0x02, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02,
+            0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79,
+            0x02, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyImports::with_preset("wasi_snapshot_preview1").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
     #[test]
     fn one_import_bad_namespace_ewasm() {
         // wast:
@@ -727,4 +703,313 @@ mod tests {
 
         assert_eq!(true, result);
     }
+
+    #[test]
+    fn from_module_round_trip() {
+        // wast:
+        // (module
+        //   (import "ethereum" "useGas" (func (param i64)))
+        //   (import "env" "memory" (memory 1))
+        //   (global (mut i32) (i32.const 0))
+        //   (export "main" (func 1))
+        //   (export "useGas" (func 0))
+        //   (export "memory" (memory 0))
+        //   (export "counter" (global 0))
+        //   (func)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7e,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x21, 0x02, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65,
+            0x75, 0x6d, 0x06, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x00, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x01, 0x03, 0x02, 0x01,
+            0x01, 0x06, 0x06, 0x01, 0x7f, 0x01, 0x41, 0x00, 0x0b, 0x07, 0x24, 0x04, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x01, 0x06, 0x75, 0x73, 0x65, 0x47, 0x61, 0x73, 0x00, 0x00,
+            0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x07, 0x63, 0x6f, 0x75, 0x6e,
+            0x74, 0x65, 0x72, 0x03, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let spec = VerifyImports::from_module(&module).unwrap();
+        assert_eq!(spec.list.entries().len(), 2);
+        assert_eq!(true, spec.validate(&module).unwrap());
+
+        // The same spec must reject a module with a different import surface.
+        let other = Module::from_bytes([0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(false, spec.validate(&other).unwrap());
+    }
+
+    #[test]
+    fn from_module_bad_type_index() {
+        let module = builder::module()
+            .with_import(ImportEntry::new(
+                "ethereum".to_string(),
+                "useGas".to_string(),
+                External::Function(3),
+            ))
+            .build();
+        assert_eq!(
+            VerifyImports::from_module(&module).err(),
+            Some(ModuleError::NotFound)
+        );
+    }
+
+    #[test]
+    fn verbose_bad_sig_malformed() {
+        // wast:
+        // (module
+        //   (import "ethereum" "storageStore" (func $storageStore (param i32)))
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x19, 0x01, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65,
+            0x75, 0x6d, 0x0c, 0x73, 0x74, 0x6f, 0x72, 0x61, 0x67, 0x65, 0x53, 0x74, 0x6f, 0x72,
+            0x65, 0x00, 0x00, 0x03, 0x02, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11,
+            0x02, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72,
+            0x79, 0x02, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, 0x00, 0x1c, 0x04, 0x6e, 0x61,
+            0x6d, 0x65, 0x01, 0x15, 0x02, 0x00, 0x0c, 0x73, 0x74, 0x6f, 0x72, 0x61, 0x67, 0x65,
+            0x53, 0x74, 0x6f, 0x72, 0x65, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyImports::with_preset("ewasm").unwrap();
+        let report = checker.validate_verbose(&module).unwrap();
+        assert_eq!(report.len(), checker.list.entries().len());
+        for (namespace, field, status) in report {
+            assert_eq!(namespace, "ethereum");
+            if field == "storageStore" {
+                assert_eq!(status, ImportStatus::Malformed);
+            } else {
+                assert_eq!(status, ImportStatus::NotFound);
+            }
+        }
+    }
+
+    #[test]
+    fn verbose_unlisted() {
+        // wast:
+        // (module
+        //   (import "ethereum" "storageStore" (func $storageStore (param i32 i32)))
+        //   (import "ethereum" "foo" (func $foo))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x09, 0x02, 0x60, 0x02, 0x7f,
+            0x7f, 0x00, 0x60, 0x00, 0x00, 0x02, 0x28, 0x02, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72,
+            0x65, 0x75, 0x6d, 0x0c, 0x73, 0x74, 0x6f, 0x72, 0x61, 0x67, 0x65, 0x53, 0x74, 0x6f,
+            0x72, 0x65, 0x00, 0x00, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72, 0x65, 0x75, 0x6d, 0x03,
+            0x66, 0x6f, 0x6f, 0x00, 0x01, 0x00, 0x1b, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x01, 0x14,
+            0x02, 0x00, 0x0c, 0x73, 0x74, 0x6f, 0x72, 0x61, 0x67, 0x65, 0x53, 0x74, 0x6f, 0x72,
+            0x65, 0x01, 0x03, 0x66, 0x6f, 0x6f,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut checker = VerifyImports::with_preset("ewasm").unwrap();
+        let report = checker.validate_verbose(&module).unwrap();
+        assert!(report.contains(&(
+            "ethereum".to_string(),
+            "storageStore".to_string(),
+            ImportStatus::Good
+        )));
+        assert_eq!(
+            report.last(),
+            Some(&(
+                "ethereum".to_string(),
+                "foo".to_string(),
+                ImportStatus::Unlisted
+            ))
+        );
+        assert_eq!(false, checker.validate(&module).unwrap());
+
+        checker.set_allow_unlisted(true);
+        let report = checker.validate_verbose(&module).unwrap();
+        assert!(report
+            .iter()
+            .all(|(_, _, status)| *status != ImportStatus::Unlisted));
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn mutable_global_malformed() {
+        // wast:
+        // (module
+        //   (import "env" "gasCounter" (global (mut i64)))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x13, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x0a, 0x67, 0x61, 0x73, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x65, 0x72, 0x03, 0x7e,
+            0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut checker = VerifyImports {
+            list: ImportList::with_entries(vec![ImportType::Global(
+                "env",
+                "gasCounter",
+                GlobalType::new(ValueType::I64, false),
+            )]),
+            require_all: true,
+            allow_unlisted: false,
+        };
+        let report = checker.validate_verbose(&module).unwrap();
+        assert_eq!(
+            report,
+            vec![(
+                "env".to_string(),
+                "gasCounter".to_string(),
+                ImportStatus::Malformed
+            )]
+        );
+        assert_eq!(false, checker.validate(&module).unwrap());
+
+        checker.list = ImportList::with_entries(vec![ImportType::Global(
+            "env",
+            "gasCounter",
+            GlobalType::new(ValueType::I64, true),
+        )]);
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn shared_memory_malformed() {
+        // wast:
+        // (module
+        //   (import "env" "memory" (memory 1 1 shared))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x02, 0x10, 0x01, 0x03, 0x65, 0x6e,
+            0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x03, 0x01, 0x01,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let mut checker = VerifyImports {
+            list: ImportList::with_entries(vec![ImportType::Memory(
+                "env",
+                "memory",
+                MemoryType::new(1, Some(1)),
+            )]),
+            require_all: true,
+            allow_unlisted: false,
+        };
+        assert_eq!(false, checker.validate(&module).unwrap());
+
+        let mut shared = MemoryType::new(1, Some(1));
+        shared.set_shared(true);
+        checker.list = ImportList::with_entries(vec![ImportType::Memory("env", "memory", shared)]);
+        assert_eq!(true, checker.validate(&module).unwrap());
+    }
+
+    #[test]
+    fn state_root_length_eth2_v2() {
+        // wast:
+        // (module
+        //   (import "eth2" "loadPreStateRoot" (func $loadPreStateRoot (param i32 i32)))
+        //   (import "eth2" "savePostStateRoot" (func $savePostStateRoot (param i32 i32)))
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x09, 0x02, 0x60, 0x02, 0x7f,
+            0x7f, 0x00, 0x60, 0x00, 0x00, 0x02, 0x32, 0x02, 0x04, 0x65, 0x74, 0x68, 0x32, 0x10,
+            0x6c, 0x6f, 0x61, 0x64, 0x50, 0x72, 0x65, 0x53, 0x74, 0x61, 0x74, 0x65, 0x52, 0x6f,
+            0x6f, 0x74, 0x00, 0x00, 0x04, 0x65, 0x74, 0x68, 0x32, 0x11, 0x73, 0x61, 0x76, 0x65,
+            0x50, 0x6f, 0x73, 0x74, 0x53, 0x74, 0x61, 0x74, 0x65, 0x52, 0x6f, 0x6f, 0x74, 0x00,
+            0x00, 0x03, 0x02, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04,
+            0x6d, 0x61, 0x69, 0x6e, 0x00, 0x02, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02,
+            0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyImports::with_preset("eth2_v2").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+
+        // The original preset expects the state root pointer only.
+        let checker = VerifyImports::with_preset("eth2").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn pwasm_imports_good() {
+        // wast:
+        // (module
+        //   (import "env" "storage_read" (func $storage_read (param i32 i32)))
+        //   (import "env" "ret" (func $ret (param i32 i32)))
+        //   (import "env" "blocknumber" (func $blocknumber (result i64)))
+        //   (memory 1)
+        //   (export "call" (func $call))
+        //   (export "memory" (memory 0))
+        //   (func $call)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0d, 0x03, 0x60, 0x02, 0x7f,
+            0x7f, 0x00, 0x60, 0x00, 0x01, 0x7e, 0x60, 0x00, 0x00, 0x02, 0x30, 0x03, 0x03, 0x65,
+            0x6e, 0x76, 0x0c, 0x73, 0x74, 0x6f, 0x72, 0x61, 0x67, 0x65, 0x5f, 0x72, 0x65, 0x61,
+            0x64, 0x00, 0x00, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x72, 0x65, 0x74, 0x00, 0x00, 0x03,
+            0x65, 0x6e, 0x76, 0x0b, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x6e, 0x75, 0x6d, 0x62, 0x65,
+            0x72, 0x00, 0x01, 0x03, 0x02, 0x01, 0x02, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11,
+            0x02, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x03, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72,
+            0x79, 0x02, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyImports::with_preset("pwasm").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn pwasm_bad_sig_malformed() {
+        // wast:
+        // (module
+        //   (import "env" "storage_read" (func $storage_read (param i32)))
+        //   (export "call" (func $call))
+        //   (func $call)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x60, 0x01, 0x7f,
+            0x00, 0x60, 0x00, 0x00, 0x02, 0x14, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x0c, 0x73, 0x74,
+            0x6f, 0x72, 0x61, 0x67, 0x65, 0x5f, 0x72, 0x65, 0x61, 0x64, 0x00, 0x00, 0x03, 0x02,
+            0x01, 0x01, 0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x01, 0x0a, 0x04,
+            0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyImports::with_preset("pwasm").unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+
+    #[test]
+    fn opaque_module() {
+        // wast:
+        // (module
+        //   (memory 1)
+        //   (export "main" (func $main))
+        //   (export "memory" (memory 0))
+        //   (func $main)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x04, 0x6d,
+            0x61, 0x69, 0x6e, 0x00, 0x00, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyImports::with_preset("ewasm").unwrap();
+        assert_eq!(checker.id(), "verifyimports");
+
+        let opaque: &dyn ChiselModule<ObjectReference = &dyn ModuleValidator> =
+            &checker as &dyn ChiselModule<ObjectReference = &dyn ModuleValidator>;
+        assert_eq!(opaque.kind(), ModuleKind::Validator);
+
+        let as_trait: &dyn ModuleValidator = opaque.as_abstract();
+        assert_eq!(as_trait.validate(&module), Ok(true));
+    }
 }
@@ -0,0 +1,161 @@
+//! Validator restricting function bodies to an allowlist of instructions.
+//!
+//! This module is only available through the library and is not registered in the chisel driver.
+//! Its allowlist is a set of `Instruction` discriminants, which has no textual form to give in a
+//! configuration file; `checkopcodes` covers the configurable restrictions by proposal instead.
+
+use std::collections::{HashMap, HashSet};
+use std::mem::{discriminant, Discriminant};
+
+use parity_wasm::elements::{Instruction, Module};
+
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleValidator};
+
+/// Enum representing which instructions are accepted in function bodies.
+pub enum Filter {
+    /// Accept every instruction.
+    NoFilter,
+    /// Accept only instructions of the listed kinds. Immediates are not compared, so
+    /// `discriminant(&Instruction::I32Const(0))` allows every `i32.const`.
+    AllowList(HashSet<Discriminant<Instruction>>),
+}
+
+/// Struct on which ModuleValidator is implemented.
+pub struct VerifyInstructions {
+    filter: Filter,
+}
+
+impl VerifyInstructions {
+    pub fn new(filter: Filter) -> Self {
+        VerifyInstructions { filter }
+    }
+
+    /// Checks that every instruction passes the filter.
+    fn check_instructions(&self, instructions: &[Instruction]) -> bool {
+        match &self.filter {
+            Filter::NoFilter => true,
+            Filter::AllowList(allowed) => instructions
+                .iter()
+                .all(|instruction| allowed.contains(&discriminant(instruction))),
+        }
+    }
+}
+
+impl<'a> ChiselModule<'a> for VerifyInstructions {
+    type ObjectReference = &'a dyn ModuleValidator;
+
+    fn id(&'a self) -> String {
+        "verifyinstructions".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Validator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(VerifyInstructions {
+            filter: Filter::NoFilter,
+        })
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleValidator for VerifyInstructions {
+    fn validate(&self, module: &Module) -> Result<bool, ModuleError> {
+        if let Some(code) = module.code_section() {
+            Ok(code
+                .bodies()
+                .iter()
+                .all(|body| self.check_instructions(body.code().elements())))
+        } else {
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allow_list(instructions: &[Instruction]) -> Filter {
+        Filter::AllowList(instructions.iter().map(discriminant).collect())
+    }
+
+    #[test]
+    fn no_filter_good() {
+        // wast:
+        // (module
+        //   (func (result i64)
+        //     i64.const 1
+        //     i64.const 2
+        //     i64.add)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7e, 0x03, 0x02, 0x01, 0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x42, 0x01, 0x42, 0x02,
+            0x7c, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyInstructions::with_defaults().unwrap();
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn allow_list_good() {
+        // wast:
+        // (module
+        //   (func (result i64)
+        //     i64.const 1
+        //     i64.const 2
+        //     i64.add)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7e, 0x03, 0x02, 0x01, 0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x42, 0x01, 0x42, 0x02,
+            0x7c, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyInstructions::new(allow_list(&[
+            Instruction::I64Const(0),
+            Instruction::I64Add,
+            Instruction::End,
+        ]));
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(true, result);
+    }
+
+    #[test]
+    fn allow_list_rejects_opcode() {
+        // wast:
+        // (module
+        //   (func (result i64)
+        //     i64.const 1
+        //     i64.const 2
+        //     i64.add)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01,
+            0x7e, 0x03, 0x02, 0x01, 0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x42, 0x01, 0x42, 0x02,
+            0x7c, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let checker = VerifyInstructions::new(allow_list(&[
+            Instruction::I64Const(0),
+            Instruction::I64Sub,
+            Instruction::End,
+        ]));
+        let result = checker.validate(&module).unwrap();
+        assert_eq!(false, result);
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{
+    FuncBody, FunctionType, ImportCountType, Instruction, Instructions, Internal, Module,
+};
+
+use super::funcindex::append_function;
+use super::typeindex::signature_type_ref;
+use super::verifyexports::func_sig_by_index;
+use super::{ChiselModule, ModuleError, ModuleKind, ModuleTranslator};
+
+/// Name given to the original `main` in the name section.
+const INNER_NAME: &str = "__main_inner";
+
+/// Struct on which ModuleTranslator is implemented.
+/// Wraps the exported `main` in a new `() -> ()` function which calls it, and exports the wrapper
+/// as `main` instead. This gives trap-handling instrumentation a single function to target. If
+/// the module has a name section, the original function is renamed to `__main_inner`. A `main`
+/// which already only calls another `() -> ()` function is left unchanged.
+pub struct WrapMain {}
+
+impl<'a> ChiselModule<'a> for WrapMain {
+    type ObjectReference = &'a dyn ModuleTranslator;
+
+    fn id(&'a self) -> String {
+        "wrapmain".to_string()
+    }
+
+    fn kind(&'a self) -> ModuleKind {
+        ModuleKind::Translator
+    }
+
+    fn as_abstract(&'a self) -> Self::ObjectReference {
+        self as Self::ObjectReference
+    }
+
+    fn with_defaults() -> Result<Self, ModuleError> {
+        Ok(WrapMain {})
+    }
+
+    fn with_config(_config: &HashMap<String, String>) -> Result<Self, ModuleError> {
+        Err(ModuleError::NotSupported)
+    }
+}
+
+impl ModuleTranslator for WrapMain {
+    fn translate_inplace(&self, module: &mut Module) -> Result<bool, ModuleError> {
+        wrap_main(module)
+    }
+
+    fn translate(&self, module: &Module) -> Result<Option<Module>, ModuleError> {
+        let mut ret = module.clone();
+        if wrap_main(&mut ret)? {
+            Ok(Some(ret))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the export position and function index of `main`.
+fn main_export(module: &Module) -> Option<(usize, u32)> {
+    let section = module.export_section()?;
+    for (position, entry) in section.entries().iter().enumerate() {
+        if let Internal::Function(index) = entry.internal() {
+            if entry.field() == "main" {
+                return Some((position, *index));
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if the function has the shape of a wrapper produced by this translator: a body
+/// without locals which only calls another `() -> ()` function and returns. The name section is
+/// not consulted, as it may be absent or stripped after wrapping.
+fn is_wrapper(module: &Module, index: u32) -> bool {
+    let imports_len = module.import_count(ImportCountType::Function) as u32;
+    if index < imports_len {
+        return false;
+    }
+    let body = module
+        .code_section()
+        .and_then(|code| code.bodies().get((index - imports_len) as usize));
+    if let Some(body) = body {
+        if let [Instruction::Call(inner), Instruction::End] = body.code().elements() {
+            return body.locals().is_empty()
+                && *inner != index
+                && func_sig_by_index(module, *inner)
+                    .map_or(false, |signature| *signature == FunctionType::default());
+        }
+    }
+    false
+}
+
+fn wrap_main(module: &mut Module) -> Result<bool, ModuleError> {
+    let (position, inner) = match main_export(module) {
+        Some(main) => main,
+        None => return Ok(false),
+    };
+
+    if is_wrapper(module, inner) {
+        return Ok(false);
+    }
+
+    let signature = func_sig_by_index(module, inner).ok_or(ModuleError::NotFound)?;
+    if *signature != FunctionType::default() {
+        return Err(ModuleError::Custom(
+            "main must have the signature () -> ()".to_string(),
+        ));
+    }
+
+    let type_ref = signature_type_ref(module, FunctionType::default());
+    let wrapper = append_function(
+        module,
+        type_ref,
+        FuncBody::new(
+            vec![],
+            Instructions::new(vec![Instruction::Call(inner), Instruction::End]),
+        ),
+    );
+
+    *module
+        .export_section_mut()
+        .expect("Export section exists")
+        .entries_mut()[position]
+        .internal_mut() = Internal::Function(wrapper);
+
+    if let Some(functions) = module
+        .names_section_mut()
+        .and_then(|names| names.functions_mut().as_mut())
+    {
+        functions.names_mut().insert(inner, INNER_NAME.to_string());
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::{FunctionNameSubsection, NameSection, Section};
+
+    use super::*;
+
+    #[test]
+    fn main_wrapped() {
+        // wast:
+        // (module
+        //   (import "ethereum" "finish" (func (param i32 i32)))
+        //   (memory (export "memory") 1)
+        //   (func (export "main")
+        //     i32.const 0
+        //     i32.const 0
+        //     call 0)
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x09, 0x02, 0x60, 0x02, 0x7f,
+            0x7f, 0x00, 0x60, 0x00, 0x00, 0x02, 0x13, 0x01, 0x08, 0x65, 0x74, 0x68, 0x65, 0x72,
+            0x65, 0x75, 0x6d, 0x06, 0x66, 0x69, 0x6e, 0x69, 0x73, 0x68, 0x00, 0x00, 0x03, 0x02,
+            0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x06, 0x6d, 0x65, 0x6d,
+            0x6f, 0x72, 0x79, 0x02, 0x00, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x01, 0x0a, 0x0a,
+            0x01, 0x08, 0x00, 0x41, 0x00, 0x41, 0x00, 0x10, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let translator = WrapMain::with_defaults().unwrap();
+        let did_change = translator.translate_inplace(&mut module).unwrap();
+        assert_eq!(true, did_change);
+
+        let (_, wrapper) = main_export(&module).unwrap();
+        assert_eq!(wrapper, 2);
+        assert_eq!(
+            *func_sig_by_index(&module, wrapper).unwrap(),
+            FunctionType::default()
+        );
+        let body = &module.code_section().unwrap().bodies()[1];
+        assert_eq!(
+            body.code().elements(),
+            &[Instruction::Call(1), Instruction::End][..]
+        );
+
+        assert!(walrus::Module::from_buffer(&module.to_bytes().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn inner_renamed_once() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        let mut functions = FunctionNameSubsection::default();
+        functions.names_mut().insert(0, "main".to_string());
+        module
+            .sections_mut()
+            .push(Section::Name(NameSection::new(None, Some(functions), None)));
+
+        let translator = WrapMain::with_defaults().unwrap();
+        assert_eq!(true, translator.translate_inplace(&mut module).unwrap());
+        let functions = module.names_section().unwrap().functions().unwrap();
+        assert_eq!(functions.names().get(0).unwrap(), INNER_NAME);
+
+        // Already wrapped.
+        assert_eq!(false, translator.translate_inplace(&mut module).unwrap());
+    }
+
+    #[test]
+    fn wrapped_once_without_names() {
+        // wast:
+        // (module
+        //   (func (export "main"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00,
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let mut module = Module::from_bytes(&wasm).unwrap();
+        assert!(module.names_section().is_none());
+
+        let translator = WrapMain::with_defaults().unwrap();
+        assert_eq!(true, translator.translate_inplace(&mut module).unwrap());
+        assert_eq!(false, translator.translate_inplace(&mut module).unwrap());
+
+        assert_eq!(module.code_section().unwrap().bodies().len(), 2);
+        assert_eq!(main_export(&module), Some((0, 1)));
+    }
+
+    #[test]
+    fn main_wrong_signature() {
+        // wast:
+        // (module
+        //   (func (export "main") (param i32))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x01, 0x7f,
+            0x00, 0x03, 0x02, 0x01, 0x00, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00,
+            0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = WrapMain::with_defaults().unwrap();
+        assert!(translator.translate(&module).is_err());
+    }
+
+    #[test]
+    fn no_main() {
+        // wast:
+        // (module
+        //   (func (export "other"))
+        // )
+        let wasm: Vec<u8> = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            0x03, 0x02, 0x01, 0x00, 0x07, 0x09, 0x01, 0x05, 0x6f, 0x74, 0x68, 0x65, 0x72, 0x00,
+            0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+        ];
+
+        let module = Module::from_bytes(&wasm).unwrap();
+        let translator = WrapMain::with_defaults().unwrap();
+        assert!(translator.translate(&module).unwrap().is_none());
+    }
+}